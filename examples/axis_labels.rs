@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+use bevy_plot::*;
+
+fn main() {
+    App::new()
+        .insert_resource(WindowDescriptor {
+            width: 800.,
+            height: 600.,
+            ..Default::default()
+        })
+        .insert_resource(ClearColor(Color::rgb(0.9, 0.9, 0.9)))
+        .add_plugins(DefaultPlugins)
+        .add_plugin(PlotPlugin)
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut plots: ResMut<Assets<Plot>>,
+    asset_server: Res<AssetServer>,
+    mut maybe_font: ResMut<TickLabelFont>,
+) {
+    commands.spawn_bundle(OrthographicCameraBundle::new_2d());
+    let font: Handle<Font> = asset_server.load("fonts/Roboto-Bold.ttf");
+    maybe_font.maybe_font = Some(font);
+
+    let mut plot = Plot::default();
+
+    // leave some room around the canvas for the title and the axis labels
+    plot.canvas_size = Vec2::new(680.0, 460.0);
+    plot.canvas_position = Vec2::new(15.0, 0.0);
+
+    plot.title = Some("Damped oscillation".to_string());
+    plot.x_label = Some("time (s)".to_string());
+    plot.y_label = Some("displacement (m)".to_string());
+
+    plot.plotopt_func(
+        |x, _t| 0.5 + 0.4 * (x * 20.0).cos() * (-3.0 * x).exp(),
+        vec![Opt::Color(Color::rgb(0.33, 0.56, 0.82))],
+    );
+
+    let plot_handle = plots.add(plot.clone());
+    commands.spawn().insert(plot_handle);
+}
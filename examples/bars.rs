@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+use bevy_plot::*;
+
+fn main() {
+    App::new()
+        .insert_resource(WindowDescriptor {
+            width: 800.,
+            height: 600.,
+            ..Default::default()
+        })
+        .add_plugins(DefaultPlugins)
+        .add_plugin(PlotPlugin)
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut plots: ResMut<Assets<Plot>>,
+    asset_server: Res<AssetServer>,
+    mut maybe_font: ResMut<TickLabelFont>,
+) {
+    commands.spawn_bundle(OrthographicCameraBundle::new_2d());
+    let font: Handle<Font> = asset_server.load("fonts/Roboto-Bold.ttf");
+    maybe_font.maybe_font = Some(font);
+
+    let mut plot = Plot::default();
+    plot.canvas_size = Vec2::new(790.0, 590.0);
+
+    // completion rate of each level for three difficulty settings
+    let levels = ["Forest", "Caves", "Castle", "Swamp", "Summit"];
+    let easy = vec![0.98, 0.91, 0.85, 0.80, 0.64];
+    let normal = vec![0.95, 0.82, 0.70, 0.61, 0.42];
+    let hard = vec![0.88, 0.65, 0.47, 0.33, 0.18];
+
+    // Use Opt::BarLayout(BarLayout::Stacked) for stacked bars and Opt::Horizontal(true)
+    // for horizontal bars.
+    plot.plotopt_bars(
+        levels,
+        vec![easy, normal, hard],
+        vec![Opt::BarLayout(BarLayout::Grouped), Opt::BarWidth(0.8)],
+    );
+
+    let plot_handle = plots.add(plot.clone());
+    commands.spawn().insert(plot_handle);
+}
@@ -0,0 +1,61 @@
+use bevy::prelude::*;
+use bevy_plot::*;
+
+use rand::Rng;
+
+fn main() {
+    App::new()
+        .insert_resource(WindowDescriptor {
+            width: 800.,
+            height: 600.,
+            ..Default::default()
+        })
+        .add_plugins(DefaultPlugins)
+        .add_plugin(PlotPlugin)
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut plots: ResMut<Assets<Plot>>,
+    asset_server: Res<AssetServer>,
+    mut maybe_font: ResMut<TickLabelFont>,
+) {
+    commands.spawn_bundle(OrthographicCameraBundle::new_2d());
+    let font: Handle<Font> = asset_server.load("fonts/Roboto-Bold.ttf");
+    maybe_font.maybe_font = Some(font);
+
+    let mut plot = Plot::default();
+    plot.canvas_size = Vec2::new(790.0, 590.0);
+
+    let mut rng = rand::thread_rng();
+    let points = (0..60)
+        .map(|_| Vec2::new(rng.gen_range(0.05..0.95), rng.gen_range(0.05..0.95)))
+        .collect::<Vec<Vec2>>();
+
+    // bubble chart: the size of each marker depends on a third variable, and its color on a fourth
+    let sizes = points
+        .iter()
+        .map(|_| rng.gen_range(0.5..2.5))
+        .collect::<Vec<f32>>();
+    let values = points
+        .iter()
+        .map(|p| (p.x * 6.0).sin() + p.y)
+        .collect::<Vec<f32>>();
+
+    plot.plotopt(
+        points,
+        vec![
+            Opt::LineStyle(LineStyle::None),
+            Opt::MarkerStyle(MarkerStyle::Circle),
+            Opt::MarkerSizes(sizes),
+            Opt::MarkerValues(values),
+            Opt::Colormap(Colormap::Magma),
+            Opt::Contour(true),
+        ],
+    );
+
+    let plot_handle = plots.add(plot.clone());
+    commands.spawn().insert(plot_handle);
+}
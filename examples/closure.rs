@@ -0,0 +1,77 @@
+use bevy::prelude::*;
+use bevy_plot::*;
+
+use std::sync::{Arc, RwLock};
+
+fn main() {
+    App::new()
+        .insert_resource(WindowDescriptor {
+            width: 800.,
+            height: 600.,
+            ..Default::default()
+        })
+        .add_plugins(DefaultPlugins)
+        .add_plugin(PlotPlugin)
+        .add_startup_system(setup)
+        .add_system(change_frequency)
+        .run();
+}
+
+// Parameter shared between the app and the closure given to plot_func
+struct Frequency(Arc<RwLock<f32>>);
+
+// Press the Up and Down arrows to change the frequency of the curve
+fn change_frequency(
+    frequency: Res<Frequency>,
+    keyboard_input: Res<Input<KeyCode>>,
+    query: Query<(&Handle<Plot>, &BezierCurveNumber)>,
+    mut event: EventWriter<SpawnBezierCurveEvent>,
+) {
+    let factor = if keyboard_input.just_pressed(KeyCode::Up) {
+        1.2
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        1.0 / 1.2
+    } else {
+        return;
+    };
+
+    *frequency.0.write().unwrap() *= factor;
+
+    // The curve is not animated, so it has to be redrawn by hand
+    for (plot_handle, curve_number) in query.iter() {
+        event.send(SpawnBezierCurveEvent {
+            plot_handle: plot_handle.clone(),
+            group_number: curve_number.0,
+        });
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    mut plots: ResMut<Assets<Plot>>,
+    asset_server: Res<AssetServer>,
+    mut maybe_font: ResMut<TickLabelFont>,
+) {
+    commands.spawn_bundle(OrthographicCameraBundle::new_2d());
+    let font: Handle<Font> = asset_server.load("fonts/Roboto-Bold.ttf");
+    maybe_font.maybe_font = Some(font);
+
+    let mut plot = Plot::default();
+    plot.canvas_size = Vec2::new(790.0, 590.0);
+    plot.set_bounds(Vec2::new(-1.0, -1.5), Vec2::new(1.0, 1.5));
+
+    // coefficients that would typically come from a config file
+    let amplitude = 1.2;
+    let frequency = Arc::new(RwLock::new(3.0));
+
+    let captured_frequency = frequency.clone();
+    plot.plotopt_func(
+        move |x, _t| amplitude * (*captured_frequency.read().unwrap() * x).sin(),
+        vec![Opt::Size(1.5), Opt::Color(Color::rgb(0.9, 0.5, 0.2))],
+    );
+
+    commands.insert_resource(Frequency(frequency));
+
+    let plot_handle = plots.add(plot.clone());
+    commands.spawn().insert(plot_handle);
+}
@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+use bevy_plot::*;
+
+use std::time::SystemTime;
+
+fn main() {
+    App::new()
+        .insert_resource(WindowDescriptor {
+            width: 800.,
+            height: 600.,
+            ..Default::default()
+        })
+        .insert_resource(ClearColor(Color::rgb(0.9, 0.9, 0.9)))
+        .add_plugins(DefaultPlugins)
+        .add_plugin(PlotPlugin)
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut plots: ResMut<Assets<Plot>>,
+    asset_server: Res<AssetServer>,
+    mut maybe_font: ResMut<TickLabelFont>,
+) {
+    commands.spawn_bundle(OrthographicCameraBundle::new_2d());
+    let font: Handle<Font> = asset_server.load("fonts/Roboto-Bold.ttf");
+    maybe_font.maybe_font = Some(font);
+
+    let now = unix_timestamp(SystemTime::now());
+
+    // values on the x axis are the seconds elapsed since the origin
+    let time_axis = AxisScale::DateTime { origin: now };
+
+    // one sample every 30 seconds over the last two hours
+    let timestamps = (0..240).map(|k| now - 7200.0 + 30.0 * k as f64);
+
+    let cpu_load = timestamps
+        .map(|ts| {
+            let t = time_axis.time_coordinate(ts);
+            Vec2::new(t, 50.0 + 20.0 * (t / 900.0).sin() + 5.0 * (t / 97.0).cos())
+        })
+        .collect::<Vec<Vec2>>();
+
+    let mut plot = Plot::default();
+    plot.canvas_size = Vec2::new(700.0, 480.0);
+    plot.title = Some("CPU load (UTC)".to_string());
+    plot.y_label = Some("load (%)".to_string());
+
+    plot.set_axis_scales(time_axis, AxisScale::Linear);
+
+    // zooming in and out switches between hours, minutes and seconds
+    plot.set_bounds(Vec2::new(-7500.0, 0.0), Vec2::new(300.0, 100.0));
+
+    plot.plotopt(cpu_load, vec![Opt::Color(Color::rgb(0.33, 0.56, 0.82))]);
+
+    let plot_handle = plots.add(plot.clone());
+    commands.spawn().insert(plot_handle);
+}
@@ -0,0 +1,73 @@
+use bevy::prelude::*;
+use bevy_plot::*;
+
+fn main() {
+    App::new()
+        .insert_resource(WindowDescriptor {
+            width: 800.,
+            height: 600.,
+            ..Default::default()
+        })
+        .add_plugins(DefaultPlugins)
+        .add_plugin(PlotPlugin)
+        .add_startup_system(setup)
+        .add_system(print_edits)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut maybe_font: ResMut<TickLabelFont>,
+) {
+    commands.spawn_bundle(OrthographicCameraBundle::new_2d());
+    let font: Handle<Font> = asset_server.load("fonts/Roboto-Bold.ttf");
+    maybe_font.maybe_font = Some(font);
+
+    let mut plot = Plot::default();
+    plot.canvas_size = Vec2::new(790.0, 590.0);
+    plot.title = Some("Difficulty curve".to_string());
+
+    // control points of a curve, kept sorted along x and snapped to the grid
+    let control_points = (0..6)
+        .map(|k| Vec2::new(k as f32 * 0.2, 0.2 + 0.1 * k as f32))
+        .collect::<Vec<Vec2>>();
+
+    plot.plotopt(
+        control_points,
+        vec![
+            Opt::Color(Color::rgb(0.33, 0.56, 0.82)),
+            Opt::MarkerStyle(MarkerStyle::Circle),
+            Opt::Draggable(DragConstraints {
+                monotonic_x: true,
+                snap_to_grid: true,
+                ..Default::default()
+            }),
+        ],
+    );
+
+    // these markers only move vertically
+    let thresholds = (0..6)
+        .map(|k| Vec2::new(k as f32 * 0.2, 0.9))
+        .collect::<Vec<Vec2>>();
+
+    plot.plotopt(
+        thresholds,
+        vec![
+            Opt::LineStyle(LineStyle::None),
+            Opt::MarkerStyle(MarkerStyle::Square),
+            Opt::Draggable(DragConstraints::y_only()),
+        ],
+    );
+
+    commands.spawn_plot(plot);
+}
+
+fn print_edits(mut data_edited_events: EventReader<PlotDataEdited>) {
+    for event in data_edited_events.iter() {
+        println!(
+            "marker #{} of group {} moved from {:?} to {:?}",
+            event.point_index, event.group_index, event.old_value, event.new_value
+        );
+    }
+}
@@ -0,0 +1,70 @@
+use bevy::prelude::*;
+use bevy_plot::*;
+
+fn main() {
+    App::new()
+        .insert_resource(WindowDescriptor {
+            width: 800.,
+            height: 600.,
+            ..Default::default()
+        })
+        .add_plugins(DefaultPlugins)
+        .add_plugin(PlotPlugin)
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut plots: ResMut<Assets<Plot>>,
+    asset_server: Res<AssetServer>,
+    mut maybe_font: ResMut<TickLabelFont>,
+) {
+    commands.spawn_bundle(OrthographicCameraBundle::new_2d());
+    let font: Handle<Font> = asset_server.load("fonts/Roboto-Bold.ttf");
+    maybe_font.maybe_font = Some(font);
+
+    let mut plot = Plot::default();
+    plot.canvas_size = Vec2::new(790.0, 590.0);
+
+    let xs = (0..12).map(|k| k as f32 * 0.5).collect::<Vec<f32>>();
+
+    // measurements with symmetric vertical errors
+    let measurements = xs
+        .iter()
+        .map(|x| Vec2::new(*x, (x * 0.8).sin() + 0.1 * (x * 7.0).cos()))
+        .collect::<Vec<Vec2>>();
+    let errors = xs.iter().map(|x| 0.1 + 0.03 * x).collect::<Vec<f32>>();
+
+    plot.plot_errorbars(
+        measurements,
+        ErrorBars::Symmetric(errors),
+        vec![
+            Opt::MarkerStyle(MarkerStyle::Circle),
+            Opt::MarkerColor(Color::rgb(0.33, 0.56, 0.82)),
+        ],
+    );
+
+    // line plot with asymmetric errors along both axes
+    let model = xs
+        .iter()
+        .map(|x| Vec2::new(*x, (x * 0.8).cos() - 1.5))
+        .collect::<Vec<Vec2>>();
+    let y_errors = xs.iter().map(|x| (0.05, 0.15 + 0.02 * x)).collect::<Vec<(f32, f32)>>();
+
+    plot.plotopt(
+        model,
+        vec![
+            Opt::Color(Color::rgb(0.9, 0.4, 0.3)),
+            Opt::MarkerStyle(MarkerStyle::Square),
+            Opt::MarkerColor(Color::rgb(0.9, 0.4, 0.3)),
+            Opt::XErr(ErrorBars::Symmetric(vec![0.1; xs.len()])),
+            Opt::YErr(ErrorBars::Asymmetric(y_errors)),
+            Opt::ErrorColor(Color::rgb(0.5, 0.2, 0.15)),
+            Opt::CapWidth(5.0),
+        ],
+    );
+
+    let plot_handle = plots.add(plot.clone());
+    commands.spawn().insert(plot_handle);
+}
@@ -0,0 +1,64 @@
+use bevy::prelude::*;
+use bevy_plot::*;
+
+fn main() {
+    App::new()
+        .insert_resource(WindowDescriptor {
+            width: 1000.,
+            height: 750.,
+            ..Default::default()
+        })
+        .insert_resource(ClearColor(Color::rgb(0.9, 0.9, 0.9)))
+        .add_plugins(DefaultPlugins)
+        .add_plugin(PlotPlugin)
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut plots: ResMut<Assets<Plot>>,
+    asset_server: Res<AssetServer>,
+    mut maybe_font: ResMut<TickLabelFont>,
+) {
+    commands.spawn_bundle(OrthographicCameraBundle::new_2d());
+    let font: Handle<Font> = asset_server.load("fonts/Roboto-Bold.ttf");
+    maybe_font.maybe_font = Some(font);
+
+    let mut sine = Plot::default();
+    sine.title = Some("sine".to_string());
+    sine.plotopt_func(
+        |x, _t| 0.5 + 0.4 * (x * 10.0).sin(),
+        vec![Opt::Color(Color::rgb(0.33, 0.56, 0.82))],
+    );
+
+    let mut cosine = Plot::default();
+    cosine.title = Some("cosine".to_string());
+    cosine.plotopt_func(
+        |x, _t| 0.5 + 0.4 * (x * 10.0).cos(),
+        vec![Opt::Color(Color::rgb(0.9, 0.4, 0.3))],
+    );
+
+    let mut product = Plot::default();
+    product.x_label = Some("x".to_string());
+    product.plotopt_func(
+        |x, _t| 0.5 + 0.4 * (x * 10.0).sin() * (x * 10.0).cos(),
+        vec![Opt::Color(Color::rgb(0.56, 0.7, 0.45))],
+    );
+
+    let mut figure = Figure::new(2, 2);
+    figure.title = Some("Trigonometric functions".to_string());
+
+    // panning or zooming any plot moves the x axis of the plots in the same column
+    figure.share_x = ShareAxis::Column;
+
+    figure.add(plots.add(sine), 0, 0);
+    figure.add(plots.add(cosine), 0, 1);
+    // the last plot spans both columns of the bottom row
+    figure.add_span(plots.add(product), 1, 0, 1, 2);
+
+    for cell in figure.cells.iter() {
+        commands.spawn().insert(cell.plot_handle.clone());
+    }
+    commands.spawn().insert(figure);
+}
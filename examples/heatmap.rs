@@ -0,0 +1,66 @@
+use bevy::prelude::*;
+use bevy_plot::*;
+
+fn main() {
+    App::new()
+        .insert_resource(WindowDescriptor {
+            width: 800.,
+            height: 600.,
+            ..Default::default()
+        })
+        .add_plugins(DefaultPlugins)
+        .add_plugin(PlotPlugin)
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut plots: ResMut<Assets<Plot>>,
+    asset_server: Res<AssetServer>,
+    mut maybe_font: ResMut<TickLabelFont>,
+) {
+    commands.spawn_bundle(OrthographicCameraBundle::new_2d());
+    let font: Handle<Font> = asset_server.load("fonts/Roboto-Bold.ttf");
+    maybe_font.maybe_font = Some(font);
+
+    let mut plot = Plot::default();
+    plot.canvas_size = Vec2::new(790.0, 590.0);
+    plot.set_bounds(Vec2::new(-3.0, -2.0), Vec2::new(3.0, 2.0));
+
+    // potential of two moving charges
+    plot.plotopt_heatmap(
+        |x, y, t| {
+            let q1 = Vec2::new(-1.0 + 0.5 * t.cos(), 0.5 * t.sin());
+            let q2 = Vec2::new(1.0, 0.0);
+            let p = Vec2::new(x, y);
+            1.0 / (p - q1).length().max(0.1) - 1.0 / (p - q2).length().max(0.1)
+        },
+        vec![
+            Opt::Colormap(Colormap::Coolwarm),
+            Opt::ValueRange(-3.0, 3.0),
+            Opt::Animate(true),
+        ],
+    );
+
+    // influence map sampled on a coarse grid, at the bottom right of the canvas
+    let (width, height) = (8, 5);
+    let values = (0..width * height)
+        .map(|k| {
+            let (i, j) = ((k % width) as f32, (k / width) as f32);
+            (i * 0.7).sin() * (j * 0.9).cos()
+        })
+        .collect::<Vec<f32>>();
+
+    plot.heatmap_grid(
+        values,
+        width,
+        height,
+        Vec2::new(0.5, -1.8),
+        Vec2::new(2.8, -0.4),
+        vec![Opt::Colormap(Colormap::Viridis), Opt::Interpolate(true)],
+    );
+
+    let plot_handle = plots.add(plot.clone());
+    commands.spawn().insert(plot_handle);
+}
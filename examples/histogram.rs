@@ -0,0 +1,55 @@
+use bevy::prelude::*;
+use bevy_plot::*;
+
+use rand::Rng;
+
+fn main() {
+    App::new()
+        .insert_resource(WindowDescriptor {
+            width: 800.,
+            height: 600.,
+            ..Default::default()
+        })
+        .add_plugins(DefaultPlugins)
+        .add_plugin(PlotPlugin)
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut plots: ResMut<Assets<Plot>>,
+    asset_server: Res<AssetServer>,
+    mut maybe_font: ResMut<TickLabelFont>,
+) {
+    commands.spawn_bundle(OrthographicCameraBundle::new_2d());
+    let font: Handle<Font> = asset_server.load("fonts/Roboto-Bold.ttf");
+    maybe_font.maybe_font = Some(font);
+
+    let mut plot = Plot::default();
+    plot.canvas_size = Vec2::new(790.0, 590.0);
+
+    // approximately normal samples, as the sum of uniform samples
+    let mut rng = rand::thread_rng();
+    let samples = (0..2000)
+        .map(|_| (0..12).map(|_| rng.gen::<f32>()).sum::<f32>() - 6.0)
+        .collect::<Vec<f32>>();
+
+    plot.plotopt_hist(
+        samples,
+        vec![
+            Opt::Bins(BinStrategy::FreedmanDiaconis),
+            Opt::Density(true),
+            Opt::Color(Color::rgba(0.33, 0.56, 0.82, 0.85)),
+        ],
+    );
+
+    // standard normal density for comparison
+    plot.plotopt_func(
+        |x, _t| (-x * x / 2.0).exp() / (2.0 * std::f32::consts::PI).sqrt(),
+        vec![Opt::Color(Color::rgb(0.9, 0.4, 0.3)), Opt::Size(1.0)],
+    );
+
+    let plot_handle = plots.add(plot.clone());
+    commands.spawn().insert(plot_handle);
+}
@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+use bevy_plot::*;
+
+fn main() {
+    App::new()
+        .insert_resource(WindowDescriptor {
+            width: 800.,
+            height: 600.,
+            ..Default::default()
+        })
+        .add_plugins(DefaultPlugins)
+        .add_plugin(PlotPlugin)
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut plots: ResMut<Assets<Plot>>,
+    asset_server: Res<AssetServer>,
+    mut maybe_font: ResMut<TickLabelFont>,
+) {
+    commands.spawn_bundle(OrthographicCameraBundle::new_2d());
+    let font: Handle<Font> = asset_server.load("fonts/Roboto-Bold.ttf");
+    maybe_font.maybe_font = Some(font);
+
+    let mut plot = Plot::default();
+    plot.canvas_size = Vec2::new(790.0, 590.0);
+    plot.set_bounds(Vec2::new(-2.0, -1.5), Vec2::new(2.0, 1.5));
+
+    // ellipse together with its interior
+    plot.plotopt_implicit(
+        |x, y, _t| x * x / 1.5 + y * y - 1.0,
+        vec![
+            Opt::Color(Color::rgb(0.9, 0.5, 0.2)),
+            Opt::Size(1.5),
+            Opt::FillColor(Color::rgba(0.9, 0.5, 0.2, 0.15)),
+        ],
+    );
+
+    // hyperbola
+    plot.plotopt_implicit(
+        |x, y, _t| x * x - y * y - 0.25,
+        vec![Opt::Color(Color::rgb(0.3, 0.6, 0.9))],
+    );
+
+    // constraint region y > sin(3x + t), without its boundary
+    plot.plotopt_implicit(
+        |x, y, t| (3.0 * x + t).sin() * 0.5 - y,
+        vec![
+            Opt::LineStyle(LineStyle::None),
+            Opt::FillColor(Color::rgba(0.4, 0.8, 0.4, 0.12)),
+            Opt::Animate(true),
+        ],
+    );
+
+    let plot_handle = plots.add(plot.clone());
+    commands.spawn().insert(plot_handle);
+}
@@ -0,0 +1,71 @@
+use bevy::prelude::*;
+use bevy_plot::*;
+
+fn main() {
+    App::new()
+        .insert_resource(WindowDescriptor {
+            width: 800.,
+            height: 600.,
+            ..Default::default()
+        })
+        .add_plugins(DefaultPlugins)
+        .add_plugin(PlotPlugin)
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut plots: ResMut<Assets<Plot>>,
+    asset_server: Res<AssetServer>,
+    mut maybe_font: ResMut<TickLabelFont>,
+) {
+    commands.spawn_bundle(OrthographicCameraBundle::new_2d());
+    let font: Handle<Font> = asset_server.load("fonts/Roboto-Bold.ttf");
+    maybe_font.maybe_font = Some(font);
+
+    let mut plot = Plot::default();
+    plot.canvas_size = Vec2::new(790.0, 590.0);
+
+    // the legend is placed in the corner that hides the least data
+    plot.legend.position = LegendPosition::Best;
+
+    let xs = (0..30).map(|k| k as f32 / 29.0).collect::<Vec<f32>>();
+
+    let measurements = xs
+        .iter()
+        .map(|x| Vec2::new(*x, 0.5 + 0.3 * (x * 6.0).sin()))
+        .collect::<Vec<Vec2>>();
+
+    // a line with markers makes a single entry
+    plot.plotopt(
+        measurements,
+        vec![
+            Opt::Label("measurements".to_string()),
+            Opt::Color(Color::rgb(0.33, 0.56, 0.82)),
+            Opt::MarkerStyle(MarkerStyle::Circle),
+            Opt::MarkerColor(Color::rgb(0.33, 0.56, 0.82)),
+        ],
+    );
+
+    plot.plotopt_func(
+        |x, _t| 0.5 + 0.3 * (x * 6.0).sin() * (-x).exp(),
+        vec![
+            Opt::Label("damped model".to_string()),
+            Opt::LineStyle(LineStyle::Dashed),
+            Opt::Color(Color::rgb(0.9, 0.4, 0.3)),
+        ],
+    );
+
+    plot.plotopt_func(
+        |x, _t| 0.2 * x,
+        vec![
+            Opt::Label("trend".to_string()),
+            Opt::LineStyle(LineStyle::Dotted),
+            Opt::Color(Color::rgb(0.56, 0.7, 0.45)),
+        ],
+    );
+
+    let plot_handle = plots.add(plot.clone());
+    commands.spawn().insert(plot_handle);
+}
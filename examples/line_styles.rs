@@ -0,0 +1,76 @@
+use bevy::prelude::*;
+use bevy_plot::*;
+
+fn main() {
+    App::new()
+        .insert_resource(WindowDescriptor {
+            width: 800.,
+            height: 600.,
+            ..Default::default()
+        })
+        .add_plugins(DefaultPlugins)
+        .add_plugin(PlotPlugin)
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut plots: ResMut<Assets<Plot>>,
+    asset_server: Res<AssetServer>,
+    mut maybe_font: ResMut<TickLabelFont>,
+) {
+    commands.spawn_bundle(OrthographicCameraBundle::new_2d());
+    let font: Handle<Font> = asset_server.load("fonts/Roboto-Bold.ttf");
+    maybe_font.maybe_font = Some(font);
+
+    let mut plot = Plot::default();
+    plot.canvas_size = Vec2::new(790.0, 590.0);
+
+    // segments: the dash pattern continues from one segment to the next
+    let xs = (0..40).map(|k| k as f32 * 0.025).collect::<Vec<f32>>();
+    let styles = [
+        LineStyle::Dashed,
+        LineStyle::Dotted,
+        LineStyle::DashDot,
+        LineStyle::DashDotDot,
+    ];
+
+    for (k, style) in styles.iter().enumerate() {
+        let ys = xs
+            .iter()
+            .map(|x| Vec2::new(*x, 0.1 + 0.15 * k as f32 + 0.05 * (x * 9.0).sin()))
+            .collect::<Vec<Vec2>>();
+
+        plot.plotopt(
+            ys,
+            vec![
+                Opt::LineStyle(style.clone()),
+                Opt::Size(1.5),
+                Opt::Color(Color::rgb(0.33, 0.56, 0.82)),
+            ],
+        );
+    }
+
+    // functions
+    plot.plotopt_func(
+        |x, _t| 0.75 + 0.1 * (x * 12.0).cos(),
+        vec![
+            Opt::LineStyle(LineStyle::Dashed),
+            Opt::DashLength(20.0),
+            Opt::Color(Color::rgb(0.9, 0.4, 0.3)),
+        ],
+    );
+
+    plot.plotopt_func(
+        |x, _t| 0.9 + 0.05 * (x * 6.0).sin(),
+        vec![
+            Opt::LineStyle(LineStyle::DashDot),
+            Opt::Size(2.0),
+            Opt::Color(Color::rgb(0.56, 0.7, 0.45)),
+        ],
+    );
+
+    let plot_handle = plots.add(plot.clone());
+    commands.spawn().insert(plot_handle);
+}
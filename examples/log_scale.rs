@@ -0,0 +1,53 @@
+use bevy::prelude::*;
+use bevy_plot::*;
+
+fn main() {
+    App::new()
+        .insert_resource(WindowDescriptor {
+            width: 800.,
+            height: 600.,
+            ..Default::default()
+        })
+        .add_plugins(DefaultPlugins)
+        .add_plugin(PlotPlugin)
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut plots: ResMut<Assets<Plot>>,
+    asset_server: Res<AssetServer>,
+    mut maybe_font: ResMut<TickLabelFont>,
+) {
+    commands.spawn_bundle(OrthographicCameraBundle::new_2d());
+    let font: Handle<Font> = asset_server.load("fonts/Roboto-Bold.ttf");
+    maybe_font.maybe_font = Some(font);
+
+    let mut plot = Plot::default();
+    plot.canvas_size = Vec2::new(790.0, 590.0);
+
+    // the axis scales must be set before the data, so that the bounds are computed
+    // in the right space
+    plot.set_axis_scales(AxisScale::Log10, AxisScale::Log10);
+
+    // a power law is a straight line on a log-log plot
+    let xs = (0..40)
+        .map(|i| 10_f32.powf(i as f32 / 10.0))
+        .map(|x| Vec2::new(x, 3.0 * x.powf(1.5)))
+        .collect::<Vec<Vec2>>();
+
+    plot.plotopt(
+        xs,
+        vec![
+            Opt::LineStyle(LineStyle::None),
+            Opt::MarkerStyle(MarkerStyle::Circle),
+            Opt::MarkerSize(0.5),
+        ],
+    );
+
+    plot.plot_func(|x, _t| 3.0 * x.powf(1.5));
+
+    let plot_handle = plots.add(plot.clone());
+    commands.spawn().insert(plot_handle);
+}
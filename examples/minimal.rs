@@ -9,10 +9,10 @@ fn main() {
         .run();
 }
 
-// If no fonts are loaded and put into the TickLabelFont resource,
+// If no font is given to the plot or put into the TickLabelFont resource,
 // the canvas will not include the tick labels. See the "markers" example
 // for an instance of loading a font.
-fn setup(mut commands: Commands, mut plots: ResMut<Assets<Plot>>) {
+fn setup(mut commands: Commands) {
     commands.spawn_bundle(OrthographicCameraBundle::new_2d());
 
     let mut plot = Plot::default();
@@ -26,6 +26,5 @@ fn setup(mut commands: Commands, mut plots: ResMut<Assets<Plot>>) {
 
     plot.plot(ys);
 
-    let plot_handle = plots.add(plot.clone());
-    commands.spawn().insert(plot_handle);
+    commands.spawn_plot(plot);
 }
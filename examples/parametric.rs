@@ -0,0 +1,54 @@
+use bevy::prelude::*;
+use bevy_plot::*;
+
+use std::f32::consts::PI;
+
+fn main() {
+    App::new()
+        .insert_resource(WindowDescriptor {
+            width: 800.,
+            height: 600.,
+            ..Default::default()
+        })
+        .add_plugins(DefaultPlugins)
+        .add_plugin(PlotPlugin)
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut plots: ResMut<Assets<Plot>>,
+    asset_server: Res<AssetServer>,
+    mut maybe_font: ResMut<TickLabelFont>,
+) {
+    commands.spawn_bundle(OrthographicCameraBundle::new_2d());
+    let font: Handle<Font> = asset_server.load("fonts/Roboto-Bold.ttf");
+    maybe_font.maybe_font = Some(font);
+
+    let mut plot = Plot::default();
+    plot.canvas_size = Vec2::new(790.0, 590.0);
+
+    // Lissajous figure with a slowly rotating phase
+    plot.plot_parametric(
+        |t, time| Vec2::new((3.0 * t + 0.5 * time).sin(), (2.0 * t).sin()),
+        (0.0, 2.0 * PI),
+        300,
+        vec![
+            Opt::Color(Color::rgb(0.9, 0.4, 0.3)),
+            Opt::Size(1.0),
+            Opt::Animate(true),
+        ],
+    );
+
+    // spiral trajectory
+    plot.plot_parametric(
+        |t, _time| Vec2::new(t * t.cos(), t * t.sin()) * 0.08,
+        (0.0, 6.0 * PI),
+        400,
+        vec![Opt::Color(Color::rgb(0.3, 0.6, 0.9)), Opt::Size(0.7)],
+    );
+
+    let plot_handle = plots.add(plot.clone());
+    commands.spawn().insert(plot_handle);
+}
@@ -0,0 +1,61 @@
+use bevy::prelude::*;
+use bevy_plot::*;
+
+use std::f32::consts::PI;
+
+fn main() {
+    App::new()
+        .insert_resource(WindowDescriptor {
+            width: 800.,
+            height: 600.,
+            ..Default::default()
+        })
+        .add_plugins(DefaultPlugins)
+        .add_plugin(PlotPlugin)
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut plots: ResMut<Assets<Plot>>,
+    asset_server: Res<AssetServer>,
+    mut maybe_font: ResMut<TickLabelFont>,
+) {
+    commands.spawn_bundle(OrthographicCameraBundle::new_2d());
+    let font: Handle<Font> = asset_server.load("fonts/Roboto-Bold.ttf");
+    maybe_font.maybe_font = Some(font);
+
+    let mut plot = Plot::default();
+    plot.canvas_size = Vec2::new(790.0, 590.0);
+    plot.tick_label_color = Color::WHITE;
+
+    // the data is now given as (θ, r) pairs
+    plot.set_polar(true);
+
+    // antenna pattern r = f(θ)
+    plot.plotopt_func(
+        |theta, _t| 0.2 + (2.0 * theta).cos().abs(),
+        vec![Opt::Color(Color::rgb(0.9, 0.5, 0.2)), Opt::Size(1.0)],
+    );
+
+    // measured points
+    let measurements = (0..24)
+        .map(|i| {
+            let theta = i as f32 * 2.0 * PI / 24.0;
+            Vec2::new(theta, 0.2 + (2.0 * theta).cos().abs() * 0.9)
+        })
+        .collect::<Vec<Vec2>>();
+
+    plot.plotopt(
+        measurements,
+        vec![
+            Opt::LineStyle(LineStyle::None),
+            Opt::MarkerStyle(MarkerStyle::Circle),
+            Opt::MarkerSize(0.6),
+        ],
+    );
+
+    let plot_handle = plots.add(plot.clone());
+    commands.spawn().insert(plot_handle);
+}
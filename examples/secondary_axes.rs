@@ -0,0 +1,75 @@
+use bevy::prelude::*;
+use bevy_plot::*;
+
+fn main() {
+    App::new()
+        .insert_resource(WindowDescriptor {
+            width: 800.,
+            height: 600.,
+            ..Default::default()
+        })
+        .insert_resource(ClearColor(Color::rgb(0.9, 0.9, 0.9)))
+        .add_plugins(DefaultPlugins)
+        .add_plugin(PlotPlugin)
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut plots: ResMut<Assets<Plot>>,
+    asset_server: Res<AssetServer>,
+    mut maybe_font: ResMut<TickLabelFont>,
+) {
+    commands.spawn_bundle(OrthographicCameraBundle::new_2d());
+    let font: Handle<Font> = asset_server.load("fonts/Roboto-Bold.ttf");
+    maybe_font.maybe_font = Some(font);
+
+    let mut plot = Plot::default();
+    plot.canvas_size = Vec2::new(640.0, 460.0);
+
+    plot.title = Some("Benchmark".to_string());
+    plot.x_label = Some("time (s)".to_string());
+    plot.y_label = Some("frames per second".to_string());
+    plot.secondary_y_label = Some("memory (MB)".to_string());
+
+    let times = (0..60).map(|k| k as f32).collect::<Vec<f32>>();
+
+    let fps = times
+        .iter()
+        .map(|t| Vec2::new(*t, 60.0 - 10.0 * (t / 8.0).sin().abs()))
+        .collect::<Vec<Vec2>>();
+
+    let memory = times
+        .iter()
+        .map(|t| Vec2::new(*t, 250.0 + 12.0 * t))
+        .collect::<Vec<Vec2>>();
+
+    plot.set_bounds(Vec2::new(-5.0, 40.0), Vec2::new(65.0, 65.0));
+
+    // the memory is read on the right axis
+    plot.set_secondary_y_bounds(0.0, 1200.0);
+
+    plot.plotopt(
+        fps,
+        vec![
+            Opt::Color(Color::rgb(0.33, 0.56, 0.82)),
+            Opt::Label("FPS".to_string()),
+        ],
+    );
+
+    plot.plotopt(
+        memory,
+        vec![
+            Opt::Color(Color::rgb(0.9, 0.4, 0.3)),
+            Opt::LineStyle(LineStyle::Dashed),
+            Opt::SecondaryY(true),
+            Opt::Label("memory".to_string()),
+        ],
+    );
+
+    plot.legend.position = LegendPosition::BottomRight;
+
+    let plot_handle = plots.add(plot.clone());
+    commands.spawn().insert(plot_handle);
+}
@@ -0,0 +1,85 @@
+use bevy::prelude::*;
+use bevy_plot::*;
+
+fn main() {
+    App::new()
+        .insert_resource(WindowDescriptor {
+            width: 800.,
+            height: 600.,
+            ..Default::default()
+        })
+        .insert_resource(ClearColor(Color::rgb(0.9, 0.9, 0.9)))
+        .add_plugins(DefaultPlugins)
+        .add_plugin(PlotPlugin)
+        .add_startup_system(setup)
+        .add_system(stream_samples)
+        .run();
+}
+
+struct StreamedPlot(Handle<Plot>);
+
+fn setup(
+    mut commands: Commands,
+    mut plots: ResMut<Assets<Plot>>,
+    asset_server: Res<AssetServer>,
+    mut maybe_font: ResMut<TickLabelFont>,
+) {
+    commands.spawn_bundle(OrthographicCameraBundle::new_2d());
+    let font: Handle<Font> = asset_server.load("fonts/Roboto-Bold.ttf");
+    maybe_font.maybe_font = Some(font);
+
+    let mut plot = Plot::default();
+    plot.canvas_size = Vec2::new(700.0, 480.0);
+    plot.title = Some("Live signal".to_string());
+    plot.x_label = Some("time (s)".to_string());
+
+    // the x axis keeps a range of 5 seconds and scrolls with the latest sample
+    plot.set_bounds(Vec2::new(-4.75, -1.5), Vec2::new(0.25, 1.5));
+    plot.follow_latest = true;
+
+    // the value axis stays fixed when panning and zooming with the mouse
+    plot.lock_pan = BVec2::new(false, true);
+    plot.lock_zoom = BVec2::new(false, true);
+
+    // only the last 600 samples are kept
+    plot.plotopt(
+        vec![Vec2::ZERO],
+        vec![Opt::Color(Color::rgb(0.33, 0.56, 0.82)), Opt::Capacity(600)],
+    );
+
+    // a sample of the noisy signal every 10 frames
+    plot.plotopt(
+        vec![Vec2::ZERO],
+        vec![
+            Opt::LineStyle(LineStyle::None),
+            Opt::MarkerStyle(MarkerStyle::Circle),
+            Opt::MarkerSize(0.4),
+            Opt::Capacity(60),
+        ],
+    );
+
+    let plot_handle = plots.add(plot.clone());
+    commands.spawn().insert(plot_handle.clone());
+    commands.insert_resource(StreamedPlot(plot_handle));
+}
+
+// Appends the latest samples without respawning the plot
+fn stream_samples(
+    time: Res<Time>,
+    streamed_plot: Res<StreamedPlot>,
+    mut plots: ResMut<Assets<Plot>>,
+    mut frame: Local<usize>,
+) {
+    if let Some(plot) = plots.get_mut(&streamed_plot.0) {
+        let t = time.seconds_since_startup() as f32;
+        let signal = (2.0 * t).sin() * (0.3 * t).cos();
+
+        plot.push_segment_points(0, &[Vec2::new(t, signal)]);
+
+        *frame += 1;
+        if *frame % 10 == 0 {
+            let noise = 0.2 * (37.0 * t).sin() * (23.0 * t).cos();
+            plot.push_marker_points(0, &[Vec2::new(t, signal + noise)]);
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use bevy::prelude::*;
+use bevy_plot::*;
+
+fn main() {
+    App::new()
+        .insert_resource(WindowDescriptor {
+            width: 800.,
+            height: 600.,
+            ..Default::default()
+        })
+        .add_plugins(DefaultPlugins)
+        .add_plugin(PlotPlugin)
+        .add_startup_system(setup)
+        .add_system(print_picked_elements)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut maybe_font: ResMut<TickLabelFont>,
+) {
+    commands.spawn_bundle(OrthographicCameraBundle::new_2d());
+    let font: Handle<Font> = asset_server.load("fonts/Roboto-Bold.ttf");
+    maybe_font.maybe_font = Some(font);
+
+    let mut plot = Plot::default();
+    plot.canvas_size = Vec2::new(790.0, 590.0);
+
+    // hovering within 10 pixels of a point shows its coordinates, group label and index
+    plot.show_tooltips = true;
+    plot.pick_radius = 10.0;
+
+    // a large scatter plot, searched through a spatial index
+    let scatter = (0..5000)
+        .map(|k| {
+            let t = k as f32 * 0.618;
+            let r = (k as f32 / 5000.0).sqrt() * 0.45;
+            Vec2::new(0.5 + r * t.cos(), 0.5 + r * t.sin())
+        })
+        .collect::<Vec<Vec2>>();
+
+    plot.plotopt(
+        scatter,
+        vec![
+            Opt::Label("samples".to_string()),
+            Opt::LineStyle(LineStyle::None),
+            Opt::MarkerStyle(MarkerStyle::Circle),
+            Opt::MarkerSize(0.3),
+        ],
+    );
+
+    // the points of a regular plot have tooltips as well
+    let xs = (0..40).map(|k| k as f32 / 39.0).collect::<Vec<f32>>();
+    let curve = xs
+        .iter()
+        .map(|x| Vec2::new(*x, 0.1 + 0.8 * x * x))
+        .collect::<Vec<Vec2>>();
+
+    plot.plotopt(
+        curve,
+        vec![
+            Opt::Label("fit".to_string()),
+            Opt::Color(Color::rgb(0.9, 0.4, 0.3)),
+        ],
+    );
+
+    plot.plotopt_func(
+        |x, _t| 0.9 - 0.3 * x,
+        vec![
+            Opt::Label("threshold".to_string()),
+            Opt::LineStyle(LineStyle::Dashed),
+        ],
+    );
+
+    commands.spawn_plot(plot);
+}
+
+fn print_picked_elements(
+    mut point_clicked_events: EventReader<PlotPointClicked>,
+    mut curve_hovered_events: EventReader<PlotCurveHovered>,
+) {
+    for event in point_clicked_events.iter() {
+        println!(
+            "clicked point #{} of the {:?} group {}: {:?}",
+            event.point_index, event.group_kind, event.group_index, event.value
+        );
+    }
+
+    for event in curve_hovered_events.iter() {
+        println!(
+            "hovering over the {:?} group {} at {:?}",
+            event.group_kind, event.group_index, event.position
+        );
+    }
+}
@@ -0,0 +1,252 @@
+use bevy::{
+    ecs::system::{lifetimeless::SRes, SystemParamItem},
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        mesh::Indices,
+        render_asset::{PrepareAssetError, RenderAsset},
+        render_resource::{
+            std140::{AsStd140, Std140},
+            *,
+        },
+        renderer::RenderDevice,
+    },
+    sprite::{Material2d, Material2dPipeline, MaterialMesh2dBundle, Mesh2dHandle},
+};
+
+use crate::plot::*;
+use crate::util::*;
+
+/// Component that serves as identification for the nth group of the `bar_groups` field of [`PlotData`].
+#[derive(Component)]
+pub(crate) struct BarGroupNumber(#[allow(dead_code)] pub usize);
+
+/// Bar shader parameters
+#[derive(TypeUuid, Debug, Clone, AsStd140)]
+#[uuid = "7c2e9d41-6b3a-4f0e-8d25-91e4c6a7b3f2"]
+pub(crate) struct BarMaterial {
+    pub color: Vec4,
+    pub edge_color: Vec4,
+    pub inner_canvas_size_in_pixels: Vec2,
+    pub canvas_position_in_pixels: Vec2,
+
+    /// If > 0.5, a thin border is drawn around each bar
+    pub draw_contour: f32,
+}
+
+impl BarMaterial {
+    pub fn new(plot: &Plot, bars: &BarData) -> Self {
+        BarMaterial {
+            color: col_to_vec4(bars.color),
+            edge_color: col_to_vec4(bars.edge_color),
+            inner_canvas_size_in_pixels: plot.canvas_size / (1.0 + plot.outer_border),
+            canvas_position_in_pixels: plot.canvas_position,
+            draw_contour: if bars.draw_contour { 1.0 } else { 0.0 },
+        }
+    }
+}
+
+pub(crate) struct GpuBarMaterial {
+    _buffer: Buffer,
+    bind_group: BindGroup,
+}
+
+pub(crate) struct BarMesh2dPlugin;
+
+pub const BAR_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 8420563190274136581);
+
+impl Plugin for BarMesh2dPlugin {
+    fn build(&self, app: &mut App) {
+        let mut shaders = app.world.get_resource_mut::<Assets<Shader>>().unwrap();
+
+        shaders.set_untracked(
+            BAR_SHADER_HANDLE.clone(),
+            Shader::from_wgsl(include_str!("bars.wgsl")),
+        );
+    }
+}
+
+impl Material2d for BarMaterial {
+    fn fragment_shader(_asset_server: &AssetServer) -> Option<Handle<Shader>> {
+        Some(BAR_SHADER_HANDLE.clone().typed::<Shader>())
+    }
+
+    fn bind_group(render_asset: &<Self as RenderAsset>::PreparedAsset) -> &BindGroup {
+        &render_asset.bind_group
+    }
+
+    fn bind_group_layout(render_device: &RenderDevice) -> BindGroupLayout {
+        render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            entries: &[BindGroupLayoutEntry {
+                binding: 0,
+                visibility: ShaderStages::FRAGMENT,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: BufferSize::new(BarMaterial::std140_size_static() as u64),
+                },
+                count: None,
+            }],
+            label: Some("bar_material_layout"),
+        })
+    }
+}
+
+impl RenderAsset for BarMaterial {
+    type ExtractedAsset = BarMaterial;
+    type PreparedAsset = GpuBarMaterial;
+    type Param = (SRes<RenderDevice>, SRes<Material2dPipeline<Self>>);
+
+    fn extract_asset(&self) -> Self::ExtractedAsset {
+        self.clone()
+    }
+
+    fn prepare_asset(
+        extracted_asset: Self::ExtractedAsset,
+        (render_device, material_pipeline): &mut SystemParamItem<Self::Param>,
+    ) -> Result<Self::PreparedAsset, PrepareAssetError<Self::ExtractedAsset>> {
+        let material_std140 = extracted_asset.as_std140();
+
+        let buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
+            contents: material_std140.as_bytes(),
+            label: Some("bar_uniform_buffer"),
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+        });
+
+        let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+            entries: &[BindGroupEntry {
+                binding: 0,
+                resource: buffer.as_entire_binding(),
+            }],
+            label: Some("bar_bind_group"),
+            layout: &material_pipeline.material2d_layout,
+        });
+
+        Ok(GpuBarMaterial {
+            _buffer: buffer,
+            bind_group,
+        })
+    }
+}
+
+// Builds one quad per bar, in world coordinates modulo the canvas position. The bars are clipped
+// slightly beyond the canvas, where they are hidden by the mask of the shader.
+fn make_bar_mesh(plot: &Plot, bars: &BarData) -> Option<Mesh> {
+    let bounds_world = plot.compute_bounds_world();
+
+    let margin = Vec2::splat(10.0);
+    let clip_lo = bounds_world.lo - margin;
+    let clip_up = bounds_world.up + margin;
+
+    let mut rects = Vec::new();
+
+    for (lo, up) in bars.rects.iter() {
+        let a = plot.to_local(*lo);
+        let b = plot.to_local(*up);
+
+        // a corner that cannot be displayed, such as the bottom of a bar starting at zero on
+        // a logarithmic axis, is sent beyond the canvas
+        let fix = |v: f32, fallback: f32| if v.is_finite() { v } else { fallback };
+        let a = Vec2::new(fix(a.x, clip_lo.x), fix(a.y, clip_lo.y));
+        let b = Vec2::new(fix(b.x, clip_up.x), fix(b.y, clip_up.y));
+
+        // bars can extend in the negative direction
+        let lo_corner = a.min(b).max(clip_lo);
+        let up_corner = a.max(b).min(clip_up);
+
+        if lo_corner.x >= up_corner.x || lo_corner.y >= up_corner.y {
+            continue;
+        }
+
+        rects.push((lo_corner, up_corner));
+    }
+
+    make_rects_mesh(&rects)
+}
+
+/// Builds one quad per rectangle given by its lower left and upper right corners. The uvs contain the
+/// position inside the rectangle and the normals contain the size of the rectangle, both in pixels,
+/// as expected by bars.wgsl.
+pub(crate) fn make_rects_mesh(rects: &[(Vec2, Vec2)]) -> Option<Mesh> {
+    let mut positions: Vec<[f32; 3]> = Vec::new();
+    let mut normals: Vec<[f32; 3]> = Vec::new();
+    let mut uvs: Vec<[f32; 2]> = Vec::new();
+    let mut inds: Vec<u32> = Vec::new();
+
+    for (lo_corner, up_corner) in rects.iter().copied() {
+        let size = up_corner - lo_corner;
+        let ki = positions.len() as u32;
+
+        for corner in [
+            lo_corner,
+            Vec2::new(up_corner.x, lo_corner.y),
+            up_corner,
+            Vec2::new(lo_corner.x, up_corner.y),
+        ] {
+            positions.push([corner.x, corner.y, 0.0]);
+            normals.push([size.x, size.y, 0.0]);
+            let uv = corner - lo_corner;
+            uvs.push([uv.x, uv.y]);
+        }
+
+        inds.extend_from_slice(&[ki, ki + 1, ki + 2, ki, ki + 2, ki + 3]);
+    }
+
+    if positions.is_empty() {
+        return None;
+    }
+
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    mesh.set_indices(Some(Indices::U32(inds)));
+
+    Some(mesh)
+}
+
+/// Rebuilds the bars of a plot each time the plot is respawned (e.g. after panning or zooming).
+pub(crate) fn bars_setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<BarMaterial>>,
+    mut change_canvas_material_event: EventReader<RespawnAllEvent>,
+    mut plots: ResMut<Assets<Plot>>,
+    query: Query<(Entity, &Handle<Plot>), With<BarGroupNumber>>,
+) {
+    for event in change_canvas_material_event.iter() {
+        //
+        for (entity, plot_handle) in query.iter() {
+            if event.plot_handle == *plot_handle {
+                commands.entity(entity).despawn();
+            }
+        }
+
+        if let Some(plot) = plots.get_mut(&event.plot_handle) {
+            plot.compute_zeros();
+
+            for (group_number, bars) in plot.data.bar_groups.iter().enumerate() {
+                if bars.hidden {
+                    continue;
+                }
+
+                if let Some(mesh) = make_bar_mesh(plot, bars) {
+                    commands
+                        .spawn()
+                        .insert_bundle(MaterialMesh2dBundle {
+                            mesh: Mesh2dHandle(meshes.add(mesh)),
+                            material: materials.add(BarMaterial::new(plot, bars)),
+                            transform: Transform::from_translation(
+                                plot.canvas_position.extend(0.6),
+                            ),
+                            ..Default::default()
+                        })
+                        .insert(BarGroupNumber(group_number))
+                        .insert(event.plot_handle.clone())
+                        .insert(PlotElement);
+                }
+            }
+        }
+    }
+}
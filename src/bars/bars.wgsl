@@ -0,0 +1,58 @@
+type float4 = vec4<f32>;
+type float2 = vec2<f32>;
+
+struct BarUniform {
+    color: float4;
+    edge_color: float4;
+    inner_canvas_size_in_pixels: float2;
+    canvas_position_in_pixels: float2;
+    draw_contour: f32;
+};
+
+[[group(1), binding(0)]]
+var<uniform> bar_uni: BarUniform;
+
+// uv: position inside the bar in pixels, measured from its lower left corner
+// world_normal.xy: size of the bar in pixels
+struct FragmentInput {
+    [[location(0)]] world_position: vec4<f32>;
+    [[location(1)]] world_normal: vec3<f32>;
+    [[location(2)]] uv: vec2<f32>;
+};
+
+fn sdRoundedBox(p: vec2<f32>, b: vec2<f32>, r: vec4<f32>) -> f32 {
+  var x = r.x;
+  var y = r.y;
+  x = select(r.z, r.x, p.x > 0.);
+  y = select(r.w, r.y, p.x > 0.);
+  x  = select(y, x, p.y > 0.);
+  let q = abs(p) - b + x;
+  return min(max(q.x, q.y), 0.) + length(max(q, vec2<f32>(0.))) - x;
+}
+
+[[stage(fragment)]]
+fn fragment(in: FragmentInput) -> [[location(0)]] vec4<f32> {
+    var out_col = bar_uni.color;
+
+    // distance to the closest edge of the bar, in pixels
+    let p = in.uv;
+    let size = in.world_normal.xy;
+    let d = min(min(p.x, size.x - p.x), min(p.y, size.y - p.y));
+
+    if (bar_uni.draw_contour > 0.5) {
+        out_col = mix(bar_uni.edge_color, out_col, smoothStep(0.5, 1.5, d));
+    }
+
+    // mask with the canvas
+    let local = in.world_position.xy - bar_uni.canvas_position_in_pixels;
+    let r = 0.02 * bar_uni.inner_canvas_size_in_pixels.x;
+    let dm = sdRoundedBox(
+        local,
+        bar_uni.inner_canvas_size_in_pixels / 2.0 - 1.0, float4(r,r,r,r)
+    );
+
+    let sm = smoothStep(-2.0, 0.0, dm);
+    out_col.a = out_col.a * (1.0 - sm);
+
+    return out_col;
+}
@@ -0,0 +1,3 @@
+pub mod bars;
+#[allow(unused_imports)]
+pub use bars::*;
@@ -32,6 +32,9 @@ use crate::util::*;
 
 use itertools_num::linspace;
 
+use std::f32::consts::PI;
+use std::sync::Arc;
+
 #[derive(Copy, Clone, Debug)]
 struct Line(Vec2, Vec2);
 
@@ -60,23 +63,33 @@ impl Line {
     }
 }
 
-// Compute derivatives at each point
-pub(crate) fn make_df(xs: &Vec<f32>, time: f32, f: &fn(f32, f32) -> f32) -> (Vec<Vec2>, Vec<Vec2>) {
-    let delta = (xs[1] - xs[0]) / 1000.0;
+// Compute derivatives at each point, in world coordinates modulo the canvas position.
+// The derivatives are taken after the axis scales are applied, so that the quadratic
+// interpolation stays smooth on non-linear axes.
+pub(crate) fn make_df(
+    xs: &Vec<f32>,
+    time: f32,
+    f: &dyn Fn(f32, f32) -> f32,
+    plot: &Plot,
+) -> Vec<Vec2> {
+    let scale = &plot.x_axis_scale;
+    let delta = (scale.forward(xs[1]) - scale.forward(xs[0])) / 1000.0;
 
     // derivatives
     let dfs = xs
         .iter()
-        .map(|x| Vec2::new(1.0, (f(x + delta, time) - f(x - delta, time)) / delta / 2.0))
+        .map(|x| {
+            let x_lo = scale.inverse(scale.forward(*x) - delta);
+            let x_up = scale.inverse(scale.forward(*x) + delta);
+
+            (plot.to_local(Vec2::new(x_up, f(x_up, time)))
+                - plot.to_local(Vec2::new(x_lo, f(x_lo, time))))
+                / delta
+                / 2.0
+        })
         .collect::<Vec<Vec2>>();
 
-    // normals
-    let ns = dfs
-        .iter()
-        .map(|q| Vec2::new(q.y, -q.x).normalize())
-        .collect::<Vec<Vec2>>();
-
-    return (dfs, ns);
+    return dfs;
 
     // // Code for computing the derivatives of an array instead of a function
     // let df0 = (f(xs[1]) - f(xs[0])) / (xs[1] - xs[0]);
@@ -114,6 +127,28 @@ pub(crate) fn make_df(xs: &Vec<f32>, time: f32, f: &fn(f32, f32) -> f32) -> (Vec
     // return (dfs_vec2, ns_vec2);
 }
 
+// Compute derivatives of a parametric curve at each value of the parameter, in world coordinates
+// modulo the canvas position.
+pub(crate) fn make_parametric_df(
+    ts: &[f32],
+    time: f32,
+    f: &dyn Fn(f32, f32) -> Vec2,
+    plot: &Plot,
+) -> Vec<Vec2> {
+    // a single sample or an empty range of the parameter has no derivative
+    if ts.len() < 2 || ts[1] == ts[0] {
+        return vec![Vec2::ZERO; ts.len()];
+    }
+
+    let delta = (ts[1] - ts[0]) / 1000.0;
+
+    ts.iter()
+        .map(|t| {
+            (plot.to_local(f(t + delta, time)) - plot.to_local(f(t - delta, time))) / delta / 2.0
+        })
+        .collect::<Vec<Vec2>>()
+}
+
 /// Uniform sent to bezier_spline.wgsl
 #[derive(Component, Clone, AsStd140)]
 pub(crate) struct BezierCurveUniform {
@@ -129,8 +164,10 @@ pub(crate) struct BezierCurveUniform {
 
     /// unused
     pub dummy: f32,
-    /// unused
+    /// see [`LineStyle::to_int32`]
     pub style: i32,
+    /// length of the dashes in pixels
+    pub dash_length: f32,
 }
 
 pub(crate) fn update_bezier_uniform(
@@ -157,12 +194,16 @@ pub(crate) fn update_bezier_uniform(
                 color: col_to_vec4(bezier_curve.color),
                 size: bezier_curve.size,
                 style: bezier_curve.line_style.clone().to_int32(),
+                dash_length: bezier_curve.dash_length,
             };
         }
     }
 }
 
-pub(crate) struct SpawnBezierCurveEvent {
+/// Send this event to redraw the explicit function of the `group_number`th curve of `data.bezier_groups`,
+/// for instance after a parameter captured by the function has changed. Curves whose `show_animation`
+/// field is true are redrawn every frame anyway.
+pub struct SpawnBezierCurveEvent {
     pub group_number: usize,
     pub plot_handle: Handle<Plot>,
 }
@@ -190,9 +231,14 @@ pub(crate) fn spawn_bezier_function(
                 }
             }
 
+            // the samples are evenly spaced in the scaled space of the x axis
             let num_pts = plot.bezier_num_points;
-            let xs_linspace = linspace(plot.bounds.lo.x, plot.bounds.up.x, num_pts);
-            let xs = xs_linspace.into_iter().collect::<Vec<f32>>();
+            let bounds = plot.scaled_bounds();
+            let xs_linspace = linspace(bounds.lo.x, bounds.up.x, num_pts);
+            let xs = xs_linspace
+                .into_iter()
+                .map(|x| plot.x_axis_scale.inverse(x))
+                .collect::<Vec<f32>>();
 
             plot_fn(
                 &mut commands,
@@ -226,6 +272,14 @@ pub(crate) fn animate_bezier(
     }
 }
 
+// Approximate length of the quadratic bezier curve with end points p0 and p2 and control point p1,
+// as a weighted average of the length of the chord and of the control polygon
+fn quadratic_bezier_length(p0: Vec2, p1: Vec2, p2: Vec2) -> f32 {
+    let chord = (p2 - p0).length();
+    let polygon = (p1 - p0).length() + (p2 - p1).length();
+    (2.0 * chord + polygon) / 3.0
+}
+
 fn plot_fn(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
@@ -238,76 +292,116 @@ fn plot_fn(
     plot.compute_zeros();
 
     if let Some(bezier_curve) = plot.data.bezier_groups.get(curve_number) {
-        let func = bezier_curve.function.clone();
-
-        let num_pts = plot.bezier_num_points;
+        if bezier_curve.hidden {
+            return;
+        }
 
         let t = time.seconds_since_startup() as f32;
-        let ys = xs
-            .iter()
-            .map(|x| Vec2::new(*x, func(*x, t)))
-            .collect::<Vec<Vec2>>();
 
-        let ys_world = ys.iter().map(|y| plot.to_local(*y)).collect::<Vec<Vec2>>();
+        let parametric = match &bezier_curve.parametric {
+            Some(parametric) => Some((
+                parametric.function.clone(),
+                parametric.t_range,
+                bezier_curve.num_points,
+            )),
+            // in polar mode, explicit functions are r = f(θ) with θ going around a full circle
+            None if plot.polar => {
+                let func = bezier_curve.function.clone();
+                let polar_func: ParametricFunction =
+                    Arc::new(move |theta, time| Vec2::new(theta, func(theta, time)));
+
+                Some((polar_func, (0.0, 2.0 * PI), plot.bezier_num_points))
+            }
+            None => None,
+        };
 
-        let (dys, _) = make_df(&xs, t, &func);
+        // curves read on the secondary axes are mapped to the primary axes
+        let secondary_map = plot.secondary_map(bezier_curve.secondary);
 
-        let dys_p_ys = dys
-            .iter()
-            .zip(ys.iter())
-            .map(|(dy, y)| *dy + *y)
-            .collect::<Vec<Vec2>>();
+        let parametric = parametric.map(|(func, t_range, num_points)| {
+            let map = secondary_map.clone();
+            let mapped: ParametricFunction = Arc::new(move |s, time| map.to_primary(func(s, time)));
+            (mapped, t_range, num_points)
+        });
+
+        let is_parametric = parametric.is_some();
+
+        // sample the curve and compute its derivatives, both in world coordinates
+        let (ys_world, dys) = if let Some((func, t_range, num_points)) = parametric {
+            // nothing to draw with a single sample or an empty range of the parameter
+            if num_points < 2 || t_range.0 == t_range.1 {
+                return;
+            }
+
+            let ts = linspace(t_range.0, t_range.1, num_points).collect::<Vec<f32>>();
+
+            let ys_world = ts
+                .iter()
+                .map(|s| plot.to_local(func(*s, t)))
+                .collect::<Vec<Vec2>>();
+
+            (ys_world, make_parametric_df(&ts, t, func.as_ref(), plot))
+        } else {
+            let function = bezier_curve.function.clone();
+            let map = secondary_map;
+            let func: PlotFunction = Arc::new(move |x, time| {
+                let x = map.to_secondary(Vec2::new(x, 0.0)).x;
+                map.to_primary(Vec2::new(x, function(x, time))).y
+            });
+
+            if xs.len() < 2 {
+                return;
+            }
+
+            let ys = xs
+                .iter()
+                .map(|x| Vec2::new(*x, func(*x, t)))
+                .collect::<Vec<Vec2>>();
+
+            let ys_world = ys.iter().map(|y| plot.to_local(*y)).collect::<Vec<Vec2>>();
+
+            (ys_world, make_df(&xs, t, func.as_ref(), plot))
+        };
 
-        // let dys_p_ys_world = plot.plot_to_local(&dys_p_ys);
-        let dys_p_ys_world = dys_p_ys
+        let num_pts = ys_world.len();
+
+        let dys_p_ys_world = dys
             .iter()
-            .map(|y| plot.to_local(*y))
+            .zip(ys_world.iter())
+            .map(|(dy, y)| *dy + *y)
             .collect::<Vec<Vec2>>();
 
         let mut ends = Vec::new();
 
         let mut mesh_attr_uvs: Vec<[f32; 2]> = Vec::new();
         let mut mesh_attr_controls: Vec<[f32; 4]> = Vec::new();
+        let mut arc_lengths: Vec<[f32; 2]> = Vec::new();
+
+        // arc length at the start of the current part, which keeps the dash pattern continuous
+        let mut arc_length = 0.0;
 
         let mut mesh0 = vec![];
         let mut inds: Vec<u32> = vec![];
 
-        let mut controls = Vec::new();
         let mut kk = 0;
 
         let bounds_world = plot.compute_bounds_world();
 
+        // tells the shader not to clamp the control point between the end points along the x axis
+        let parametric_flag = if is_parametric { 1.0 } else { 0.0 };
+
         let line_width = 30.0;
         for k in 0..num_pts - 1 {
             // TODO: Figure out what quadt-offset does
             let quadt_offset = line_width * 10.0;
 
-            mesh0.push(Vec2::new(ys_world[k].x - quadt_offset, bounds_world.up.y));
-            mesh0.push(Vec2::new(ys_world[k].x - quadt_offset, bounds_world.lo.y));
-
-            mesh0.push(Vec2::new(
-                ys_world[k + 1].x + quadt_offset,
-                bounds_world.up.y,
-            ));
-            mesh0.push(Vec2::new(
-                ys_world[k + 1].x + quadt_offset,
-                bounds_world.lo.y,
-            ));
+            let p0 = ys_world[k];
+            let p1 = ys_world[k + 1];
 
-            mesh_attr_uvs.push([ys_world[k].x - quadt_offset, bounds_world.up.y]);
-            mesh_attr_uvs.push([ys_world[k].x - quadt_offset, bounds_world.lo.y]);
-            mesh_attr_uvs.push([ys_world[k + 1].x + quadt_offset, bounds_world.up.y]);
-            mesh_attr_uvs.push([ys_world[k + 1].x + quadt_offset, bounds_world.lo.y]);
-
-            let ki = kk * 4;
-
-            inds.push(ki as u32);
-            inds.push((ki + 1) as u32);
-            inds.push((ki + 2) as u32);
-
-            inds.push((ki + 3) as u32);
-            inds.push((ki + 2) as u32);
-            inds.push((ki + 1) as u32);
+            // skip the parts of the curve that cannot be displayed with the current axis scales
+            if !(p0.is_finite() && p1.is_finite()) {
+                continue;
+            }
 
             let mut is_last = 0.0;
             if k == num_pts - 2 {
@@ -320,78 +414,78 @@ fn plot_fn(
             // if the angle between the two tangents is greater than 10 degrees,
             // we use a bezier curve (cos(3 degrees) ~= 0.0.9986)))
 
+            let mut control_point = None;
             if (dys[k].normalize().dot(dys[k + 1].normalize())).abs() < 0.9986 {
-                let line0 = Line(ys_world[k], dys_p_ys_world[k]);
-                let line1 = Line(ys_world[k + 1], dys_p_ys_world[k + 1]);
-                let intersection = line1.intersect(line0).unwrap();
-
-                let control_point = intersection;
-
-                mesh_attr_controls.push([control_point.x, control_point.y, is_last, is_last]);
-                mesh_attr_controls.push([control_point.x, control_point.y, is_last, is_last]);
-                mesh_attr_controls.push([control_point.x, control_point.y, is_last, is_last]);
-                mesh_attr_controls.push([control_point.x, control_point.y, is_last, is_last]);
-
-                controls.push(control_point);
-
-                ends.push([
-                    ys_world[k].x,
-                    ys_world[k].y,
-                    ys_world[k + 1].x,
-                    ys_world[k + 1].y,
-                ]);
-                ends.push([
-                    ys_world[k].x,
-                    ys_world[k].y,
-                    ys_world[k + 1].x,
-                    ys_world[k + 1].y,
-                ]);
-                ends.push([
-                    ys_world[k].x,
-                    ys_world[k].y,
-                    ys_world[k + 1].x,
-                    ys_world[k + 1].y,
-                ]);
-                ends.push([
-                    ys_world[k].x,
-                    ys_world[k].y,
-                    ys_world[k + 1].x,
-                    ys_world[k + 1].y,
-                ]);
-            } else {
+                let line0 = Line(p0, dys_p_ys_world[k]);
+                let line1 = Line(p1, dys_p_ys_world[k + 1]);
+                control_point = line1.intersect(line0);
+
+                // A parametric curve can turn back on itself, in which case the tangents intersect
+                // behind one of the end points. This part of the curve is then estimated as a line.
+                if is_parametric {
+                    control_point = control_point
+                        .filter(|c| (*c - p0).dot(dys[k]) > 0.0 && (p1 - *c).dot(dys[k + 1]) > 0.0);
+                }
+            }
+
+            let vertex_controls = match control_point {
+                Some(c) => [c; 4],
+                // line. Any control point between the end points yields a straight line, but the exact
+                // midpoint makes sdBezier() degenerate.
+                None if is_parametric => [p0 + (p1 - p0) * 0.55; 4],
                 // line
-                controls.push(Vec2::new(0.50001, 0.00001));
-
-                mesh_attr_controls.push([ys_world[k].x, ys_world[k].y, is_last, is_last]);
-                mesh_attr_controls.push([ys_world[k].x, ys_world[k].y, is_last, is_last]);
-                mesh_attr_controls.push([ys_world[k + 1].x, ys_world[k + 1].y, is_last, is_last]);
-                mesh_attr_controls.push([ys_world[k + 1].x, ys_world[k + 1].y, is_last, is_last]);
-
-                ends.push([
-                    ys_world[k].x,
-                    ys_world[k].y,
-                    ys_world[k + 1].x,
-                    ys_world[k + 1].y,
-                ]);
-                ends.push([
-                    ys_world[k].x,
-                    ys_world[k].y,
-                    ys_world[k + 1].x,
-                    ys_world[k + 1].y,
-                ]);
-                ends.push([
-                    ys_world[k].x,
-                    ys_world[k].y,
-                    ys_world[k + 1].x,
-                    ys_world[k + 1].y,
-                ]);
-                ends.push([
-                    ys_world[k].x,
-                    ys_world[k].y,
-                    ys_world[k + 1].x,
-                    ys_world[k + 1].y,
-                ]);
+                None => [p0, p0, p1, p1],
+            };
+
+            let quad = if is_parametric {
+                // bounding box of the bezier curve
+                let c = vertex_controls[0];
+                let lo = p0.min(p1).min(c) - line_width;
+                let up = p0.max(p1).max(c) + line_width;
+
+                [Vec2::new(lo.x, up.y), lo, up, Vec2::new(up.x, lo.y)]
+            } else {
+                [
+                    Vec2::new(p0.x - quadt_offset, bounds_world.up.y),
+                    Vec2::new(p0.x - quadt_offset, bounds_world.lo.y),
+                    Vec2::new(p1.x + quadt_offset, bounds_world.up.y),
+                    Vec2::new(p1.x + quadt_offset, bounds_world.lo.y),
+                ]
+            };
+
+            let part_length = match control_point {
+                Some(c) => {
+                    // the shader keeps the control point between the end points of explicit functions
+                    let c = if is_parametric {
+                        c
+                    } else {
+                        Vec2::new(c.x.clamp(p0.x.min(p1.x), p0.x.max(p1.x)), c.y)
+                    };
+                    quadratic_bezier_length(p0, c, p1)
+                }
+                None => (p1 - p0).length(),
+            };
+
+            for (corner, control) in quad.iter().zip(vertex_controls.iter()) {
+                mesh0.push(*corner);
+                mesh_attr_uvs.push([corner.x, corner.y]);
+                mesh_attr_controls.push([control.x, control.y, parametric_flag, is_last]);
+                ends.push([p0.x, p0.y, p1.x, p1.y]);
+                arc_lengths.push([arc_length, part_length]);
             }
+
+            arc_length += part_length;
+
+            let ki = kk * 4;
+
+            inds.push(ki as u32);
+            inds.push((ki + 1) as u32);
+            inds.push((ki + 2) as u32);
+
+            inds.push((ki + 3) as u32);
+            inds.push((ki + 2) as u32);
+            inds.push((ki + 1) as u32);
+
             kk = kk + 1;
         }
 
@@ -417,6 +511,10 @@ fn plot_fn(
 
         mesh.insert_attribute(mva_controls, mesh_attr_controls);
 
+        let mva_arc_lengths = MeshVertexAttribute::new("Arc_Length", 4, VertexFormat::Float32x2);
+
+        mesh.insert_attribute(mva_arc_lengths, arc_lengths);
+
         // println!("mesh: {:?}", mesh.iter().map(|x| ));
 
         commands
@@ -430,6 +528,7 @@ fn plot_fn(
             ))
             .insert(BezierCurveNumber(curve_number))
             .insert(plot_handle.clone())
+            .insert(PlotElement)
             .insert(BezierCurveUniform {
                 mech: if bezier_curve.mech { 1.0 } else { 0.0 },
                 dummy: plot.bezier_dummy,
@@ -439,6 +538,7 @@ fn plot_fn(
                 color: col_to_vec4(bezier_curve.color),
                 size: bezier_curve.size,
                 style: bezier_curve.line_style.clone().to_int32(),
+                dash_length: bezier_curve.dash_length,
             });
     }
 }
@@ -525,6 +625,8 @@ impl SpecializedRenderPipeline for BezierMesh2dPipeline {
             VertexFormat::Float32x2,
             // Controls
             VertexFormat::Float32x4,
+            // Arc length at the start of the part and length of the part
+            VertexFormat::Float32x2,
         ];
 
         let vertex_layout =
@@ -18,6 +18,7 @@ struct BezierCurveUniform {
     size: f32;
     dummy: f32;
     style: i32;
+    dash_length: f32;
 };
 
 [[group(2), binding(0)]]
@@ -29,12 +30,14 @@ struct Vertex {
     [[location(1)]] ends: vec4<f32>;
     [[location(2)]] uv: vec2<f32>;
     [[location(3)]] control: vec4<f32>;
+    [[location(4)]] arc: vec2<f32>;
 };
 struct VertexOutput {
     [[builtin(position)]] clip_position: vec4<f32>;
     [[location(0)]] ends: vec4<f32>;
     [[location(1)]] uv: vec2<f32>;
     [[location(2)]] control: vec4<f32>;
+    [[location(3)]] arc: vec2<f32>;
 };
 /// Entry point for the vertex shader
 [[stage(vertex)]]
@@ -45,6 +48,7 @@ fn vertex(vertex: Vertex) -> VertexOutput {
     out.ends = vertex.ends;
     out.uv = vertex.uv;
     out.control = vertex.control;
+    out.arc = vertex.arc;
     return out;
 }
 
@@ -72,6 +76,8 @@ struct FragmentInput {
     [[location(0)]] ends: vec4<f32>;
     [[location(1)]] uv: vec2<f32>;
     [[location(2)]] control: vec4<f32>;
+    // arc length at the start of the part and approximate length of the part
+    [[location(3)]] arc: vec2<f32>;
 };
 
 
@@ -178,6 +184,42 @@ fn sdPie(p: vec2<f32>, sc: vec2<f32>, r: f32) -> f32 {
   return max(l, m * sign(sc.y * q.x - sc.x * q.y));
 }
 
+// Distance along the curve between the arc length s and the closest dash or dot of the
+// line style (1: dashed, 2: dotted, 3: dash-dot, 4: dash-dot-dot). Dots are dashes of
+// zero length. The gaps account for the round caps of radius r.
+fn dash_distance(s: f32, style: i32, dash_length: f32, r: f32) -> f32 {
+    let gap = 0.5 * dash_length + 2.0 * r;
+
+    if (style == 2) {
+        let dot_gap = dash_length / 3.0 + 2.0 * r;
+        let m = s - floor(s / dot_gap) * dot_gap;
+        return min(m, dot_gap - m);
+    }
+
+    var num_dots = 0.0;
+    if (style == 3) {
+        num_dots = 1.0;
+    }
+    if (style == 4) {
+        num_dots = 2.0;
+    }
+
+    let period = dash_length + gap * (1.0 + num_dots);
+    let m = s - floor(s / period) * period;
+
+    // the dash covers [0, dash_length] and the next one starts at the end of the period
+    var d = min(max(m - dash_length, 0.0), period - m);
+
+    if (num_dots > 0.5) {
+        d = min(d, abs(m - dash_length - gap));
+    }
+    if (num_dots > 1.5) {
+        d = min(d, abs(m - dash_length - 2.0 * gap));
+    }
+
+    return d;
+}
+
 fn tips(uv: float2, m_in: float4, dy: float2, solid: f32, w: f32 ) -> float4 {
     var m = m_in;
     let theta = atan2(dy.y, dy.x) + 3.1415 / 2.0;
@@ -210,19 +252,34 @@ fn fragment(in: FragmentInput) -> [[location(0)]] vec4<f32> {
     var control = in.control.xy;
     let is_last = in.control.w;
 
-    control.x = clamp(p0.x, p1.x, control.x);
+    // for explicit functions, the control point has to lie between the end points
+    // along the x axis. Parametric curves (control.z > 0.5) are left untouched.
+    let is_parametric = in.control.z;
 
-    if (control.x < min(p1.x, p0.x)) {
-        control.x = min(p1.x, p0.x);
-    }
+    if (is_parametric < 0.5) {
+        control.x = clamp(p0.x, p1.x, control.x);
+
+        if (control.x < min(p1.x, p0.x)) {
+            control.x = min(p1.x, p0.x);
+        }
 
-    if (control.x > max(p1.x, p0.x)) {
-        control.x = max(p1.x, p0.x);
+        if (control.x > max(p1.x, p0.x)) {
+            control.x = max(p1.x, p0.x);
+        }
     }
     
 
     let d = sdBezier(uv, p0, control  , p1);
-    let s = smoothStep(0.0 + solid, w  + solid , d.x);
+    var dist = d.x;
+
+    // dashed and dotted styles, d.y being the parameter of the closest point on the curve
+    if (bez_uni.style > 0) {
+        let arc_length = in.arc.x + d.y * in.arc.y;
+        let along = dash_distance(arc_length, bez_uni.style, bez_uni.dash_length, solid + 0.5 * w);
+        dist = length(float2(dist, along));
+    }
+
+    let s = smoothStep(0.0 + solid, w  + solid , dist);
 
     // mechanical look
     if (bez_uni.mech > 0.5) {
@@ -61,6 +61,10 @@ struct GraphEditShader {
     target_color: float4;
     show_grid: f32;
     show_axes: f32;
+    polar: f32;
+    selection: float4;
+    // (months, origin in days) of an axis whose grid follows the calendar, (0, tick offset) otherwise
+    ticks: float4;
     
 };
 
@@ -201,6 +205,32 @@ fn draw_circle(
 
 
 
+// Index of the calendar month at the coordinate t of a date/time axis, in seconds since the
+// origin of the axis, which lies origin_days days after the Unix epoch. Month m of year y has the
+// index 12 y + m - 1, and the fractional part is the time elapsed in the month. The date is found
+// with Howard Hinnant's algorithm for the proleptic Gregorian calendar, see date_time.rs.
+fn month_coordinate(t: f32, origin_days: f32) -> f32 {
+    let days = origin_days + t / 86400.0;
+
+    let z = i32(floor(days)) + 719468;
+    let era = select(z, z - 146096, z < 0) / 146097;
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5;
+    let month = select(mp + 3, mp - 9, mp >= 10);
+    let year = yoe + era * 400 + select(0, 1, month <= 2);
+
+    let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+    var month_length = 30 + ((month + month / 8) % 2);
+    if (month == 2) {
+        month_length = select(28, 29, leap);
+    }
+
+    return f32(year * 12 + month - 1) + (f32(day) + fract(days)) / f32(month_length);
+}
+
 [[stage(fragment)]]
 fn fragment(in: VertexOutput) -> [[location(0)]] vec4<f32> {
 
@@ -230,10 +260,36 @@ fn fragment(in: VertexOutput) -> [[location(0)]] vec4<f32> {
     
 
     ///////////////////// background /////////////////
-    let tile_freq_x: f32 = 1.0 / mate.tick_period.x;
-    let tile_freq_y: f32 = 1.0 / mate.tick_period.y;
-   
-    let tiles = even( (floor(tile_freq_x*uv.x) + floor(tile_freq_y*uv.y) ) ) ; //+ even(uv.y * 5.);
+    // the tiles and the grid are shifted by the tick offset, which is non-zero on a date/time axis
+    let tick_offset = float2(
+        select(mate.ticks.y, 0.0, mate.ticks.x > 0.5),
+        select(mate.ticks.w, 0.0, mate.ticks.z > 0.5)
+    );
+    let grid_uv = uv - tick_offset;
+
+    // in the tick coordinate, the grid lines lie on integers
+    var tick_coord = grid_uv / mate.tick_period;
+    if (mate.ticks.x > 0.5) {
+        tick_coord.x = month_coordinate(uv.x, mate.ticks.y) / mate.ticks.x;
+    }
+    if (mate.ticks.z > 0.5) {
+        tick_coord.y = month_coordinate(uv.y, mate.ticks.w) / mate.ticks.z;
+    }
+
+    var tiles = even( (floor(tick_coord.x) + floor(tick_coord.y) ) ) ; //+ even(uv.y * 5.);
+
+    // polar coordinates of the current pixel, with theta between 0 and 2 pi
+    let two_pi = 6.283185307;
+    let radius = length(uv);
+    var theta = atan2(uv.y, uv.x);
+    if (theta < 0.0) {
+        theta = theta + two_pi;
+    }
+
+    if (mate.polar > 0.5) {
+        // in polar mode, tick_period.x is an angle and tick_period.y a radius
+        tiles = even( (floor(theta / mate.tick_period.x) + floor(radius / mate.tick_period.y) ) );
+    }
 
     var rect: vec4<f32> = mix(colBackground1, colBackground2, tiles );
     ///////////////////// background /////////////////
@@ -251,10 +307,10 @@ fn fragment(in: VertexOutput) -> [[location(0)]] vec4<f32> {
 
     var segment: Segment;
 
-    var sig = sign(uv);
+    var sig = sign(tick_coord);
 
     // in the tiki coordinate, 1 corresponds to one tick period
-    let tiki = (in.uv - mate.position - origin) / tick_period_pix   - float2(0.5, 0.5) * sig ;
+    let tiki = tick_coord - float2(0.5, 0.5) * sig ;
 
     // In wgpu currently, the mod function take a reference to a dummy variable.
     // This will change in the future.
@@ -273,7 +329,23 @@ fn fragment(in: VertexOutput) -> [[location(0)]] vec4<f32> {
 
     let bars_thickness  = 0.5 / tick_period_pix ;
 
-    if (mate.show_grid > 0.5 ) {
+    if (mate.show_grid > 0.5 && mate.polar > 0.5) {
+        // number of pixels per unit length, averaged over both axes
+        let pixels_per_unit = 0.5 * (so.x / x_range + so.y / y_range);
+
+        // rings
+        let ring = radius / mate.tick_period.y;
+        let ring_dist = abs(ring - round(ring)) * mate.tick_period.y * pixels_per_unit;
+        let ring_alpha = 1.0 - smoothStep(0.5, 1.5, ring_dist);
+
+        // radial lines
+        let spoke = theta / mate.tick_period.x;
+        let spoke_dist = abs(spoke - round(spoke)) * mate.tick_period.x * radius * pixels_per_unit;
+        let spoke_alpha = 1.0 - smoothStep(0.5, 1.5, spoke_dist);
+
+        rect = mix(rect, black, bar_alpha * max(ring_alpha, spoke_alpha));
+
+    } else if (mate.show_grid > 0.5 ) {
         // horizontal bars
         segment.start = float2(-edges.x,  half.y) ;
         segment.end = float2( edges.x,  half.y) ;
@@ -331,6 +403,21 @@ fn fragment(in: VertexOutput) -> [[location(0)]] vec4<f32> {
     /////////////////// mouse target /////////////////////////
 
 
+    /////////////////// box zoom selection /////////////////////////
+    if (mate.selection.z > mate.selection.x && mate.selection.w > mate.selection.y) {
+        let selection_color = float4(0.85, 0.85, 0.85, 1.0);
+        let center = (mate.selection.xy + mate.selection.zw) / 2.0;
+        let half_size = (mate.selection.zw - mate.selection.xy) / 2.0;
+
+        let d = sdRoundedBox(in.uv - center, half_size, float4(0.0, 0.0, 0.0, 0.0));
+
+        // translucent inside and a thin border
+        rect = mix(rect, selection_color, 0.15 * (1.0 - smoothStep(0.0, 1.0, d)));
+        rect = mix(rect, selection_color, 1.0 - smoothStep(0.5, 1.5, abs(d)));
+    }
+    /////////////////// box zoom selection /////////////////////////
+
+
     /////////////////// contours /////////////////////////
     if (mate.hide_contour < 0.5) {
 
@@ -11,6 +11,8 @@ use crate::canvas::RespawnAllEvent;
 use crate::util::*;
 
 use crate::bezier::*;
+use crate::drag::*;
+use crate::legend::*;
 use crate::plot::*;
 
 fn spawn_axis_tick_labels(
@@ -55,14 +57,24 @@ pub(crate) fn update_target(
     // mut materials: ResMut<Assets<CanvasMaterial>>,
     mut plots: ResMut<Assets<Plot>>,
     mut update_target_labels_event: EventReader<UpdateTargetLabelEvent>,
-    taget_label_query: Query<Entity, With<TargetLabel>>,
+    taget_label_query: Query<(Entity, &Parent), With<TargetLabel>>,
     // canvas_query: Query<(Entity, &mut Handle<CanvasMaterial>, &Handle<Plot>)>,
     mut canvas_materials: ResMut<Assets<CanvasMaterial>>,
     // mut canvas_query: Query<&mut Canvas>,
 ) {
-    if let Some(event) = update_target_labels_event.iter().next() {
-        for entity in taget_label_query.iter() {
-            commands.entity(entity).despawn();
+    // Only the first event of each canvas is read
+    let mut updated_canvases = Vec::new();
+
+    for event in update_target_labels_event.iter() {
+        if updated_canvases.contains(&event.canvas_entity) {
+            continue;
+        }
+        updated_canvases.push(event.canvas_entity);
+
+        for (entity, parent) in taget_label_query.iter() {
+            if parent.0 == event.canvas_entity {
+                commands.entity(entity).despawn();
+            }
         }
         // let graph_sprite = canvas_query.get_mut(event.canvas_entity).unwrap();
 
@@ -82,14 +94,7 @@ pub(crate) fn update_target(
                 let target_text_z_plane = 1.2;
                 let font_size = 16.0;
 
-                let pos = plot.target_position;
-
-                let target_str_x =
-                    format_numeric_label(&plot, pos.x, pos.x > 1000.0 || pos.x < 0.01);
-                let target_str_y =
-                    format_numeric_label(&plot, pos.y, pos.y > 1000.0 || pos.y < 0.01);
-
-                let target_str = format!("({}, {})", target_str_x, target_str_y);
+                let target_str = format_coordinates(plot, plot.target_position);
 
                 let offset = font_size * 0.2;
                 let mut target_position = plot
@@ -112,7 +117,7 @@ pub(crate) fn update_target(
                     target_position.x -= font_size * 0.4;
                 }
 
-                if let Some(font_handle) = maybe_font.maybe_font.as_ref() {
+                if let Some(font_handle) = plot.label_font(&maybe_font) {
                     let text_style = TextStyle {
                         font: font_handle.clone(),
                         font_size,
@@ -149,24 +154,37 @@ pub(crate) fn update_plot_labels(
     // mut materials: ResMut<Assets<CanvasMaterial>>,
     mut plots: ResMut<Assets<Plot>>,
     mut update_plot_labels_event: EventReader<UpdatePlotLabelsEvent>,
-    plot_label_query: Query<Entity, With<PlotLabel>>,
+    plot_label_query: Query<(Entity, &Parent), With<PlotLabel>>,
     mut canvas_query: Query<&mut Canvas>,
 ) {
-    // If there is a stack of UpdatePlotLabelsEvent, only read the first one.
-    if let Some(font_handle) = maybe_font.maybe_font.as_ref() {
-        if let Some(event) = update_plot_labels_event.iter().next() {
-            let plot_handle = event.plot_handle.clone();
+    // If there is a stack of UpdatePlotLabelsEvent, only read the first one of each canvas.
+    let mut updated_canvases = Vec::new();
 
-            // if let Some(plot) = materials.get_mut(plot_handle.clone()) {
+    for event in update_plot_labels_event.iter() {
+        if updated_canvases.contains(&event.canvas_entity) {
+            continue;
+        }
+        updated_canvases.push(event.canvas_entity);
 
-            if let Some(plot) = plots.get_mut(plot_handle.clone()) {
+        let plot_handle = event.plot_handle.clone();
+
+        // if let Some(plot) = materials.get_mut(plot_handle.clone()) {
+
+        if let Some(plot) = plots.get_mut(plot_handle.clone()) {
+            if let Some(font_handle) = plot.label_font(&maybe_font) {
                 if !plot.hide_tick_labels {
-                    for entity in plot_label_query.iter() {
-                        commands.entity(entity).despawn();
+                    for (entity, parent) in plot_label_query.iter() {
+                        if parent.0 == event.canvas_entity {
+                            commands.entity(entity).despawn();
+                        }
                     }
                     let plot_entity = event.canvas_entity;
 
-                    let graph_sprite = canvas_query.get_mut(event.canvas_entity).unwrap();
+                    let graph_sprite = if let Ok(graph_sprite) = canvas_query.get_mut(plot_entity) {
+                        graph_sprite
+                    } else {
+                        continue;
+                    };
 
                     let size = graph_sprite.original_size;
 
@@ -179,13 +197,48 @@ pub(crate) fn update_plot_labels(
                     let x_edge = size.x / (1. + plot.outer_border.x) / 2.0;
                     let y_edge = size.y / (1. + plot.outer_border.y) / 2.0;
 
-                    let x_range = plot.bounds.up.x - plot.bounds.lo.x;
-                    let y_range = plot.bounds.up.y - plot.bounds.lo.y;
+                    // ticks are evenly spaced in the scaled space of each axis
+                    let bounds = plot.scaled_bounds();
+
+                    let x_range = bounds.up.x - bounds.lo.x;
+                    let y_range = bounds.up.y - bounds.lo.y;
 
                     let text_z_plane = 1.0001;
 
+                    if plot.polar {
+                        spawn_polar_tick_labels(
+                            &mut commands,
+                            plot,
+                            plot_entity,
+                            font_size,
+                            text_z_plane,
+                            font_handle,
+                        );
+                        continue;
+                    }
+
                     ///////////////////////////// x_axis labels  /////////////////////////////
-                    {
+                    if !plot.x_categories.is_empty() {
+                        spawn_category_tick_labels(
+                            &mut commands,
+                            plot,
+                            plot_entity,
+                            font_size,
+                            text_z_plane,
+                            font_handle,
+                            true,
+                        );
+                    } else if plot.x_axis_scale.is_date_time() {
+                        spawn_date_time_tick_labels(
+                            &mut commands,
+                            plot,
+                            plot_entity,
+                            font_size,
+                            text_z_plane,
+                            font_handle,
+                            true,
+                        );
+                    } else {
                         // distance from center for
                         let center_dist_y = -graph_y / 2.0 + font_size * 1.0;
 
@@ -193,16 +246,18 @@ pub(crate) fn update_plot_labels(
                         let iter_x = x_edge * 2.0 / x_range;
 
                         // integer corresponding to lowest x tick
-                        let bottom_x = (plot.bounds.lo.x / plot.tick_period.x).abs().floor() as i64
-                            * (plot.bounds.lo.x).signum() as i64;
+                        let bottom_x = (bounds.lo.x / plot.tick_period.x).abs().floor() as i64
+                            * (bounds.lo.x).signum() as i64;
 
                         // integer corresponding to highest x tick
-                        let top_x = (plot.bounds.up.x / plot.tick_period.x).abs().floor() as i64
-                            * (plot.bounds.up.x).signum() as i64;
+                        let top_x = (bounds.up.x / plot.tick_period.x).abs().floor() as i64
+                            * (bounds.up.x).signum() as i64;
 
-                        let max_abs_x = (plot.tick_period.x * bottom_x as f32)
+                        let max_abs_x = plot
+                            .x_axis_scale
+                            .inverse(plot.tick_period.x * bottom_x as f32)
                             .abs()
-                            .max(plot.tick_period.x * top_x as f32);
+                            .max(plot.x_axis_scale.inverse(plot.tick_period.x * top_x as f32).abs());
 
                         for i in bottom_x..(top_x + 1) {
                             if plot.hide_half_ticks && (i % 2).abs() == 1 {
@@ -231,12 +286,13 @@ pub(crate) fn update_plot_labels(
 
                             let x_str = format_numeric_label(
                                 &plot,
-                                i as f32 * plot.tick_period.x,
+                                plot.x_axis_scale.inverse(i as f32 * plot.tick_period.x),
                                 max_abs_x >= 1000.0 || max_abs_x < 0.01,
+                                &plot.x_axis_scale,
                             );
 
                             // leftmost position on the x axis
-                            let x0 = x_edge * (-1.0 - plot.bounds.lo.x * 2.0 / x_range);
+                            let x0 = x_edge * (-1.0 - bounds.lo.x * 2.0 / x_range);
 
                             // iterator for each label
                             let x_pos = iter_x * i as f32 * plot.tick_period.x;
@@ -265,7 +321,27 @@ pub(crate) fn update_plot_labels(
                     }
 
                     ////////////////////////////////// y_axis labels //////////////////////////////////
-                    {
+                    if !plot.y_categories.is_empty() {
+                        spawn_category_tick_labels(
+                            &mut commands,
+                            plot,
+                            plot_entity,
+                            font_size,
+                            text_z_plane,
+                            font_handle,
+                            false,
+                        );
+                    } else if plot.y_axis_scale.is_date_time() {
+                        spawn_date_time_tick_labels(
+                            &mut commands,
+                            plot,
+                            plot_entity,
+                            font_size,
+                            text_z_plane,
+                            font_handle,
+                            false,
+                        );
+                    } else {
                         // distance from center for
                         let center_dist_x = -graph_x / 2.0 + font_size * 0.2;
 
@@ -273,16 +349,18 @@ pub(crate) fn update_plot_labels(
                         let iter_y = y_edge * 2.0 / y_range;
 
                         // integer corresponding to lowest y tick
-                        let bottom_y = (plot.bounds.lo.y / plot.tick_period.y).abs().floor() as i64
-                            * (plot.bounds.lo.y).signum() as i64;
+                        let bottom_y = (bounds.lo.y / plot.tick_period.y).abs().floor() as i64
+                            * (bounds.lo.y).signum() as i64;
 
                         // integer corresponding to highest y tick
-                        let top_y = (plot.bounds.up.y / plot.tick_period.y).abs().floor() as i64
-                            * (plot.bounds.up.y).signum() as i64;
+                        let top_y = (bounds.up.y / plot.tick_period.y).abs().floor() as i64
+                            * (bounds.up.y).signum() as i64;
 
-                        let max_abs_y = (plot.tick_period.y * bottom_y as f32)
+                        let max_abs_y = plot
+                            .y_axis_scale
+                            .inverse(plot.tick_period.y * bottom_y as f32)
                             .abs()
-                            .max(plot.tick_period.y * top_y as f32);
+                            .max(plot.y_axis_scale.inverse(plot.tick_period.y * top_y as f32).abs());
 
                         for i in bottom_y..top_y + 1 {
                             if plot.hide_half_ticks && (i % 2).abs() == 1 {
@@ -309,13 +387,14 @@ pub(crate) fn update_plot_labels(
 
                             let y_str = format_numeric_label(
                                 &plot,
-                                i as f32 * plot.tick_period.y,
+                                plot.y_axis_scale.inverse(i as f32 * plot.tick_period.y),
                                 // scientific notation if the numbers are larger than 1000 or smaller than 0.01
                                 max_abs_y >= 1000.0 || max_abs_y < 0.01,
+                                &plot.y_axis_scale,
                             );
 
                             // leftmost position on the x axis
-                            let y0 = y_edge * (-1.0 - plot.bounds.lo.y * 2.0 / y_range);
+                            let y0 = y_edge * (-1.0 - bounds.lo.y * 2.0 / y_range);
 
                             // iterator for each label
                             let y_pos = iter_y * i as f32 * plot.tick_period.y;
@@ -341,12 +420,530 @@ pub(crate) fn update_plot_labels(
                             }
                         }
                     }
+
+                    if plot.secondary_axes.any() {
+                        spawn_secondary_tick_labels(
+                            &mut commands,
+                            plot,
+                            plot_entity,
+                            font_size,
+                            text_z_plane,
+                            font_handle,
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+// The title and the axis labels are children of the canvas, so that they follow it when it moves.
+// They are laid out outside of the canvas, and their transform undoes the scale of the canvas
+// so that the text keeps its size when the canvas is resized.
+pub(crate) fn update_axis_labels(
+    mut commands: Commands,
+    maybe_font: Res<TickLabelFont>,
+    plots: Res<Assets<Plot>>,
+    mut update_plot_labels_event: EventReader<UpdatePlotLabelsEvent>,
+    canvas_query: Query<(Entity, &Canvas, &Handle<Plot>)>,
+    changed_canvas_query: Query<Entity, Changed<Canvas>>,
+    axis_label_query: Query<(Entity, &Parent), With<AxisLabel>>,
+) {
+    let mut canvas_entities = update_plot_labels_event
+        .iter()
+        .map(|event| event.canvas_entity)
+        .chain(changed_canvas_query.iter())
+        .collect::<Vec<Entity>>();
+    canvas_entities.sort();
+    canvas_entities.dedup();
+
+    for canvas_entity in canvas_entities {
+        if let Ok((_, canvas, plot_handle)) = canvas_query.get(canvas_entity) {
+            if let Some(plot) = plots.get(plot_handle) {
+                for (entity, parent) in axis_label_query.iter() {
+                    if parent.0 == canvas_entity {
+                        commands.entity(entity).despawn();
+                    }
+                }
+
+                let font_handle = if let Some(font_handle) = plot.label_font(&maybe_font) {
+                    font_handle
+                } else {
+                    continue;
+                };
+
+                let half_size = canvas.original_size / 2.0;
+                let inverse_scale = Vec2::ONE / canvas.scale;
+                let text_z_plane = 1.0001;
+
+                // the x tick labels are drawn inside the canvas, so the labels only need a small gap
+                let gap = plot.axis_label_font_size * 0.3;
+
+                // the title goes above the label of the secondary x axis
+                let title_offset = if plot.secondary_x_label.is_some() {
+                    gap + plot.axis_label_font_size
+                } else {
+                    0.0
+                };
+
+                if let Some(title) = &plot.title {
+                    spawn_axis_label(
+                        &mut commands,
+                        canvas_entity,
+                        title,
+                        plot.title_font_size,
+                        plot.axis_label_color,
+                        font_handle,
+                        Transform {
+                            translation: Vec3::new(
+                                0.0,
+                                half_size.y + (gap + title_offset) * inverse_scale.y,
+                                text_z_plane,
+                            ),
+                            scale: inverse_scale.extend(1.0),
+                            ..Default::default()
+                        },
+                    );
+                }
+
+                if let Some(x_label) = &plot.x_label {
+                    spawn_axis_label(
+                        &mut commands,
+                        canvas_entity,
+                        x_label,
+                        plot.axis_label_font_size,
+                        plot.axis_label_color,
+                        font_handle,
+                        Transform {
+                            translation: Vec3::new(
+                                0.0,
+                                -half_size.y - (gap + plot.axis_label_font_size) * inverse_scale.y,
+                                text_z_plane,
+                            ),
+                            scale: inverse_scale.extend(1.0),
+                            ..Default::default()
+                        },
+                    );
+                }
+
+                // The y label is rotated by 90 degrees, so the x and y components of the inverse
+                // scale are swapped. The bottom of the text faces the canvas.
+                if let Some(y_label) = &plot.y_label {
+                    spawn_axis_label(
+                        &mut commands,
+                        canvas_entity,
+                        y_label,
+                        plot.axis_label_font_size,
+                        plot.axis_label_color,
+                        font_handle,
+                        Transform {
+                            translation: Vec3::new(
+                                -half_size.x - gap * inverse_scale.x,
+                                0.0,
+                                text_z_plane,
+                            ),
+                            rotation: Quat::from_rotation_z(std::f32::consts::FRAC_PI_2),
+                            scale: Vec3::new(inverse_scale.y, inverse_scale.x, 1.0),
+                        },
+                    );
+                }
+
+                if let Some(x_label) = &plot.secondary_x_label {
+                    spawn_axis_label(
+                        &mut commands,
+                        canvas_entity,
+                        x_label,
+                        plot.axis_label_font_size,
+                        plot.axis_label_color,
+                        font_handle,
+                        Transform {
+                            translation: Vec3::new(
+                                0.0,
+                                half_size.y + gap * inverse_scale.y,
+                                text_z_plane,
+                            ),
+                            scale: inverse_scale.extend(1.0),
+                            ..Default::default()
+                        },
+                    );
+                }
+
+                // the label of the secondary y axis is rotated by -90 degrees so that the bottom
+                // of the text also faces the canvas
+                if let Some(y_label) = &plot.secondary_y_label {
+                    spawn_axis_label(
+                        &mut commands,
+                        canvas_entity,
+                        y_label,
+                        plot.axis_label_font_size,
+                        plot.axis_label_color,
+                        font_handle,
+                        Transform {
+                            translation: Vec3::new(
+                                half_size.x + gap * inverse_scale.x,
+                                0.0,
+                                text_z_plane,
+                            ),
+                            rotation: Quat::from_rotation_z(-std::f32::consts::FRAC_PI_2),
+                            scale: Vec3::new(inverse_scale.y, inverse_scale.x, 1.0),
+                        },
+                    );
+                }
+            }
+        }
+    }
+}
+
+// Spawns a label anchored at the middle of its bottom edge
+fn spawn_axis_label(
+    commands: &mut Commands,
+    canvas_entity: Entity,
+    text: &str,
+    font_size: f32,
+    font_color: Color,
+    font: &Handle<Font>,
+    transform: Transform,
+) {
+    let text_style = TextStyle {
+        font: font.clone(),
+        font_size,
+        color: font_color,
+    };
+    let text_alignment = TextAlignment {
+        vertical: VerticalAlign::Bottom,
+        horizontal: HorizontalAlign::Center,
+    };
+
+    let label_entity = commands
+        .spawn_bundle(Text2dBundle {
+            text: Text::with_section(text, text_style, text_alignment),
+            transform,
+            ..Default::default()
+        })
+        .insert(AxisLabel)
+        .id();
+
+    commands.entity(canvas_entity).push_children(&[label_entity]);
+}
+
+// The tick labels of the secondary axes are spawned inside the canvas, along its top edge for the
+// x axis and along its right edge for the y axis. Ticks are evenly spaced in the scaled space.
+fn spawn_secondary_tick_labels(
+    commands: &mut Commands,
+    plot: &Plot,
+    plot_entity: Entity,
+    font_size: f32,
+    text_z_plane: f32,
+    font_handle: &Handle<Font>,
+) {
+    let inner_half_size = plot.canvas_size / (1.0 + plot.outer_border) / 2.0;
+    let bounds = plot.secondary_scaled_bounds();
+    let [show_x, show_y] = <[bool; 2]>::from(plot.secondary_axes);
+
+    for (is_x_axis, shown) in [(true, show_x), (false, show_y)] {
+        if !shown {
+            continue;
+        }
+
+        let (lo, up, period, scale) = if is_x_axis {
+            (
+                bounds.lo.x,
+                bounds.up.x,
+                plot.secondary_tick_period.x,
+                &plot.x_axis_scale,
+            )
+        } else {
+            (
+                bounds.lo.y,
+                bounds.up.y,
+                plot.secondary_tick_period.y,
+                &plot.y_axis_scale,
+            )
+        };
+
+        let first = (lo / period).ceil() as i64;
+        let last = (up / period).floor() as i64;
+
+        let max_abs = scale
+            .inverse(first as f32 * period)
+            .abs()
+            .max(scale.inverse(last as f32 * period).abs());
+
+        for i in first..(last + 1) {
+            if plot.hide_half_ticks && (i % 2).abs() == 1 {
+                continue;
+            }
+
+            let label = format_numeric_label(
+                plot,
+                scale.inverse(i as f32 * period),
+                !(0.01..1000.0).contains(&max_abs),
+                scale,
+            );
+
+            // position of the tick between -1 and 1
+            let t = (i as f32 * period - lo) / (up - lo) * 2.0 - 1.0;
+
+            if is_x_axis {
+                let x = t * inner_half_size.x - font_size * 0.2;
+                if x < -inner_half_size.x + font_size * 3.0 || x > inner_half_size.x {
+                    continue;
+                }
+
+                spawn_axis_tick_labels(
+                    commands,
+                    plot_entity,
+                    &label,
+                    font_size,
+                    Vec3::new(x, inner_half_size.y - font_size * 0.1, text_z_plane),
+                    VerticalAlign::Top,
+                    HorizontalAlign::Right,
+                    plot.tick_label_color,
+                    font_handle,
+                );
+            } else {
+                let y = t * inner_half_size.y - font_size * 0.1;
+                // leaves room for the tick labels of the secondary x axis
+                if y < -inner_half_size.y + font_size * 1.2 || y > inner_half_size.y - font_size * 1.2 {
+                    continue;
                 }
+
+                spawn_axis_tick_labels(
+                    commands,
+                    plot_entity,
+                    &label,
+                    font_size,
+                    Vec3::new(inner_half_size.x - font_size * 0.2, y, text_z_plane),
+                    VerticalAlign::Top,
+                    HorizontalAlign::Right,
+                    plot.tick_label_color,
+                    font_handle,
+                );
             }
         }
     }
 }
 
+// On a date/time axis, the ticks fall on round times, or on the first day of a month when they are
+// a month or more apart, and they are labeled with a time or a date depending on the tick period.
+fn spawn_date_time_tick_labels(
+    commands: &mut Commands,
+    plot: &Plot,
+    plot_entity: Entity,
+    font_size: f32,
+    text_z_plane: f32,
+    font_handle: &Handle<Font>,
+    is_x_axis: bool,
+) {
+    let inner_half_size = plot.canvas_size / (1.0 + plot.outer_border) / 2.0;
+    let bounds = plot.scaled_bounds();
+
+    let (lo, up, period, scale) = if is_x_axis {
+        (bounds.lo.x, bounds.up.x, plot.tick_period.x, &plot.x_axis_scale)
+    } else {
+        (bounds.lo.y, bounds.up.y, plot.tick_period.y, &plot.y_axis_scale)
+    };
+
+    for (i, timestamp) in time_ticks(scale.timestamp(lo), scale.timestamp(up), period) {
+        if plot.hide_half_ticks && (i % 2).abs() == 1 {
+            continue;
+        }
+
+        let value = scale.time_coordinate(timestamp);
+        let label = format_time_tick(timestamp, period);
+
+        // position of the tick between -1 and 1
+        let t = (value - lo) / (up - lo) * 2.0 - 1.0;
+
+        let (position, h_align) = if is_x_axis {
+            let x = t * inner_half_size.x - font_size * 0.2;
+            if x < -inner_half_size.x + font_size * 3.0 || x > inner_half_size.x {
+                continue;
+            }
+            (
+                Vec2::new(x, -inner_half_size.y + font_size * 1.0),
+                HorizontalAlign::Right,
+            )
+        } else {
+            let y = t * inner_half_size.y - font_size * 0.1;
+            if y < -inner_half_size.y + font_size * 1.2 || y > inner_half_size.y {
+                continue;
+            }
+            (
+                Vec2::new(-inner_half_size.x + font_size * 0.2, y),
+                HorizontalAlign::Left,
+            )
+        };
+
+        spawn_axis_tick_labels(
+            commands,
+            plot_entity,
+            &label,
+            font_size,
+            position.extend(text_z_plane),
+            VerticalAlign::Top,
+            h_align,
+            plot.tick_label_color,
+            font_handle,
+        );
+    }
+}
+
+// On a categorical axis, the tick labels are the names of the categories, centered on the
+// integer coordinates. Some labels are skipped when the categories are too close to each other.
+fn spawn_category_tick_labels(
+    commands: &mut Commands,
+    plot: &Plot,
+    plot_entity: Entity,
+    font_size: f32,
+    text_z_plane: f32,
+    font_handle: &Handle<Font>,
+    is_x_axis: bool,
+) {
+    let categories = if is_x_axis {
+        &plot.x_categories
+    } else {
+        &plot.y_categories
+    };
+
+    let inner_half_size = plot.canvas_size / (1.0 + plot.outer_border) / 2.0;
+
+    // distance in pixels between two consecutive categories
+    let spacing = (plot.to_local(Vec2::ONE) - plot.to_local(Vec2::ZERO)).abs();
+
+    // rough estimate of the space taken by a label
+    let label_extent = if is_x_axis {
+        let max_chars = categories.iter().map(|c| c.chars().count()).max().unwrap_or(1);
+        (max_chars as f32 * font_size * 0.55) / spacing.x
+    } else {
+        (font_size * 1.2) / spacing.y
+    };
+    let stride = (label_extent.ceil() as usize).max(1);
+
+    for (k, category) in categories.iter().enumerate().step_by(stride) {
+        let center = plot.to_local(Vec2::splat(k as f32));
+
+        let (position, v_align, h_align) = if is_x_axis {
+            if center.x.abs() > inner_half_size.x - font_size {
+                continue;
+            }
+            (
+                Vec2::new(center.x, -inner_half_size.y + font_size * 1.0),
+                VerticalAlign::Top,
+                HorizontalAlign::Center,
+            )
+        } else {
+            if center.y.abs() > inner_half_size.y - font_size * 0.5 {
+                continue;
+            }
+            (
+                Vec2::new(-inner_half_size.x + font_size * 0.2, center.y),
+                VerticalAlign::Center,
+                HorizontalAlign::Left,
+            )
+        };
+
+        spawn_axis_tick_labels(
+            commands,
+            plot_entity,
+            category,
+            font_size,
+            position.extend(text_z_plane),
+            v_align,
+            h_align,
+            plot.tick_label_color,
+            font_handle,
+        );
+    }
+}
+
+// In polar mode, the radial tick labels are spawned along the θ = 0 line and the angular tick labels
+// (in degrees) are spawned on a circle centered on the pole, close to the edge of the canvas.
+fn spawn_polar_tick_labels(
+    commands: &mut Commands,
+    plot: &Plot,
+    plot_entity: Entity,
+    font_size: f32,
+    text_z_plane: f32,
+    font_handle: &Handle<Font>,
+) {
+    let bounds = plot.scaled_bounds();
+    let inner_half_size = plot.canvas_size / (1.0 + plot.outer_border) / 2.0;
+
+    let is_inside = |pos: Vec2, margin: Vec2| {
+        pos.x.abs() < inner_half_size.x - margin.x && pos.y.abs() < inner_half_size.y - margin.y
+    };
+
+    ///////////////////////////// radial labels /////////////////////////////
+    let r_max = bounds.lo.abs().max(bounds.up.abs()).length();
+    let num_rings = (r_max / plot.tick_period.y).floor() as i64;
+    let max_abs_r = plot.tick_period.y * num_rings as f32;
+
+    for i in 1..(num_rings + 1) {
+        if plot.hide_half_ticks && (i % 2).abs() == 1 {
+            continue;
+        }
+
+        let r = i as f32 * plot.tick_period.y;
+        let position =
+            plot.to_local(Vec2::new(0.0, r)) + Vec2::new(-font_size * 0.2, -font_size * 0.1);
+
+        if !is_inside(position, Vec2::new(font_size * 0.5, font_size * 1.2)) {
+            continue;
+        }
+
+        let r_str = format_numeric_label(
+            plot,
+            r,
+            !(0.01..1000.0).contains(&max_abs_r),
+            &AxisScale::Linear,
+        );
+
+        spawn_axis_tick_labels(
+            commands,
+            plot_entity,
+            &r_str,
+            font_size,
+            position.extend(text_z_plane),
+            VerticalAlign::Top,
+            HorizontalAlign::Right,
+            plot.tick_label_color,
+            font_handle,
+        );
+    }
+
+    ///////////////////////////// angular labels /////////////////////////////
+    let pole = plot.to_local(Vec2::ZERO);
+    let label_radius_in_pixels = (inner_half_size - pole.abs()).min_element() - font_size * 1.5;
+
+    // the pole is too close to the edge of the canvas (or outside of it)
+    if label_radius_in_pixels < font_size * 2.0 {
+        return;
+    }
+
+    let pixels_per_unit = inner_half_size * 2.0 / (bounds.up - bounds.lo);
+    let label_radius = label_radius_in_pixels / pixels_per_unit.min_element();
+
+    let num_spokes = (2.0 * std::f32::consts::PI / plot.tick_period.x).round() as i64;
+
+    for i in 0..num_spokes {
+        let theta = i as f32 * plot.tick_period.x;
+        let position = plot.to_local(Vec2::new(theta, label_radius));
+
+        spawn_axis_tick_labels(
+            commands,
+            plot_entity,
+            &format!("{}°", theta.to_degrees().round()),
+            font_size,
+            position.extend(text_z_plane),
+            VerticalAlign::Center,
+            HorizontalAlign::Center,
+            plot.tick_label_color,
+            font_handle,
+        );
+    }
+}
+
 // delays the update of the plot labels until the next frame, after which the
 // plot canvas is definitely spawned
 pub(crate) fn wait_for_graph_spawn(
@@ -383,9 +980,13 @@ pub(crate) fn spawn_graph(
 
         let canvas_material_handle = materials.add(material);
 
+        let mut canvas_commands = match event.canvas_entity {
+            Some(entity) => commands.entity(entity),
+            None => commands.spawn(),
+        };
+
         // quad
-        let plot_entity = commands
-            .spawn()
+        let plot_entity = canvas_commands
             .insert_bundle(MaterialMesh2dBundle {
                 mesh: Mesh2dHandle(meshes.add(Mesh::from(shape::Quad::new(plot.canvas_size)))),
                 material: canvas_material_handle.clone(),
@@ -419,7 +1020,37 @@ pub(crate) fn spawn_graph(
             });
     }
 }
-fn format_numeric_label(plot: &Plot, label: f32, scientific_notation: bool) -> String {
+/// Coordinates of a point as shown next to the target, e.g. "(0.50, 1.2e3)"
+pub(crate) fn format_coordinates(plot: &Plot, pos: Vec2) -> String {
+    let x = format_numeric_label(plot, pos.x, pos.x > 1000.0 || pos.x < 0.01, &plot.x_axis_scale);
+    let y = format_numeric_label(plot, pos.y, pos.y > 1000.0 || pos.y < 0.01, &plot.y_axis_scale);
+
+    format!("({}, {})", x, y)
+}
+
+fn format_numeric_label(
+    plot: &Plot,
+    label: f32,
+    scientific_notation: bool,
+    axis_scale: &AxisScale,
+) -> String {
+    if axis_scale.is_date_time() {
+        return format_date_time(axis_scale.timestamp(label));
+    }
+
+    // on a logarithmic axis, whole decades are shown as 10^n
+    if axis_scale.is_log() {
+        let exponent = label.log10();
+        if (exponent - exponent.round()).abs() < 1e-3 {
+            let exponent = exponent.round() as i32;
+            if (-2..=3).contains(&exponent) {
+                let decimals = (-exponent).max(0) as usize;
+                return format!("{:.1$}", 10_f32.powi(exponent), decimals);
+            }
+            return format!("1e{}", exponent);
+        }
+    }
+
     // scientific notation if the numbers are larger than 1000
     // if max_abs_y >= 1000.0 || max_abs_y < 0.01 {
     if scientific_notation {
@@ -433,20 +1064,37 @@ fn format_numeric_label(plot: &Plot, label: f32, scientific_notation: bool) -> S
     }
 }
 
+// Components of a canvas needed to find the canvas under the cursor, see `hovered_canvas`
+type HoverableCanvas<'a> = (
+    Entity,
+    &'a Canvas,
+    &'a GlobalTransform,
+    &'a Handle<Plot>,
+    &'a Handle<CanvasMaterial>,
+);
+
 pub(crate) fn update_mouse_target(
     // mut commands: Commands,
     mut my_canvas_mats: ResMut<Assets<CanvasMaterial>>,
     mut my_plots: ResMut<Assets<Plot>>,
     //
     // canvas_query: Query<(Entity, &GraphSprite, &Handle<CanvasMaterial>)>,
-    canvas_query: Query<(Entity, &mut Handle<CanvasMaterial>, &Handle<Plot>)>,
+    canvas_query: Query<HoverableCanvas>,
     mut update_target_labels_event: EventWriter<UpdateTargetLabelEvent>,
 
     cursor: Res<Cursor>,
     mouse_button_input: Res<Input<MouseButton>>,
 ) {
     if mouse_button_input.pressed(MouseButton::Middle) {
-        for (canvas_entity, canvas_material_handle, plot_handle) in canvas_query.iter() {
+        // only the plot under the cursor shows a target
+        let hovered = hovered_canvas(
+            canvas_query.iter().map(|(entity, canvas, transform, ..)| (entity, canvas, transform)),
+            cursor.position,
+        );
+
+        if let Some(Ok((canvas_entity, _, _, plot_handle, canvas_material_handle))) =
+            hovered.map(|entity| canvas_query.get(entity))
+        {
             // println!("{:?}", "CHANGING SHADER");
             // if let Some(plot) = my_canvas_mat.get_mut(plot_handle) {
             if let Some(plot) = my_plots.get_mut(plot_handle) {
@@ -476,10 +1124,42 @@ pub(crate) fn update_mouse_target(
     }
 }
 
+// Width of the strip of y tick labels along the left edge of the canvas, and height of the strip
+// of x tick labels along its bottom edge, in pixels
+const Y_TICK_LABEL_STRIP: f32 = 48.0;
+const X_TICK_LABEL_STRIP: f32 = 24.0;
+
+// Axes zoomed by the mousewheel: only x while holding X or over the x tick labels, only y while
+// holding Y or over the y tick labels, and both otherwise
+fn zoom_axes_under_cursor(
+    plot: &Plot,
+    keyboard_input: &Input<KeyCode>,
+    cursor_position: Vec2,
+) -> BVec2 {
+    if keyboard_input.pressed(KeyCode::X) {
+        return BVec2::new(true, false);
+    }
+    if keyboard_input.pressed(KeyCode::Y) {
+        return BVec2::new(false, true);
+    }
+
+    let local = cursor_position - plot.canvas_position;
+    let strips = Vec2::new(Y_TICK_LABEL_STRIP, X_TICK_LABEL_STRIP);
+    let near_edges = local.cmplt(plot.compute_bounds_world().lo + strips);
+
+    // the y labels lie along the left edge and the x labels along the bottom edge
+    let [on_y_labels, on_x_labels]: [bool; 2] = near_edges.into();
+
+    // in the corner where both strips meet, both axes are zoomed
+    BVec2::new(!on_y_labels || on_x_labels, !on_x_labels || on_y_labels)
+}
+
+// Mouse and keyboard input goes to the plot under the cursor only, see `hovered_canvas`
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn change_plot(
     mut commands: Commands,
     mut my_plots: ResMut<Assets<Plot>>,
-    canvas_query: Query<(Entity, &Canvas, &Handle<Plot>, &mut Handle<CanvasMaterial>)>,
+    canvas_query: Query<HoverableCanvas>,
 
     keyboard_input: Res<Input<KeyCode>>,
     cursor: Res<Cursor>,
@@ -491,61 +1171,125 @@ pub(crate) fn change_plot(
     mut update_plot_labels_event: EventWriter<UpdatePlotLabelsEvent>,
     mut update_target_labels_event: EventWriter<UpdateTargetLabelEvent>,
     mut windows: ResMut<Windows>,
+    legend_query: Query<(&LegendItem, &LegendEntry)>,
 ) {
-    for (canvas_entity, graph_sprite, plot_handle, canvas_material_handle) in canvas_query.iter() {
-        //
+    // the events are read once for all the canvases
+    let mouse_motions = mouse_motion_events.iter().map(|e| e.delta).collect::<Vec<Vec2>>();
+    let wheel_directions = mouse_wheel_events.iter().map(|e| e.y).collect::<Vec<f32>>();
+
+    for (_, _, _, plot_handle, _) in canvas_query.iter() {
         if let Some(plot) = my_plots.get_mut(plot_handle) {
             plot.plot_coord_mouse_pos = plot.world_to_plot(cursor.position);
+        }
+    }
+
+    let hovered = hovered_canvas(
+        canvas_query.iter().map(|(entity, canvas, transform, ..)| (entity, canvas, transform)),
+        cursor.position,
+    );
+
+    let hovered = if let Some(Ok(hovered)) = hovered.map(|entity| canvas_query.get(entity)) {
+        hovered
+    } else {
+        if let Some(window) = windows.get_primary_mut() {
+            window.set_cursor_icon(CursorIcon::Default);
+        }
+        if mouse_button_input.just_released(MouseButton::Left) {
+            release_all_event.send(ReleaseAllEvent);
+        }
+        return;
+    };
 
-            graph_sprite.hovered_on_plot_edges(cursor.position, &mut windows);
+    let (canvas_entity, graph_sprite, _, plot_handle, canvas_material_handle) = hovered;
 
-            for event in mouse_motion_events.iter() {
-                //
-                // When pressing P and moving the mouse, the tick period changes
-                if keyboard_input.pressed(KeyCode::P) {
-                    plot.tick_period.x *= 1.0 + (event.delta.x) / 1000.0;
-                    plot.tick_period.y *= 1.0 + (event.delta.y) / 1000.0;
+    if let Some(plot) = my_plots.get_mut(plot_handle) {
+        graph_sprite.hovered_on_plot_edges(cursor.position, &mut windows);
 
-                    plot.clamp_tick_period();
+        // a draggable marker under the cursor is dragged instead of the axes
+        let within_rect = graph_sprite.within_rect(cursor.position);
+        let drag = if within_rect {
+            draggable_point(plot, cursor.position)
+        } else {
+            None
+        };
 
-                    update_plot_labels_event.send(UpdatePlotLabelsEvent {
-                        plot_handle: plot_handle.clone(),
-                        canvas_entity,
-                    });
-                    update_target_labels_event.send(UpdateTargetLabelEvent {
-                        plot_handle: plot_handle.clone(),
-                        canvas_entity,
-                        canvas_material_handle: canvas_material_handle.clone(),
-                    });
-                }
+        if drag.is_some() {
+            if let Some(window) = windows.get_primary_mut() {
+                window.set_cursor_icon(CursorIcon::Hand);
             }
+        }
 
-            for wheel_event in mouse_wheel_events.iter() {
-                commands.entity(canvas_entity).insert(ZoomAxes {
-                    wheel_dir: wheel_event.y,
-                    mouse_pos: cursor.position,
-                });
+        for delta in mouse_motions.iter() {
+            //
+            // When pressing P and moving the mouse, the tick period changes
+            if keyboard_input.pressed(KeyCode::P) {
+                plot.tick_period.x *= 1.0 + (delta.x) / 1000.0;
+                plot.tick_period.y *= 1.0 + (delta.y) / 1000.0;
+
+                plot.clamp_tick_period();
 
                 update_plot_labels_event.send(UpdatePlotLabelsEvent {
                     plot_handle: plot_handle.clone(),
                     canvas_entity,
                 });
-
                 update_target_labels_event.send(UpdateTargetLabelEvent {
                     plot_handle: plot_handle.clone(),
                     canvas_entity,
                     canvas_material_handle: canvas_material_handle.clone(),
                 });
             }
+        }
+
+        // wheel zoom only applies when the cursor is within the canvas, not on its corners
+        let wheel_directions = if within_rect {
+            wheel_directions
+        } else {
+            Vec::new()
+        };
+
+        for wheel_direction in wheel_directions.iter() {
+            commands.entity(canvas_entity).insert(ZoomAxes {
+                wheel_dir: *wheel_direction,
+                axes: zoom_axes_under_cursor(plot, &keyboard_input, cursor.position),
+                mouse_pos: cursor.position,
+            });
+
+            update_plot_labels_event.send(UpdatePlotLabelsEvent {
+                plot_handle: plot_handle.clone(),
+                canvas_entity,
+            });
+
+            update_target_labels_event.send(UpdateTargetLabelEvent {
+                plot_handle: plot_handle.clone(),
+                canvas_entity,
+                canvas_material_handle: canvas_material_handle.clone(),
+            });
+        }
+
+        // a click on the legend toggles an entry, see `toggle_legend_entries`
+        let on_legend = on_legend_entry(&legend_query, plot_handle, cursor.position);
 
-            if mouse_button_input.just_pressed(MouseButton::Left) {
-                //
-                if graph_sprite.within_rect(cursor.position) {
+        if mouse_button_input.just_pressed(MouseButton::Left) && !on_legend {
+            //
+            // holding Shift drags out a rectangle to zoom on instead
+            let shift = keyboard_input.any_pressed([KeyCode::LShift, KeyCode::RShift]);
+
+            match drag {
+                _ if within_rect && shift && !plot.lock_zoom.all() => {
+                    commands.entity(canvas_entity).insert(BoxZoom {
+                        start: cursor.position,
+                    });
+                }
+                Some(drag) => {
+                    commands.entity(canvas_entity).insert(drag);
+                }
+                None if within_rect => {
                     commands.entity(canvas_entity).insert(MoveAxes);
                 }
-
-                graph_sprite.clicked_on_plot_corner(cursor.position, &mut commands, canvas_entity);
+                None => {}
             }
+
+            graph_sprite.clicked_on_plot_corner(cursor.position, &mut commands, canvas_entity);
         }
     }
 
@@ -574,6 +1318,99 @@ pub(crate) fn release_all(
     }
 }
 
+// Draws the rectangle of a box zoom while the left mouse button is held, and zooms exactly on it
+// once the button is released. Rectangles smaller than a few pixels are ignored.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn box_zoom(
+    mut commands: Commands,
+    mut plots: ResMut<Assets<Plot>>,
+    mut canvas_materials: ResMut<Assets<CanvasMaterial>>,
+    cursor: Res<Cursor>,
+    mouse_button_input: Res<Input<MouseButton>>,
+    query: Query<(Entity, &Handle<Plot>, &Handle<CanvasMaterial>, &BoxZoom)>,
+    mut update_plot_labels_event: EventWriter<UpdatePlotLabelsEvent>,
+    mut update_target_labels_event: EventWriter<UpdateTargetLabelEvent>,
+    mut respawn_all_event: EventWriter<RespawnAllEvent>,
+    mut spawn_beziercurve_event: EventWriter<SpawnBezierCurveEvent>,
+) {
+    let min_size = 4.0;
+
+    for (canvas_entity, plot_handle, material_handle, box_zoom) in query.iter() {
+        let released = !mouse_button_input.pressed(MouseButton::Left);
+        if released {
+            commands.entity(canvas_entity).remove::<BoxZoom>();
+        }
+
+        let (plot, canvas_material) = match (
+            plots.get_mut(plot_handle),
+            canvas_materials.get_mut(material_handle),
+        ) {
+            (Some(plot), Some(canvas_material)) => (plot, canvas_material),
+            _ => continue,
+        };
+
+        // the rectangle stays within the canvas
+        let bounds_world = plot.compute_bounds_world();
+        let lo_world = plot.canvas_position + bounds_world.lo;
+        let up_world = plot.canvas_position + bounds_world.up;
+        let start = box_zoom.start.max(lo_world).min(up_world);
+        let end = cursor.position.max(lo_world).min(up_world);
+
+        // the rectangle spans the whole range of the axes in `lock_zoom`
+        let lo = Vec2::select(plot.lock_zoom, lo_world, start.min(end));
+        let up = Vec2::select(plot.lock_zoom, up_world, start.max(end));
+
+        if !released {
+            canvas_material.selection = Vec4::new(lo.x, lo.y, up.x, up.y);
+            continue;
+        }
+
+        canvas_material.selection = Vec4::ZERO;
+
+        if (up - lo).cmplt(Vec2::splat(min_size)).any() {
+            continue;
+        }
+
+        // in polar mode, the bounds are cartesian coordinates
+        let to_bounds = |p: Vec2| plot.from_scaled(plot.to_view(plot.world_to_plot(p)));
+        let lo = Vec2::select(plot.lock_zoom, plot.bounds.lo, to_bounds(lo));
+        let up = Vec2::select(plot.lock_zoom, plot.bounds.up, to_bounds(up));
+
+        let previous = plot.scaled_bounds();
+        if plot.try_set_bounds(lo, up).is_err() {
+            continue;
+        }
+        plot.update_secondary_bounds(&previous);
+
+        canvas_material.update_all(plot);
+
+        update_plot_labels_event.send(UpdatePlotLabelsEvent {
+            plot_handle: plot_handle.clone(),
+            canvas_entity,
+        });
+
+        update_target_labels_event.send(UpdateTargetLabelEvent {
+            plot_handle: plot_handle.clone(),
+            canvas_entity,
+            canvas_material_handle: material_handle.clone(),
+        });
+
+        respawn_all_event.send(RespawnAllEvent {
+            plot_handle: plot_handle.clone(),
+        });
+
+        for (k, bezier_curve) in plot.data.bezier_groups.iter().enumerate() {
+            // So as to not spawn twice when show_animation is turned on
+            if !bezier_curve.show_animation {
+                spawn_beziercurve_event.send(SpawnBezierCurveEvent {
+                    group_number: k,
+                    plot_handle: plot_handle.clone(),
+                });
+            }
+        }
+    }
+}
+
 pub(crate) fn adjust_graph_size(
     mut canvas_query: Query<
         (
@@ -703,7 +1540,10 @@ pub(crate) fn adjust_graph_axes(
     if delta_pixels != Vec2::ZERO {
         for (canvas_entity, _graph_sprite, plot_handle, material_handle) in query.p0().iter_mut() {
             if let Some(plot) = plots.get_mut(plot_handle) {
-                plot.move_axes(delta_pixels);
+                // nothing to redraw if the plot is locked along the moved axes
+                if !plot.move_axes(delta_pixels) {
+                    continue;
+                }
 
                 update_plot_labels_event.send(UpdatePlotLabelsEvent {
                     plot_handle: plot_handle.clone(),
@@ -746,9 +1586,14 @@ pub(crate) fn adjust_graph_axes(
         query.p1().iter_mut()
     {
         //
+        commands.entity(canvas_entity).remove::<ZoomAxes>();
+
         if let Some(plot) = plots.get_mut(plot_handle) {
             //
-            plot.zoom_axes(zoom_info.wheel_dir);
+            // nothing to redraw if the zoomed axes are locked
+            if !plot.zoom_axes(zoom_info.wheel_dir, zoom_info.axes) {
+                continue;
+            }
 
             plot.clamp_tick_period();
 
@@ -777,13 +1622,71 @@ pub(crate) fn adjust_graph_axes(
                         })
                     }
                 });
+
+            change_canvas_material_event.send(RespawnAllEvent {
+                plot_handle: plot_handle.clone(),
+                // canvas_material_handle: material_handle.clone(),
+            });
         }
+    }
+}
 
-        commands.entity(canvas_entity).remove::<ZoomAxes>();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::{asset::AssetPlugin, ecs::event::Events};
 
-        change_canvas_material_event.send(RespawnAllEvent {
-            plot_handle: plot_handle.clone(),
-            // canvas_material_handle: material_handle.clone(),
-        });
+    fn spawn_canvas(app: &mut App, plot: Plot) -> Entity {
+        let size = plot.canvas_size;
+        let plot_handle = app.world.resource_mut::<Assets<Plot>>().add(plot);
+
+        let canvas_entity = app
+            .world
+            .spawn()
+            .insert(Canvas {
+                position: Vec2::ZERO,
+                previous_position: Vec2::ZERO,
+                original_size: size,
+                scale: Vec2::ONE,
+                previous_scale: Vec2::ONE,
+                hover_radius: 20.0,
+            })
+            .id();
+
+        app.world
+            .resource_mut::<Events<UpdatePlotLabelsEvent>>()
+            .send(UpdatePlotLabelsEvent { plot_handle, canvas_entity });
+
+        canvas_entity
+    }
+
+    fn num_labels(app: &mut App, canvas_entity: Entity) -> usize {
+        app.world
+            .query_filtered::<&Parent, With<PlotLabel>>()
+            .iter(&app.world)
+            .filter(|parent| parent.0 == canvas_entity)
+            .count()
+    }
+
+    #[test]
+    fn polar_plots_do_not_stop_the_refresh_of_other_canvases() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugin(AssetPlugin)
+            .add_asset::<Plot>()
+            .add_event::<UpdatePlotLabelsEvent>()
+            .insert_resource(TickLabelFont { maybe_font: Some(Handle::default()) })
+            .add_system(update_plot_labels);
+
+        let mut polar = Plot::default();
+        polar.set_polar(true);
+
+        let polar_canvas = spawn_canvas(&mut app, polar);
+        let cartesian_canvas = spawn_canvas(&mut app, Plot::default());
+
+        app.update();
+
+        assert!(num_labels(&mut app, polar_canvas) > 0);
+        assert!(num_labels(&mut app, cartesian_canvas) > 0);
     }
 }
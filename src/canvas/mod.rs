@@ -28,9 +28,15 @@ pub(crate) struct PlotLabel;
 #[derive(Component)]
 pub(crate) struct TargetLabel;
 
+// title and axis labels, laid out outside of the canvas
+#[derive(Component)]
+pub(crate) struct AxisLabel;
+
 pub(crate) struct SpawnGraphEvent {
     pub plot_handle: Handle<Plot>,
     pub canvas: Canvas,
+    /// Entity on which the canvas is spawned. A new entity is spawned if None.
+    pub canvas_entity: Option<Entity>,
 }
 
 pub(crate) enum Corner {
@@ -73,6 +79,14 @@ impl Canvas {
         return false;
     }
 
+    // True if the position is within the canvas or close enough to one of its corners to resize it
+    pub(crate) fn within_reach(&self, position: Vec2) -> bool {
+        let half_size = self.original_size * self.scale / 2.0 + self.hover_radius;
+        let offset = (position - self.position).abs();
+
+        offset.x < half_size.x && offset.y < half_size.y
+    }
+
     pub(crate) fn clicked_on_plot_corner(
         &self,
         position: Vec2,
@@ -163,12 +177,40 @@ impl Canvas {
     }
 }
 
+/// Topmost canvas under the cursor, which receives the mouse and keyboard input. Canvases are ordered
+/// by the z coordinate of their transform, and canvases on the same z plane by the order of their entities.
+pub(crate) fn hovered_canvas<'a>(
+    canvases: impl Iterator<Item = (Entity, &'a Canvas, &'a GlobalTransform)>,
+    position: Vec2,
+) -> Option<Entity> {
+    canvases
+        .filter(|(_, canvas, _)| canvas.within_reach(position))
+        .max_by(|(entity_a, _, transform_a), (entity_b, _, transform_b)| {
+            transform_a
+                .translation
+                .z
+                .partial_cmp(&transform_b.translation.z)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(entity_a.id().cmp(&entity_b.id()))
+        })
+        .map(|(entity, _, _)| entity)
+}
+
 #[derive(Component)]
 pub(crate) struct MoveAxes;
 
+/// Rectangle being dragged out with Shift and the left mouse button, to zoom on it once the
+/// button is released. `start` is the world position where the button was pressed.
+#[derive(Component)]
+pub(crate) struct BoxZoom {
+    pub start: Vec2,
+}
+
 #[derive(Component)]
 pub(crate) struct ZoomAxes {
     pub wheel_dir: f32,
+    /// Axes to zoom along, see `zoom_axes_under_cursor`
+    pub axes: BVec2,
     #[allow(dead_code)]
     pub mouse_pos: Vec2,
 }
@@ -212,6 +254,20 @@ pub(crate) struct CanvasMaterial {
 
     pub show_grid: f32,
     pub show_axes: f32,
+
+    /// If > 0.5, a polar grid is drawn instead of the rectangular one
+    pub polar: f32,
+
+
+    /// Lower left (xy) and upper right (zw) corners of the rectangle of a box zoom in world
+    /// coordinates. Nothing is drawn if the rectangle is empty.
+    pub selection: Vec4,
+
+    /// Placement of the grid lines of the x (xy) and y (zw) axes, see [`AxisScale::DateTime`]. If the
+    /// first value of an axis is zero, its grid lines are drawn at `offset + k * tick_period`, the
+    /// offset being the second value. Otherwise, they are drawn every that many calendar months,
+    /// the second value being the origin of the axis in days since the Unix epoch.
+    pub ticks: Vec4,
 }
 
 impl CanvasMaterial {
@@ -219,7 +275,7 @@ impl CanvasMaterial {
         CanvasMaterial {
             mouse_pos: Vec2::ZERO,
             tick_period: plot.tick_period,
-            bounds: plot.bounds.clone(),
+            bounds: plot.scaled_bounds(),
             time: 0.0,
             zoom: 1.0,
             size: plot.canvas_size,
@@ -237,16 +293,21 @@ impl CanvasMaterial {
             target_color: col_to_vec4(plot.target_color),
             show_grid: if plot.show_grid { 1.0 } else { 0.0 },
             show_axes: if plot.show_axes { 1.0 } else { 0.0 },
+            polar: if plot.polar { 1.0 } else { 0.0 },
+            selection: Vec4::ZERO,
+            ticks: plot.grid_ticks(),
         }
     }
 
-    /// Updates all the shader parameters except the mouse_pos, which is updated every frame anyway.
+    /// Updates all the shader parameters except the mouse_pos, which is updated every frame anyway,
+    /// and the box zoom selection.
     pub fn update_all(&mut self, plot: &Plot) {
         // mouse_pos is supposed to be in World coordinates // self.mouse_pos = plot.plot_coord_mouse_pos;
 
         self.position = plot.canvas_position;
         self.tick_period = plot.tick_period;
-        self.bounds = plot.bounds.clone();
+        self.ticks = plot.grid_ticks();
+        self.bounds = plot.scaled_bounds();
         self.zoom = plot.zoom;
         self.time = plot.time;
         self.size = plot.canvas_size;
@@ -262,6 +323,7 @@ impl CanvasMaterial {
         self.target_color = col_to_vec4(plot.target_color);
         self.show_grid = if plot.show_grid { 1.0 } else { 0.0 };
         self.show_axes = if plot.show_axes { 1.0 } else { 0.0 };
+        self.polar = if plot.polar { 1.0 } else { 0.0 };
     }
 
     /// Checks whether position is inside the plot bounderies or not.
@@ -0,0 +1,156 @@
+use bevy::prelude::*;
+
+use crate::inputs::*;
+use crate::plot::*;
+use crate::tooltip::*;
+
+/// Sent when the left mouse button is released after dragging a marker of a group with the
+/// `draggable` field set, see [`DragConstraints`].
+#[derive(Debug, Clone)]
+pub struct PlotDataEdited {
+    pub plot: Handle<Plot>,
+
+    /// Index of the group in `data.marker_groups`
+    pub group_index: usize,
+
+    /// Index of the marker in the `data` field of its group
+    pub point_index: usize,
+
+    /// Coordinates of the marker before it was dragged
+    pub old_value: Vec2,
+
+    /// Coordinates of the marker after it was dragged
+    pub new_value: Vec2,
+}
+
+/// Marker being dragged on a canvas, inserted by `change_plot` instead of `MoveAxes` when the
+/// left mouse button is pressed over a draggable marker
+#[derive(Component)]
+pub(crate) struct DragPoint {
+    pub group: usize,
+    pub index: usize,
+    pub start_value: Vec2,
+}
+
+/// The draggable marker within the `pick_radius` of the plot closest to `position`, in world coordinates
+pub(crate) fn draggable_point(plot: &mut Plot, position: Vec2) -> Option<DragPoint> {
+    if !plot
+        .data
+        .marker_groups
+        .iter()
+        .any(|g| g.draggable.is_some())
+    {
+        return None;
+    }
+
+    let element = plot.nearest_data_element(position, plot.pick_radius)?;
+    if element.group_kind != DataKind::Marker {
+        return None;
+    }
+
+    plot.data.marker_groups[element.group_index]
+        .draggable
+        .as_ref()?;
+
+    Some(DragPoint {
+        group: element.group_index,
+        index: element.point_index?,
+        start_value: element.value,
+    })
+}
+
+// Coordinates of the dragged marker for a cursor at `position`, in world coordinates
+fn dragged_value(plot: &Plot, drag: &DragPoint, position: Vec2) -> Option<Vec2> {
+    let marker_plot = plot.data.marker_groups.get(drag.group)?;
+    let constraints = marker_plot.draggable.as_ref()?;
+
+    // the marker stays within the canvas
+    let bounds_world = plot.compute_bounds_world();
+    let local = (position - plot.canvas_position)
+        .max(bounds_world.lo)
+        .min(bounds_world.up);
+
+    let mut value = plot.world_to_plot(local + plot.canvas_position);
+
+    // the grid is drawn for the primary axes, in their scaled space
+    if constraints.snap_to_grid && !plot.polar {
+        let scaled = plot.to_scaled(value);
+        let snapped = Vec2::new(
+            plot.x_axis_scale.nearest_tick(scaled.x, plot.tick_period.x),
+            plot.y_axis_scale.nearest_tick(scaled.y, plot.tick_period.y),
+        );
+        value = plot.from_scaled(snapped);
+    }
+
+    let mut value = plot
+        .secondary_map(marker_plot.secondary)
+        .to_secondary(value);
+    value = Vec2::select(constraints.axes, value, drag.start_value);
+
+    if constraints.monotonic_x {
+        let data = &marker_plot.data;
+        if let Some(previous) = drag.index.checked_sub(1).and_then(|k| data.get(k)) {
+            value.x = value.x.max(previous.x);
+        }
+        if let Some(next) = data.get(drag.index + 1) {
+            value.x = value.x.min(next.x);
+        }
+    }
+
+    if value.is_finite() {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+// Moves the dragged markers with the cursor, and sends a `PlotDataEdited` event once the left
+// mouse button is released
+pub(crate) fn drag_data_points(
+    mut commands: Commands,
+    mut plots: ResMut<Assets<Plot>>,
+    cursor: Res<Cursor>,
+    mouse_button_input: Res<Input<MouseButton>>,
+    query: Query<(Entity, &Handle<Plot>, &DragPoint)>,
+    mut data_edited_event: EventWriter<PlotDataEdited>,
+) {
+    for (canvas_entity, plot_handle, drag) in query.iter() {
+        let released = !mouse_button_input.pressed(MouseButton::Left);
+        if released {
+            commands.entity(canvas_entity).remove::<DragPoint>();
+        }
+
+        let plot = match plots.get_mut(plot_handle) {
+            Some(plot) => plot,
+            None => continue,
+        };
+
+        let current = plot
+            .data
+            .marker_groups
+            .get(drag.group)
+            .and_then(|marker_plot| marker_plot.data.get(drag.index))
+            .copied();
+
+        let current = match current {
+            Some(current) => current,
+            // the data was edited elsewhere during the drag
+            None => continue,
+        };
+
+        let value = dragged_value(plot, drag, cursor.position).unwrap_or(current);
+        if value != current {
+            plot.set_marker_point(drag.group, drag.index, value);
+        }
+
+        if released {
+            data_edited_event.send(PlotDataEdited {
+                plot: plot_handle.clone(),
+                group_index: drag.group,
+                point_index: drag.index,
+                old_value: drag.start_value,
+                new_value: value,
+            });
+        }
+    }
+}
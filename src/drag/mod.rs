@@ -0,0 +1,3 @@
+pub mod drag;
+#[allow(unused_imports)]
+pub use drag::*;
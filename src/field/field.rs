@@ -0,0 +1,470 @@
+use bevy::{
+    core::cast_slice,
+    ecs::system::{lifetimeless::SRes, SystemParamItem},
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        render_asset::{PrepareAssetError, RenderAsset, RenderAssets},
+        render_resource::{
+            std140::{AsStd140, Std140},
+            *,
+        },
+        renderer::RenderDevice,
+    },
+    sprite::{Material2d, Material2dPipeline, MaterialMesh2dBundle, Mesh2dHandle},
+};
+
+use crate::plot::*;
+use crate::util::*;
+
+/// Component that serves as identification for the nth group of either the `implicit_groups`
+/// or the `heatmap_groups` field of [`PlotData`].
+#[derive(Component, Clone, Copy)]
+pub(crate) enum FieldGroup {
+    Implicit(usize),
+    Heatmap(usize),
+}
+
+/// Uniform sent to field.wgsl
+#[derive(Clone, AsStd140)]
+pub(crate) struct FieldUniform {
+    pub color: Vec4,
+    pub fill_color: Vec4,
+    pub inner_canvas_size_in_pixels: Vec2,
+
+    /// Number of samples along each axis of the texture
+    pub grid_size: Vec2,
+
+    /// Thickness of the curve g(x, y) = 0. Nothing is drawn if set to 0
+    pub size: f32,
+
+    /// If set to > 0.5, the region where g(x, y) < 0 is filled with `fill_color`
+    pub fill: f32,
+
+    /// If set to > 0.5, the texture contains colors to be displayed as is
+    pub heatmap: f32,
+}
+
+/// Material of the quad covering the inner canvas. The field is sampled on the CPU and
+/// sent to the shader as a texture.
+#[derive(TypeUuid, Clone)]
+#[uuid = "4a8f2f6e-1f53-4c71-9bd4-2a3c5e7d8b90"]
+pub(crate) struct FieldMaterial {
+    pub uniform: FieldUniform,
+    pub texture: Handle<Image>,
+}
+
+pub(crate) struct GpuFieldMaterial {
+    _buffer: Buffer,
+    bind_group: BindGroup,
+}
+
+pub(crate) struct FieldMesh2dPlugin;
+
+pub const FIELD_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 5938271650348125549);
+
+impl Plugin for FieldMesh2dPlugin {
+    fn build(&self, app: &mut App) {
+        let mut shaders = app.world.get_resource_mut::<Assets<Shader>>().unwrap();
+
+        shaders.set_untracked(
+            FIELD_SHADER_HANDLE.clone(),
+            Shader::from_wgsl(include_str!("field.wgsl")),
+        );
+    }
+}
+
+impl Material2d for FieldMaterial {
+    fn fragment_shader(_asset_server: &AssetServer) -> Option<Handle<Shader>> {
+        Some(FIELD_SHADER_HANDLE.clone().typed::<Shader>())
+    }
+
+    fn bind_group(render_asset: &<Self as RenderAsset>::PreparedAsset) -> &BindGroup {
+        &render_asset.bind_group
+    }
+
+    fn bind_group_layout(render_device: &RenderDevice) -> BindGroupLayout {
+        render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: BufferSize::new(
+                            FieldUniform::std140_size_static() as u64
+                        ),
+                    },
+                    count: None,
+                },
+                // samples of the field, read with textureLoad
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        multisampled: false,
+                        sample_type: TextureSampleType::Float { filterable: false },
+                        view_dimension: TextureViewDimension::D2,
+                    },
+                    count: None,
+                },
+            ],
+            label: Some("field_material_layout"),
+        })
+    }
+}
+
+impl RenderAsset for FieldMaterial {
+    type ExtractedAsset = FieldMaterial;
+    type PreparedAsset = GpuFieldMaterial;
+    type Param = (
+        SRes<RenderDevice>,
+        SRes<Material2dPipeline<Self>>,
+        SRes<RenderAssets<Image>>,
+    );
+
+    fn extract_asset(&self) -> Self::ExtractedAsset {
+        self.clone()
+    }
+
+    fn prepare_asset(
+        extracted_asset: Self::ExtractedAsset,
+        (render_device, material_pipeline, gpu_images): &mut SystemParamItem<Self::Param>,
+    ) -> Result<Self::PreparedAsset, PrepareAssetError<Self::ExtractedAsset>> {
+        // the texture might not be uploaded yet
+        let texture_view = if let Some(gpu_image) = gpu_images.get(&extracted_asset.texture) {
+            &gpu_image.texture_view
+        } else {
+            return Err(PrepareAssetError::RetryNextUpdate(extracted_asset));
+        };
+
+        let uniform_std140 = extracted_asset.uniform.as_std140();
+
+        let buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
+            contents: uniform_std140.as_bytes(),
+            label: Some("field_uniform_buffer"),
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+        });
+
+        let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: buffer.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::TextureView(texture_view),
+                },
+            ],
+            label: Some("field_bind_group"),
+            layout: &material_pipeline.material2d_layout,
+        });
+
+        Ok(GpuFieldMaterial {
+            _buffer: buffer,
+            bind_group,
+        })
+    }
+}
+
+// Positions, in plot coordinates, of a grid of samples covering the inner canvas at half the
+// pixel resolution. The first row lies at the top of the canvas. Sampling in screen space
+// keeps the resolution constant whatever the axis scales and the polar mode.
+fn sample_positions(plot: &Plot) -> (UVec2, Vec<Vec2>, Vec2) {
+    let inner_size = plot.canvas_size / (1.0 + plot.outer_border);
+
+    let grid_size = (inner_size / 2.0).ceil().as_uvec2() + UVec2::ONE;
+    let step = inner_size / (grid_size - UVec2::ONE).as_vec2();
+
+    let mut positions = Vec::with_capacity((grid_size.x * grid_size.y) as usize);
+    for j in 0..grid_size.y {
+        for i in 0..grid_size.x {
+            let local = Vec2::new(
+                -inner_size.x / 2.0 + i as f32 * step.x,
+                inner_size.y / 2.0 - j as f32 * step.y,
+            );
+            positions.push(plot.world_to_plot(local + plot.canvas_position));
+        }
+    }
+
+    (grid_size, positions, step)
+}
+
+fn make_field_image(grid_size: UVec2, texels: Vec<[f32; 4]>) -> Image {
+    Image::new(
+        Extent3d {
+            width: grid_size.x,
+            height: grid_size.y,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        cast_slice(&texels).to_vec(),
+        TextureFormat::Rgba32Float,
+    )
+}
+
+// Samples g and the norm of its gradient in pixel units, from which the shader computes the
+// distance to the curve g = 0 in pixels.
+fn sample_implicit(plot: &Plot, implicit: &ImplicitData, time: f32) -> (UVec2, Image) {
+    let (grid_size, positions, step) = sample_positions(plot);
+    let (w, h) = (grid_size.x as usize, grid_size.y as usize);
+
+    let gs = positions
+        .iter()
+        .map(|p| (implicit.function)(p.x, p.y, time))
+        .collect::<Vec<f32>>();
+
+    let mut texels = Vec::with_capacity(gs.len());
+    for j in 0..h {
+        for i in 0..w {
+            let g = gs[j * w + i];
+
+            // central differences, one-sided at the edges of the grid
+            let (i0, i1) = (i.saturating_sub(1), (i + 1).min(w - 1));
+            let (j0, j1) = (j.saturating_sub(1), (j + 1).min(h - 1));
+
+            let dg_dx = (gs[j * w + i1] - gs[j * w + i0]) / ((i1 - i0) as f32 * step.x);
+            let dg_dy = (gs[j1 * w + i] - gs[j0 * w + i]) / ((j1 - j0) as f32 * step.y);
+            let grad = Vec2::new(dg_dx, dg_dy).length();
+
+            if g.is_finite() && grad.is_finite() {
+                texels.push([g, grad, 1.0, 0.0]);
+            } else {
+                texels.push([0.0, 0.0, 0.0, 0.0]);
+            }
+        }
+    }
+
+    (grid_size, make_field_image(grid_size, texels))
+}
+
+fn implicit_uniform(plot: &Plot, implicit: &ImplicitData, grid_size: UVec2) -> FieldUniform {
+    FieldUniform {
+        color: col_to_vec4(implicit.color),
+        fill_color: col_to_vec4(implicit.fill_color.unwrap_or(Color::NONE)),
+        inner_canvas_size_in_pixels: plot.canvas_size / (1.0 + plot.outer_border),
+        grid_size: grid_size.as_vec2(),
+        size: if implicit.line_style == LineStyle::None {
+            0.0
+        } else {
+            implicit.size
+        },
+        fill: if implicit.fill_color.is_some() { 1.0 } else { 0.0 },
+        heatmap: 0.0,
+    }
+}
+
+// Value of a heatmap at the point `p` in plot coordinates. Returns None outside of the extent of a grid.
+fn heatmap_value(heatmap: &HeatmapData, p: Vec2, time: f32) -> Option<f32> {
+    match &heatmap.source {
+        HeatmapSource::Function(f) => Some(f(p.x, p.y, time)),
+
+        HeatmapSource::Grid {
+            values,
+            width,
+            height,
+            lo,
+            up,
+        } => {
+            let dims = Vec2::new(*width as f32, *height as f32);
+
+            // position in units of cells
+            let cell = (p - *lo) / (*up - *lo) * dims;
+
+            if !(cell.x >= 0.0 && cell.y >= 0.0 && cell.x < dims.x && cell.y < dims.y) {
+                return None;
+            }
+
+            if !heatmap.interpolate {
+                let (i, j) = (cell.x as usize, cell.y as usize);
+                return Some(values[j * width + i]);
+            }
+
+            // bilinear interpolation between the centers of the cells
+            let c = (cell - 0.5).clamp(Vec2::ZERO, dims - 1.0);
+            let (i0, j0) = (c.x as usize, c.y as usize);
+            let (i1, j1) = ((i0 + 1).min(width - 1), (j0 + 1).min(height - 1));
+            let f = c - Vec2::new(i0 as f32, j0 as f32);
+
+            let bottom = values[j0 * width + i0] * (1.0 - f.x) + values[j0 * width + i1] * f.x;
+            let top = values[j1 * width + i0] * (1.0 - f.x) + values[j1 * width + i1] * f.x;
+
+            Some(bottom * (1.0 - f.y) + top * f.y)
+        }
+    }
+}
+
+// Samples the heatmap and maps the samples to colors
+fn sample_heatmap(plot: &Plot, heatmap: &HeatmapData, time: f32) -> (UVec2, Image) {
+    let (grid_size, positions, _step) = sample_positions(plot);
+
+    let values = positions
+        .iter()
+        .map(|p| heatmap_value(heatmap, *p, time))
+        .collect::<Vec<Option<f32>>>();
+
+    let (lo, up) = heatmap.value_range.unwrap_or_else(|| match &heatmap.source {
+        // the colors of a grid do not change when panning or zooming
+        HeatmapSource::Grid { values, .. } => value_range(values.iter().copied()),
+        HeatmapSource::Function(_) => value_range(values.iter().flatten().copied()),
+    });
+
+    let texels = values
+        .iter()
+        .map(|v| match v {
+            Some(v) if v.is_finite() => {
+                let color = heatmap.colormap.color_in_range(*v, lo, up);
+                [color.r(), color.g(), color.b(), color.a() * heatmap.alpha]
+            }
+            _ => [0.0, 0.0, 0.0, 0.0],
+        })
+        .collect::<Vec<[f32; 4]>>();
+
+    (grid_size, make_field_image(grid_size, texels))
+}
+
+fn heatmap_uniform(plot: &Plot, grid_size: UVec2) -> FieldUniform {
+    FieldUniform {
+        color: Vec4::ZERO,
+        fill_color: Vec4::ZERO,
+        inner_canvas_size_in_pixels: plot.canvas_size / (1.0 + plot.outer_border),
+        grid_size: grid_size.as_vec2(),
+        size: 0.0,
+        fill: 0.0,
+        heatmap: 1.0,
+    }
+}
+
+// Samples a field group. Returns None if the group does not exist anymore.
+fn make_field(plot: &Plot, group: FieldGroup, time: f32) -> Option<(FieldUniform, Image)> {
+    match group {
+        FieldGroup::Implicit(k) => plot.data.implicit_groups.get(k).map(|implicit| {
+            let (grid_size, image) = sample_implicit(plot, implicit, time);
+            (implicit_uniform(plot, implicit, grid_size), image)
+        }),
+        FieldGroup::Heatmap(k) => plot.data.heatmap_groups.get(k).map(|heatmap| {
+            let (grid_size, image) = sample_heatmap(plot, heatmap, time);
+            (heatmap_uniform(plot, grid_size), image)
+        }),
+    }
+}
+
+fn is_animated(plot: &Plot, group: FieldGroup) -> bool {
+    match group {
+        FieldGroup::Implicit(k) => plot
+            .data
+            .implicit_groups
+            .get(k)
+            .is_some_and(|implicit| implicit.show_animation),
+        FieldGroup::Heatmap(k) => plot.data.heatmap_groups.get(k).is_some_and(|heatmap| {
+            heatmap.show_animation && matches!(heatmap.source, HeatmapSource::Function(_))
+        }),
+    }
+}
+
+fn spawn_fields(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    images: &mut Assets<Image>,
+    materials: &mut Assets<FieldMaterial>,
+    plot: &Plot,
+    plot_handle: &Handle<Plot>,
+    time: f32,
+) {
+    let inner_size = plot.canvas_size / (1.0 + plot.outer_border);
+
+    // heatmaps lie below the implicit curves
+    let groups = (0..plot.data.heatmap_groups.len())
+        .map(|k| (FieldGroup::Heatmap(k), 0.4))
+        .chain((0..plot.data.implicit_groups.len()).map(|k| (FieldGroup::Implicit(k), 0.5)));
+
+    for (group, z) in groups {
+        if let Some((uniform, image)) = make_field(plot, group, time) {
+            let material = FieldMaterial {
+                uniform,
+                texture: images.add(image),
+            };
+
+            commands
+                .spawn()
+                .insert_bundle(MaterialMesh2dBundle {
+                    mesh: Mesh2dHandle(meshes.add(Mesh::from(shape::Quad::new(inner_size)))),
+                    material: materials.add(material),
+                    transform: Transform::from_translation(plot.canvas_position.extend(z)),
+                    ..Default::default()
+                })
+                .insert(group)
+                .insert(plot_handle.clone())
+                .insert(PlotElement);
+        }
+    }
+}
+
+/// Resamples the implicit functions and heatmaps of a plot each time the plot is respawned
+/// (e.g. after panning or zooming).
+pub(crate) fn fields_setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut images: ResMut<Assets<Image>>,
+    mut materials: ResMut<Assets<FieldMaterial>>,
+    mut change_canvas_material_event: EventReader<RespawnAllEvent>,
+    mut plots: ResMut<Assets<Plot>>,
+    query: Query<(Entity, &Handle<Plot>), With<FieldGroup>>,
+    time: Res<Time>,
+) {
+    let t = time.seconds_since_startup() as f32;
+
+    for event in change_canvas_material_event.iter() {
+        //
+        for (entity, plot_handle) in query.iter() {
+            if event.plot_handle == *plot_handle {
+                commands.entity(entity).despawn();
+            }
+        }
+
+        if let Some(plot) = plots.get_mut(&event.plot_handle) {
+            plot.compute_zeros();
+
+            spawn_fields(
+                &mut commands,
+                &mut meshes,
+                &mut images,
+                &mut materials,
+                plot,
+                &event.plot_handle,
+                t,
+            );
+        }
+    }
+}
+
+/// Resamples every frame the implicit functions and heatmaps whose `show_animation` field is true.
+pub(crate) fn animate_fields(
+    mut images: ResMut<Assets<Image>>,
+    mut materials: ResMut<Assets<FieldMaterial>>,
+    plots: Res<Assets<Plot>>,
+    query: Query<(&Handle<Plot>, &Handle<FieldMaterial>, &FieldGroup)>,
+    time: Res<Time>,
+) {
+    let t = time.seconds_since_startup() as f32;
+
+    for (plot_handle, material_handle, group) in query.iter() {
+        if let Some(plot) = plots.get(plot_handle) {
+            if !is_animated(plot, *group) {
+                continue;
+            }
+
+            if let Some((uniform, image)) = make_field(plot, *group, t) {
+                // a new texture is created, since modifying the image would not
+                // update the bind group of the material
+                if let Some(material) = materials.get_mut(material_handle) {
+                    material.uniform = uniform;
+                    material.texture = images.add(image);
+                }
+            }
+        }
+    }
+}
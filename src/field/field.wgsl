@@ -0,0 +1,102 @@
+type float4 = vec4<f32>;
+type float2 = vec2<f32>;
+
+struct FieldUniform {
+    color: float4;
+    fill_color: float4;
+    inner_canvas_size_in_pixels: float2;
+    grid_size: float2;
+    size: f32;
+    fill: f32;
+    heatmap: f32;
+};
+
+[[group(1), binding(0)]]
+var<uniform> field: FieldUniform;
+
+// For implicit curves, each texel contains (g, norm of the gradient of g in pixel units, validity, unused).
+// For heatmaps, each texel contains the color to be displayed.
+[[group(1), binding(1)]]
+var field_texture: texture_2d<f32>;
+
+struct FragmentInput {
+    [[location(0)]] world_position: vec4<f32>;
+    [[location(1)]] world_normal: vec3<f32>;
+    [[location(2)]] uv: vec2<f32>;
+};
+
+fn sdRoundedBox(p: vec2<f32>, b: vec2<f32>, r: vec4<f32>) -> f32 {
+  var x = r.x;
+  var y = r.y;
+  x = select(r.z, r.x, p.x > 0.);
+  y = select(r.w, r.y, p.x > 0.);
+  x  = select(y, x, p.y > 0.);
+  let q = abs(p) - b + x;
+  return min(max(q.x, q.y), 0.) + length(max(q, vec2<f32>(0.))) - x;
+}
+
+fn load_texel(p: vec2<i32>) -> float4 {
+    let max_index = vec2<i32>(field.grid_size) - vec2<i32>(1, 1);
+    return textureLoad(field_texture, clamp(p, vec2<i32>(0, 0), max_index), 0);
+}
+
+// Bilinear interpolation between the four closest samples. Done by hand
+// since 32 bit float textures cannot be filtered.
+fn sample_field(uv: float2) -> float4 {
+    let p = uv * (field.grid_size - 1.0);
+    let p0 = floor(p);
+    let f = p - p0;
+    let i0 = vec2<i32>(p0);
+
+    let a = load_texel(i0);
+    let b = load_texel(i0 + vec2<i32>(1, 0));
+    let c = load_texel(i0 + vec2<i32>(0, 1));
+    let d = load_texel(i0 + vec2<i32>(1, 1));
+
+    return mix(mix(a, b, f.x), mix(c, d, f.x), f.y);
+}
+
+[[stage(fragment)]]
+fn fragment(in: FragmentInput) -> [[location(0)]] vec4<f32> {
+    let s = sample_field(in.uv);
+
+    var out_col = float4(0.0, 0.0, 0.0, 0.0);
+
+    if (field.heatmap > 0.5) {
+        out_col = s;
+    } else {
+        // the function could not be evaluated around this pixel
+        if (s.z < 0.99) {
+            return out_col;
+        }
+
+        // inequality region
+        if (field.fill > 0.5 && s.x < 0.0) {
+            out_col = field.fill_color;
+        }
+
+        // distance to the curve g = 0 in pixels, using a first order approximation
+        let d = abs(s.x) / max(s.y, 0.000001);
+
+        let solid = field.size;
+        let w = 1.0 + field.size;
+        let curve = 1.0 - smoothStep(solid, w + solid, d);
+
+        if (field.size > 0.0) {
+            out_col = mix(out_col, field.color, curve);
+        }
+    }
+
+    // mask with the canvas
+    let local = (in.uv - 0.5) * field.inner_canvas_size_in_pixels * float2(1.0, -1.0);
+    let r = 0.02 * field.inner_canvas_size_in_pixels.x;
+    let dm = sdRoundedBox(
+        local,
+        field.inner_canvas_size_in_pixels / 2.0 - 1.0, float4(r,r,r,r)
+    );
+
+    let sm = smoothStep(-2.0, 0.0, dm);
+    out_col.a = out_col.a * (1.0 - sm);
+
+    return out_col;
+}
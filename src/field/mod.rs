@@ -0,0 +1,3 @@
+pub mod field;
+#[allow(unused_imports)]
+pub use field::*;
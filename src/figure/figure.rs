@@ -0,0 +1,396 @@
+use bevy::{prelude::*, window::WindowResized};
+
+use crate::bezier::*;
+use crate::canvas::*;
+use crate::plot::*;
+
+/// Axes that are kept synchronized between the plots of a [`Figure`]. When the user pans or zooms
+/// one plot, the range of the shared axis is copied to the other plots of the same group.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum ShareAxis {
+    #[default]
+    None,
+
+    /// The plots starting on the same row share the axis
+    Row,
+
+    /// The plots starting on the same column share the axis
+    Column,
+
+    /// All the plots of the figure share the axis
+    All,
+}
+
+/// Cell of a [`Figure`] holding a plot, spanning `row_span` rows and `col_span` columns
+#[derive(Debug, Clone)]
+pub struct FigureCell {
+    pub plot_handle: Handle<Plot>,
+    pub row: usize,
+    pub col: usize,
+    pub row_span: usize,
+    pub col_span: usize,
+}
+
+/// Lays out plots on a grid of `rows` by `cols` cells covering the primary window. The
+/// `canvas_position` and `canvas_size` of each plot are overwritten, and recomputed whenever the
+/// window is resized. The figure is a component: spawn it on its own entity, next to the plot handles.
+///
+/// ```ignore
+/// let mut figure = Figure::new(2, 2);
+/// figure.add(plots.add(top_left_plot), 0, 0);
+/// figure.add(plots.add(top_right_plot), 0, 1);
+/// figure.add_span(plots.add(bottom_plot), 1, 0, 1, 2);
+/// figure.share_x = ShareAxis::Column;
+///
+/// for cell in figure.cells.iter() {
+///     commands.spawn().insert(cell.plot_handle.clone());
+/// }
+/// commands.spawn().insert(figure);
+/// ```
+#[derive(Debug, Clone, Component)]
+pub struct Figure {
+    pub rows: usize,
+    pub cols: usize,
+
+    /// Plots of the figure. Use [`Figure::add`] or [`Figure::add_span`] to add a plot.
+    pub cells: Vec<FigureCell>,
+
+    /// Space between two neighboring cells in pixels, leaving room for the axis labels and titles
+    /// of the plots
+    pub spacing: Vec2,
+
+    /// Space between the cells and the edges of the window in pixels
+    pub margin: Vec2,
+
+    /// Title shown above all the plots, with the font of the first plot that has one or the [`TickLabelFont`].
+    pub title: Option<String>,
+
+    pub title_font_size: f32,
+
+    pub title_color: Color,
+
+    pub share_x: ShareAxis,
+
+    pub share_y: ShareAxis,
+
+    // bounds of each plot at the previous frame, used to detect which plot was panned or zoomed
+    pub(crate) previous_bounds: Vec<Option<(Vec2, Vec2)>>,
+}
+
+impl Figure {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        if rows == 0 || cols == 0 {
+            panic!("a Figure needs at least one row and one column");
+        }
+
+        Figure {
+            rows,
+            cols,
+            cells: Vec::new(),
+            spacing: Vec2::new(70.0, 70.0),
+            margin: Vec2::new(50.0, 40.0),
+            title: None,
+            title_font_size: 28.0,
+            title_color: Color::BLACK,
+            share_x: ShareAxis::None,
+            share_y: ShareAxis::None,
+            previous_bounds: Vec::new(),
+        }
+    }
+
+    /// Places a plot in the cell at `row` and `col`, counted from the top left corner.
+    pub fn add(&mut self, plot_handle: Handle<Plot>, row: usize, col: usize) {
+        self.add_span(plot_handle, row, col, 1, 1);
+    }
+
+    /// Places a plot in the cell at `row` and `col`, stretched over `row_span` rows and
+    /// `col_span` columns. Panics if the plot does not fit in the grid.
+    pub fn add_span(
+        &mut self,
+        plot_handle: Handle<Plot>,
+        row: usize,
+        col: usize,
+        row_span: usize,
+        col_span: usize,
+    ) {
+        if row_span == 0 || col_span == 0 {
+            panic!("the row_span and col_span of a Figure cell must be at least 1");
+        } else if row + row_span > self.rows || col + col_span > self.cols {
+            panic!(
+                "a Figure cell spanning rows {}..{} and columns {}..{} does not fit in a {}x{} grid",
+                row,
+                row + row_span,
+                col,
+                col + col_span,
+                self.rows,
+                self.cols
+            );
+        }
+
+        self.cells.push(FigureCell {
+            plot_handle,
+            row,
+            col,
+            row_span,
+            col_span,
+        });
+    }
+
+    // Height of the band reserved for the title at the top of the window
+    fn title_height(&self) -> f32 {
+        if self.title.is_some() {
+            self.title_font_size * 1.5
+        } else {
+            0.0
+        }
+    }
+
+    /// Center and size in world coordinates of a cell, for a window of size `window_size`.
+    pub fn cell_rect(&self, cell: &FigureCell, window_size: Vec2) -> (Vec2, Vec2) {
+        let grid = Vec2::new(self.cols as f32, self.rows as f32);
+        let available =
+            window_size - 2.0 * self.margin - Vec2::new(0.0, self.title_height());
+
+        let cell_size = ((available - (grid - 1.0) * self.spacing) / grid).max(Vec2::ONE);
+        let span = Vec2::new(cell.col_span as f32, cell.row_span as f32);
+        let size = span * cell_size + (span - 1.0) * self.spacing;
+
+        let left = -window_size.x / 2.0
+            + self.margin.x
+            + cell.col as f32 * (cell_size.x + self.spacing.x);
+        let top = window_size.y / 2.0
+            - self.margin.y
+            - self.title_height()
+            - cell.row as f32 * (cell_size.y + self.spacing.y);
+
+        (Vec2::new(left + size.x / 2.0, top - size.y / 2.0), size)
+    }
+
+    // Whether the plots in cells a and b share an axis, given how the axis is shared
+    fn shared(share: &ShareAxis, a: &FigureCell, b: &FigureCell) -> bool {
+        match share {
+            ShareAxis::None => false,
+            ShareAxis::Row => a.row == b.row,
+            ShareAxis::Column => a.col == b.col,
+            ShareAxis::All => true,
+        }
+    }
+}
+
+/// Component of the title of a figure
+#[derive(Component)]
+pub(crate) struct FigureTitle {
+    figure_entity: Entity,
+}
+
+// Places the plots of the figures when a figure is spawned and when the window is resized.
+// Plots that have already been spawned are respawned with their new position and size.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn layout_figures(
+    mut commands: Commands,
+    windows: Res<Windows>,
+    maybe_font: Res<TickLabelFont>,
+    mut window_resized_events: EventReader<WindowResized>,
+    mut plots: ResMut<Assets<Plot>>,
+    figure_query: Query<(Entity, &Figure)>,
+    added_figure_query: Query<Entity, Added<Figure>>,
+    canvas_query: Query<(Entity, &Handle<Plot>), With<Canvas>>,
+    children_query: Query<&Children>,
+    title_query: Query<(Entity, &FigureTitle)>,
+    mut spawn_graph_event: EventWriter<SpawnGraphEvent>,
+) {
+    let window_resized = window_resized_events.iter().count() > 0;
+
+    let window_size = if let Some(window) = windows.get_primary() {
+        Vec2::new(window.width(), window.height())
+    } else {
+        return;
+    };
+
+    for (figure_entity, figure) in figure_query.iter() {
+        if !window_resized && added_figure_query.get(figure_entity).is_err() {
+            continue;
+        }
+
+        for cell in figure.cells.iter() {
+            if let Some(plot) = plots.get_mut(&cell.plot_handle) {
+                let (position, size) = figure.cell_rect(cell, window_size);
+                plot.canvas_position = position;
+                plot.canvas_size = size;
+                plot.compute_zeros();
+                plot.point_index = None;
+
+                // a plot that has already been spawned is respawned from scratch, on the same
+                // canvas entity
+                for (canvas_entity, plot_handle) in canvas_query.iter() {
+                    if plot_handle == &cell.plot_handle {
+                        let children = children_query.get(canvas_entity);
+                        for child in children.iter().flat_map(|children| children.iter()) {
+                            commands.entity(*child).despawn_recursive();
+                        }
+                        spawn_graph_event.send(SpawnGraphEvent {
+                            canvas: plot.make_canvas(),
+                            plot_handle: plot_handle.clone(),
+                            canvas_entity: Some(canvas_entity),
+                        });
+                    }
+                }
+            }
+        }
+
+        for (entity, title) in title_query.iter() {
+            if title.figure_entity == figure_entity {
+                commands.entity(entity).despawn();
+            }
+        }
+
+        // the title has the font of the first plot that has one
+        let font = figure
+            .cells
+            .iter()
+            .filter_map(|cell| plots.get(&cell.plot_handle))
+            .find_map(|plot| plot.font.clone())
+            .or_else(|| maybe_font.maybe_font.clone());
+
+        if let (Some(title), Some(font_handle)) = (&figure.title, font) {
+            let text_style = TextStyle {
+                font: font_handle.clone(),
+                font_size: figure.title_font_size,
+                color: figure.title_color,
+            };
+            let text_alignment = TextAlignment {
+                vertical: VerticalAlign::Top,
+                horizontal: HorizontalAlign::Center,
+            };
+
+            commands
+                .spawn_bundle(Text2dBundle {
+                    text: Text::with_section(title, text_style, text_alignment),
+                    transform: Transform::from_translation(Vec3::new(
+                        0.0,
+                        window_size.y / 2.0 - figure.margin.y / 2.0,
+                        1.0001,
+                    )),
+                    ..Default::default()
+                })
+                .insert(FigureTitle { figure_entity });
+        }
+    }
+}
+
+// Copies the range of the shared axes from a plot that has been panned or zoomed to the other
+// plots of its group. At the first frame, the first plot of each group sets the range of the group.
+pub(crate) fn sync_shared_axes(
+    mut figure_query: Query<&mut Figure>,
+    mut plots: ResMut<Assets<Plot>>,
+    canvas_query: Query<(Entity, &Handle<Plot>, &Handle<CanvasMaterial>)>,
+    mut canvas_materials: ResMut<Assets<CanvasMaterial>>,
+    mut update_plot_labels_event: EventWriter<UpdatePlotLabelsEvent>,
+    mut respawn_all_event: EventWriter<RespawnAllEvent>,
+    mut spawn_beziercurve_event: EventWriter<SpawnBezierCurveEvent>,
+) {
+    for mut figure in figure_query.iter_mut() {
+        if figure.share_x == ShareAxis::None && figure.share_y == ShareAxis::None {
+            continue;
+        }
+
+        let num_cells = figure.cells.len();
+        figure.previous_bounds.resize(num_cells, None);
+
+        let bounds = figure
+            .cells
+            .iter()
+            .map(|cell| {
+                plots
+                    .get(&cell.plot_handle)
+                    .map(|plot| (plot.bounds.lo, plot.bounds.up))
+            })
+            .collect::<Vec<Option<(Vec2, Vec2)>>>();
+
+        let changed = (0..num_cells)
+            .filter(|&k| bounds[k].is_some() && bounds[k] != figure.previous_bounds[k])
+            .collect::<Vec<usize>>();
+
+        for &k in changed.iter() {
+            figure.previous_bounds[k] = bounds[k];
+        }
+
+        // The first changed plot of a group is the source of the shared range. The shared plots
+        // form groups since being in the same row (or column) is transitive.
+        let source = |share: &ShareAxis, target: usize| {
+            changed
+                .iter()
+                .find(|&&k| {
+                    k == target || Figure::shared(share, &figure.cells[k], &figure.cells[target])
+                })
+                .filter(|&&k| k != target)
+                .copied()
+        };
+
+        let sources = (0..num_cells)
+            .map(|target| (source(&figure.share_x, target), source(&figure.share_y, target)))
+            .collect::<Vec<(Option<usize>, Option<usize>)>>();
+
+        for (target, (source_x, source_y)) in sources.into_iter().enumerate() {
+            if source_x.is_none() && source_y.is_none() {
+                continue;
+            }
+
+            let tick_period = |k: Option<usize>| {
+                k.and_then(|k| plots.get(&figure.cells[k].plot_handle))
+                    .map(|plot| plot.tick_period)
+            };
+            let (tick_x, tick_y) = (tick_period(source_x), tick_period(source_y));
+
+            let plot_handle = figure.cells[target].plot_handle.clone();
+            if let Some(plot) = plots.get_mut(&plot_handle) {
+                let previous = plot.scaled_bounds();
+
+                if let (Some(k), Some(tick)) = (source_x, tick_x) {
+                    let (lo, up) = bounds[k].unwrap();
+                    plot.bounds.lo.x = lo.x;
+                    plot.bounds.up.x = up.x;
+                    plot.tick_period.x = tick.x;
+                }
+                if let (Some(k), Some(tick)) = (source_y, tick_y) {
+                    let (lo, up) = bounds[k].unwrap();
+                    plot.bounds.lo.y = lo.y;
+                    plot.bounds.up.y = up.y;
+                    plot.tick_period.y = tick.y;
+                }
+                plot.update_secondary_bounds(&previous);
+                plot.compute_zeros();
+                plot.point_index = None;
+
+                for (canvas_entity, canvas_plot_handle, material_handle) in canvas_query.iter() {
+                    if canvas_plot_handle != &plot_handle {
+                        continue;
+                    }
+                    if let Some(canvas_material) = canvas_materials.get_mut(material_handle) {
+                        canvas_material.update_all(plot);
+                    }
+                    update_plot_labels_event.send(UpdatePlotLabelsEvent {
+                        plot_handle: plot_handle.clone(),
+                        canvas_entity,
+                    });
+                }
+
+                respawn_all_event.send(RespawnAllEvent {
+                    plot_handle: plot_handle.clone(),
+                });
+
+                for (k, bezier_curve) in plot.data.bezier_groups.iter().enumerate() {
+                    // So as to not spawn twice when show_animation is turned on
+                    if !bezier_curve.show_animation {
+                        spawn_beziercurve_event.send(SpawnBezierCurveEvent {
+                            group_number: k,
+                            plot_handle: plot_handle.clone(),
+                        });
+                    }
+                }
+
+                figure.previous_bounds[target] = Some((plot.bounds.lo, plot.bounds.up));
+            }
+        }
+    }
+}
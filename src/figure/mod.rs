@@ -0,0 +1,3 @@
+pub mod figure;
+#[allow(unused_imports)]
+pub use figure::*;
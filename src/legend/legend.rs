@@ -0,0 +1,654 @@
+use bevy::{
+    prelude::*,
+    sprite::{MaterialMesh2dBundle, Mesh2dHandle},
+};
+
+use itertools_num::linspace;
+
+use crate::bars::*;
+use crate::bezier::*;
+use crate::inputs::*;
+use crate::markers::*;
+use crate::plot::*;
+use crate::segments::*;
+use crate::util::*;
+
+/// Placement of the legend box with respect to the canvas.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum LegendPosition {
+    TopLeft,
+    #[default]
+    TopRight,
+    BottomLeft,
+    BottomRight,
+
+    /// To the right of the canvas
+    OutsideRight,
+
+    /// The inner corner of the canvas covering the fewest data points
+    Best,
+}
+
+/// Appearance of the legend, which lists the marker, segment and function groups of [`PlotData`]
+/// that have a label (see `Opt::Label`). Groups sharing a label are merged into a single entry,
+/// such that a line with markers shows both. Clicking on an entry hides or shows its groups.
+#[derive(Debug, Clone)]
+pub struct Legend {
+    /// The legend is shown as soon as a group has a label, unless `show` is false
+    pub show: bool,
+
+    pub position: LegendPosition,
+
+    /// Color of the box
+    pub background_color: Color,
+
+    /// Color of the border of the box
+    pub edge_color: Color,
+
+    pub font_color: Color,
+
+    pub font_size: f32,
+
+    /// Font of the labels. If None, the font of the plot is used, and the legend is not shown if
+    /// there is no font at all.
+    pub font: Option<Handle<Font>>,
+}
+
+impl Default for Legend {
+    fn default() -> Self {
+        Legend {
+            show: true,
+            position: LegendPosition::TopRight,
+            background_color: Color::rgba(0.1, 0.08, 0.14, 0.85),
+            edge_color: Color::rgb(0.45, 0.45, 0.5),
+            font_color: Color::rgb(0.9, 0.9, 0.9),
+            font_size: 14.0,
+            font: None,
+        }
+    }
+}
+
+/// Component of every entity making up the legend of a plot
+#[derive(Component)]
+pub(crate) struct LegendItem {
+    plot_handle: Handle<Plot>,
+}
+
+/// Clickable row of the legend. `lo` and `up` are the corners of the row in world coordinates.
+#[derive(Component)]
+pub(crate) struct LegendEntry {
+    label: String,
+    lo: Vec2,
+    up: Vec2,
+}
+
+impl LegendEntry {
+    fn contains(&self, p: Vec2) -> bool {
+        p.x >= self.lo.x && p.x <= self.up.x && p.y >= self.lo.y && p.y <= self.up.y
+    }
+}
+
+/// Whether `position` lies on an entry of the legend of the plot, where a click toggles the entry
+/// instead of panning the plot
+pub(crate) fn on_legend_entry(
+    query: &Query<(&LegendItem, &LegendEntry)>,
+    plot_handle: &Handle<Plot>,
+    position: Vec2,
+) -> bool {
+    query
+        .iter()
+        .any(|(item, entry)| &item.plot_handle == plot_handle && entry.contains(position))
+}
+
+// The legend is drawn on top of the target label
+const LEGEND_Z_PLANE: f32 = 1.3;
+
+// Canvas size given to the shaders of the legend, which is large enough to disable their canvas mask
+// and lets the legend be drawn outside of the canvas
+const UNMASKED_CANVAS_SIZE: Vec2 = bevy::math::const_vec2!([1.0e5, 1.0e5]);
+
+// Width of the line and marker sample of an entry, in pixels
+const SAMPLE_WIDTH: f32 = 36.0;
+
+const PADDING: f32 = 8.0;
+
+// Line of a legend entry, taken from a segment or function group
+struct LineSample {
+    color: Color,
+    size: f32,
+    line_style: LineStyle,
+    dash_length: f32,
+}
+
+struct EntryStyle {
+    label: String,
+    line: Option<LineSample>,
+    marker: Option<MarkerData>,
+    bar: Option<BarData>,
+    hidden: bool,
+}
+
+// Entries of the legend in the order of the groups, merging the groups that share a label
+fn collect_entries(plot: &Plot) -> Vec<EntryStyle> {
+    let mut entries: Vec<EntryStyle> = Vec::new();
+
+    let mut entry = |label: &Option<String>, hidden: bool| -> Option<usize> {
+        let label = label.as_ref()?;
+
+        match entries.iter().position(|e| &e.label == label) {
+            Some(k) => {
+                entries[k].hidden &= hidden;
+                Some(k)
+            }
+            None => {
+                entries.push(EntryStyle {
+                    label: label.clone(),
+                    line: None,
+                    marker: None,
+                    bar: None,
+                    hidden,
+                });
+                Some(entries.len() - 1)
+            }
+        }
+    };
+
+    let mut lines = Vec::new();
+    let mut markers = Vec::new();
+
+    for segments in plot.data.segment_groups.iter() {
+        if let Some(k) = entry(&segments.label, segments.hidden) {
+            lines.push((
+                k,
+                LineSample {
+                    color: segments.color,
+                    size: segments.size,
+                    line_style: segments.line_style.clone(),
+                    dash_length: segments.dash_length,
+                },
+            ));
+        }
+    }
+
+    for curve in plot.data.bezier_groups.iter() {
+        if let Some(k) = entry(&curve.label, curve.hidden) {
+            if curve.line_style != LineStyle::None {
+                lines.push((
+                    k,
+                    LineSample {
+                        color: curve.color,
+                        size: curve.size,
+                        line_style: curve.line_style.clone(),
+                        dash_length: curve.dash_length,
+                    },
+                ));
+            }
+        }
+    }
+
+    for marker_group in plot.data.marker_groups.iter() {
+        if let Some(k) = entry(&marker_group.label, marker_group.hidden) {
+            markers.push((k, marker_group.clone()));
+        }
+    }
+
+    let mut bars = Vec::new();
+
+    for bar_group in plot.data.bar_groups.iter() {
+        if let Some(k) = entry(&bar_group.label, bar_group.hidden) {
+            bars.push((k, bar_group.clone()));
+        }
+    }
+
+    for (k, line) in lines {
+        entries[k].line.get_or_insert(line);
+    }
+
+    for (k, bar) in bars {
+        entries[k].bar.get_or_insert(bar);
+    }
+
+    for (k, marker) in markers {
+        entries[k].marker.get_or_insert(marker);
+    }
+
+    entries
+}
+
+// Sample of the displayed data in world coordinates modulo the canvas position, used to place
+// the legend away from the data
+fn data_sample(plot: &Plot) -> Vec<Vec2> {
+    let mut points = Vec::new();
+
+    for segments in plot.data.segment_groups.iter().filter(|g| !g.hidden) {
+        let secondary_map = plot.secondary_map(segments.secondary);
+        points.extend(
+            segments
+                .data
+                .iter()
+                .map(|p| plot.to_local(secondary_map.to_primary(*p))),
+        );
+    }
+
+    for marker_group in plot.data.marker_groups.iter().filter(|g| !g.hidden) {
+        let secondary_map = plot.secondary_map(marker_group.secondary);
+        points.extend(
+            marker_group
+                .data
+                .iter()
+                .map(|p| plot.to_local(secondary_map.to_primary(*p))),
+        );
+    }
+
+    let bounds = plot.scaled_bounds();
+    let num_samples = 64;
+
+    for curve in plot.data.bezier_groups.iter().filter(|g| !g.hidden) {
+        let secondary_map = plot.secondary_map(curve.secondary);
+        match &curve.parametric {
+            Some(parametric) => {
+                let (t0, t1) = parametric.t_range;
+                points.extend(linspace(t0, t1, num_samples).map(|t| {
+                    plot.to_local(secondary_map.to_primary((parametric.function)(t, plot.time)))
+                }));
+            }
+            None if !plot.polar => {
+                points.extend(linspace(bounds.lo.x, bounds.up.x, num_samples).map(|x| {
+                    let x = secondary_map.to_secondary(Vec2::new(plot.x_axis_scale.inverse(x), 0.0)).x;
+                    let y = (curve.function)(x, plot.time);
+                    plot.to_local(secondary_map.to_primary(Vec2::new(x, y)))
+                }));
+            }
+            None => {}
+        }
+    }
+
+    points.retain(|p| p.is_finite());
+    points
+}
+
+// Lower left corner of the legend box of size `size`, in world coordinates modulo the canvas position
+fn place_legend(plot: &Plot, size: Vec2) -> Vec2 {
+    let inner_half = plot.canvas_size / (1.0 + plot.outer_border) / 2.0;
+    let margin = 10.0;
+
+    let top_right = inner_half - margin - size;
+    let bottom_left = -inner_half + margin;
+
+    let corner = |position: &LegendPosition| match position {
+        LegendPosition::TopLeft => Vec2::new(bottom_left.x, top_right.y),
+        LegendPosition::TopRight | LegendPosition::Best => top_right,
+        LegendPosition::BottomLeft => bottom_left,
+        LegendPosition::BottomRight => Vec2::new(top_right.x, bottom_left.y),
+        LegendPosition::OutsideRight => {
+            Vec2::new(plot.canvas_size.x / 2.0 + margin, inner_half.y - size.y)
+        }
+    };
+
+    if plot.legend.position != LegendPosition::Best {
+        return corner(&plot.legend.position);
+    }
+
+    let points = data_sample(plot);
+    let covered = |lo: Vec2| {
+        let up = lo + size;
+        points
+            .iter()
+            .filter(|p| p.x >= lo.x && p.x <= up.x && p.y >= lo.y && p.y <= up.y)
+            .count()
+    };
+
+    // the first corner wins in case of a tie
+    [
+        LegendPosition::TopRight,
+        LegendPosition::TopLeft,
+        LegendPosition::BottomRight,
+        LegendPosition::BottomLeft,
+    ]
+    .iter()
+    .map(corner)
+    .min_by_key(|lo| covered(*lo))
+    .unwrap()
+}
+
+fn faded(mut color: Color, hidden: bool) -> Color {
+    if hidden {
+        color.set_a(color.a() * 0.3);
+    }
+    color
+}
+
+#[allow(clippy::too_many_arguments)]
+fn spawn_legend(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<BarMaterial>>,
+    plot: &Plot,
+    plot_handle: &Handle<Plot>,
+    font: &Handle<Font>,
+) {
+    let entries = collect_entries(plot);
+    if entries.is_empty() {
+        return;
+    }
+
+    let legend = &plot.legend;
+    let font_size = legend.font_size;
+    let row_height = font_size * 1.6;
+
+    // the text is not laid out yet, so its width is estimated from the number of characters
+    let max_chars = entries
+        .iter()
+        .map(|e| e.label.chars().count())
+        .max()
+        .unwrap_or(0);
+    let text_width = max_chars as f32 * font_size * 0.55;
+
+    let size = Vec2::new(
+        PADDING * 3.0 + SAMPLE_WIDTH + text_width,
+        PADDING * 2.0 + row_height * entries.len() as f32,
+    );
+
+    let lo = place_legend(plot, size);
+    let up = lo + size;
+
+    let item = || LegendItem {
+        plot_handle: plot_handle.clone(),
+    };
+
+    // box
+    if let Some(mesh) = make_rects_mesh(&[(lo, up)]) {
+        commands
+            .spawn_bundle(MaterialMesh2dBundle {
+                mesh: Mesh2dHandle(meshes.add(mesh)),
+                material: materials.add(BarMaterial {
+                    color: col_to_vec4(legend.background_color),
+                    edge_color: col_to_vec4(legend.edge_color),
+                    inner_canvas_size_in_pixels: UNMASKED_CANVAS_SIZE,
+                    canvas_position_in_pixels: plot.canvas_position,
+                    draw_contour: 1.0,
+                }),
+                transform: Transform::from_translation(
+                    plot.canvas_position.extend(LEGEND_Z_PLANE),
+                ),
+                ..Default::default()
+            })
+            .insert(item());
+    }
+
+    for (k, entry) in entries.iter().enumerate() {
+        let y = up.y - PADDING - (k as f32 + 0.5) * row_height;
+        let x0 = lo.x + PADDING;
+        let x1 = x0 + SAMPLE_WIDTH;
+
+        // a square of the color of the bars, behind the line and the marker
+        if let Some(bar) = &entry.bar {
+            let half_side = row_height * 0.3;
+            let center = Vec2::new((x0 + x1) / 2.0, y);
+
+            if let Some(mesh) = make_rects_mesh(&[(center - half_side, center + half_side)]) {
+                commands
+                    .spawn_bundle(MaterialMesh2dBundle {
+                        mesh: Mesh2dHandle(meshes.add(mesh)),
+                        material: materials.add(BarMaterial {
+                            color: col_to_vec4(faded(bar.color, entry.hidden)),
+                            edge_color: col_to_vec4(faded(bar.edge_color, entry.hidden)),
+                            inner_canvas_size_in_pixels: UNMASKED_CANVAS_SIZE,
+                            canvas_position_in_pixels: plot.canvas_position,
+                            draw_contour: if bar.draw_contour { 1.0 } else { 0.0 },
+                        }),
+                        transform: Transform::from_translation(
+                            plot.canvas_position.extend(LEGEND_Z_PLANE + 0.005),
+                        ),
+                        ..Default::default()
+                    })
+                    .insert(item());
+            }
+        }
+
+        if let Some(line) = &entry.line {
+            if let Some(mesh) = make_segment_mesh(&[(Vec2::new(x0, y), Vec2::new(x1, y))]) {
+                commands
+                    .spawn_bundle((
+                        SegmentMesh2d::default(),
+                        Mesh2dHandle(meshes.add(mesh)),
+                        GlobalTransform::default(),
+                        Transform::from_translation(
+                            plot.canvas_position.extend(LEGEND_Z_PLANE + 0.01),
+                        ),
+                        Visibility::default(),
+                        ComputedVisibility::default(),
+                    ))
+                    .insert(SegmentUniform {
+                        mech: 0.0,
+                        segment_thickness: line.size,
+                        hole_size: 1.0,
+                        zoom: 1.0,
+                        color: col_to_vec4(faded(line.color, entry.hidden)),
+                        inner_canvas_size_in_pixels: UNMASKED_CANVAS_SIZE,
+                        canvas_position: plot.canvas_position,
+                        style: line.line_style.to_int32(),
+                        // short enough for the pattern to be visible in the sample
+                        dash_length: line.dash_length.min(SAMPLE_WIDTH / 4.0),
+                    })
+                    .insert(item());
+            }
+        }
+
+        if let Some(marker) = &entry.marker {
+            let mut uniform = MarkerUniform::new(plot, marker);
+            uniform.inner_canvas_size_in_pixels = UNMASKED_CANVAS_SIZE;
+            uniform.color = col_to_vec4(faded(marker.color, entry.hidden));
+            uniform.marker_point_color = col_to_vec4(faded(marker.marker_point_color, entry.hidden));
+
+            let center = plot.canvas_position + Vec2::new((x0 + x1) / 2.0, y);
+            let instance = MarkerInstanceData {
+                position: center.extend(0.0),
+                scale: (row_height / 30.0).min(1.0),
+                color: uniform.color.to_array(),
+            };
+
+            let marker_entity = spawn_marker_instances(
+                commands,
+                meshes,
+                vec![instance],
+                uniform,
+                LEGEND_Z_PLANE + 0.02,
+            );
+            commands.entity(marker_entity).insert(item());
+        }
+
+        let text_style = TextStyle {
+            font: font.clone(),
+            font_size,
+            color: faded(legend.font_color, entry.hidden),
+        };
+        let text_alignment = TextAlignment {
+            vertical: VerticalAlign::Center,
+            horizontal: HorizontalAlign::Left,
+        };
+        let text_position = plot.canvas_position + Vec2::new(x1 + PADDING, y);
+
+        commands
+            .spawn_bundle(Text2dBundle {
+                text: Text::with_section(entry.label.clone(), text_style, text_alignment),
+                transform: Transform::from_translation(text_position.extend(LEGEND_Z_PLANE + 0.03)),
+                ..Default::default()
+            })
+            .insert(item())
+            .insert(LegendEntry {
+                label: entry.label.clone(),
+                lo: plot.canvas_position + Vec2::new(lo.x, y - row_height / 2.0),
+                up: plot.canvas_position + Vec2::new(up.x, y + row_height / 2.0),
+            });
+    }
+}
+
+/// Rebuilds the legend of a plot each time the plot is respawned.
+pub(crate) fn legend_setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<BarMaterial>>,
+    mut change_canvas_material_event: EventReader<RespawnAllEvent>,
+    plots: Res<Assets<Plot>>,
+    maybe_font: Res<TickLabelFont>,
+    query: Query<(Entity, &LegendItem)>,
+) {
+    for event in change_canvas_material_event.iter() {
+        //
+        for (entity, item) in query.iter() {
+            if item.plot_handle == event.plot_handle {
+                commands.entity(entity).despawn();
+            }
+        }
+
+        if let Some(plot) = plots.get(&event.plot_handle) {
+            let font = plot.legend.font.as_ref().or(plot.label_font(&maybe_font));
+
+            if let (true, Some(font)) = (plot.legend.show, font) {
+                spawn_legend(
+                    &mut commands,
+                    &mut meshes,
+                    &mut materials,
+                    plot,
+                    &event.plot_handle,
+                    font,
+                );
+            }
+        }
+    }
+}
+
+/// Hides or shows the groups of a legend entry when it is clicked.
+pub(crate) fn toggle_legend_entries(
+    mouse_button_input: Res<Input<MouseButton>>,
+    cursor: Res<Cursor>,
+    mut plots: ResMut<Assets<Plot>>,
+    query: Query<(&LegendItem, &LegendEntry)>,
+    mut respawn_event: EventWriter<RespawnAllEvent>,
+    mut spawn_beziercurve_event: EventWriter<SpawnBezierCurveEvent>,
+) {
+    if !mouse_button_input.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    for (item, entry) in query.iter() {
+        if !entry.contains(cursor.position) {
+            continue;
+        }
+
+        if let Some(plot) = plots.get_mut(&item.plot_handle) {
+            let label = Some(entry.label.clone());
+            let data = &mut plot.data;
+
+            // the entry is shown if any of its groups is shown
+            let hidden = data
+                .segment_groups
+                .iter()
+                .filter(|g| g.label == label)
+                .map(|g| g.hidden)
+                .chain(data.marker_groups.iter().filter(|g| g.label == label).map(|g| g.hidden))
+                .chain(data.bezier_groups.iter().filter(|g| g.label == label).map(|g| g.hidden))
+                .chain(data.bar_groups.iter().filter(|g| g.label == label).map(|g| g.hidden))
+                .all(|h| h);
+
+            for group in data.segment_groups.iter_mut().filter(|g| g.label == label) {
+                group.hidden = !hidden;
+            }
+            for group in data.marker_groups.iter_mut().filter(|g| g.label == label) {
+                group.hidden = !hidden;
+            }
+            for group in data.errorbar_groups.iter_mut().filter(|g| g.label == label) {
+                group.hidden = !hidden;
+            }
+            for group in data.bar_groups.iter_mut().filter(|g| g.label == label) {
+                group.hidden = !hidden;
+            }
+            for (k, group) in data.bezier_groups.iter_mut().enumerate() {
+                if group.label == label {
+                    group.hidden = !hidden;
+
+                    spawn_beziercurve_event.send(SpawnBezierCurveEvent {
+                        group_number: k,
+                        plot_handle: item.plot_handle.clone(),
+                    });
+                }
+            }
+
+            plot.point_index = None;
+
+            respawn_event.send(RespawnAllEvent {
+                plot_handle: item.plot_handle.clone(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::{asset::AssetPlugin, ecs::system::SystemState};
+
+    #[test]
+    fn clicking_an_entry_toggles_labelled_bars() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugin(AssetPlugin)
+            .add_asset::<Plot>()
+            .add_event::<RespawnAllEvent>()
+            .add_event::<SpawnBezierCurveEvent>()
+            .insert_resource(Cursor::default())
+            .insert_resource(Input::<MouseButton>::default())
+            .add_system(toggle_legend_entries);
+
+        let mut plot = Plot::default();
+        plot.plotopt_hist(vec![1.0, 2.0, 2.5], vec![Opt::Label("samples".to_string())]);
+        plot.plotopt_bars(["a", "b"], vec![vec![1.0, 2.0]], vec![Opt::Label("counts".to_string())]);
+
+        let entries = collect_entries(&plot);
+        assert!(entries.iter().all(|entry| entry.bar.is_some()));
+
+        let plot_handle = app.world.resource_mut::<Assets<Plot>>().add(plot);
+        app.world
+            .spawn()
+            .insert(LegendItem { plot_handle: plot_handle.clone() })
+            .insert(LegendEntry {
+                label: "counts".to_string(),
+                lo: Vec2::new(-10.0, -10.0),
+                up: Vec2::new(10.0, 10.0),
+            });
+
+        let hidden = |app: &App| {
+            let plot = app.world.resource::<Assets<Plot>>().get(&plot_handle).unwrap();
+            plot.data.bar_groups.iter().map(|g| g.hidden).collect::<Vec<bool>>()
+        };
+
+        app.world.resource_mut::<Input<MouseButton>>().press(MouseButton::Left);
+        app.update();
+        assert_eq!(hidden(&app), vec![false, true]);
+
+        // a click outside of the entry does nothing
+        app.world.resource_mut::<Cursor>().position = Vec2::new(20.0, 0.0);
+        let mut mouse_button_input = app.world.resource_mut::<Input<MouseButton>>();
+        mouse_button_input.reset(MouseButton::Left);
+        mouse_button_input.press(MouseButton::Left);
+        app.update();
+        assert_eq!(hidden(&app), vec![false, true]);
+
+        // a second click on the entry shows the bars again
+        app.world.resource_mut::<Cursor>().position = Vec2::ZERO;
+        let mut mouse_button_input = app.world.resource_mut::<Input<MouseButton>>();
+        mouse_button_input.reset(MouseButton::Left);
+        mouse_button_input.press(MouseButton::Left);
+        app.update();
+        assert_eq!(hidden(&app), vec![false, false]);
+
+        assert!(on_legend_entry_at(&mut app, &plot_handle, Vec2::ZERO));
+        assert!(!on_legend_entry_at(&mut app, &plot_handle, Vec2::new(20.0, 0.0)));
+    }
+
+    fn on_legend_entry_at(app: &mut App, plot_handle: &Handle<Plot>, position: Vec2) -> bool {
+        let mut state = SystemState::<Query<(&LegendItem, &LegendEntry)>>::new(&mut app.world);
+        on_legend_entry(&state.get(&app.world), plot_handle, position)
+    }
+}
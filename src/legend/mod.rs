@@ -0,0 +1,3 @@
+pub mod legend;
+#[allow(unused_imports)]
+pub use legend::*;
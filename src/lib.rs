@@ -2,11 +2,34 @@
 //! [`PlotPlugin`] to the `App`, instantiate a [`Plot`] struct, and either use the
 //! * [`Plot::plot`]`(my_data: impl `[`Plotable`]`)` method for a regular graph, the
 //! * [`Plot::plotm`]`(my_data: impl `[`Plotable`]`)` method for a scatter plot (or plot with markers), or the
-//! * [`Plot::plot_func`]`(my_function: fn(f32, f32) -> 32)` method that supports plotting of explicit functions.
+//! * [`Plot::plot_func`]`(my_function: impl Fn(f32, f32) -> f32)` method that supports plotting of explicit functions.
 //!
 //! The `my_data` argument of either of the first two methods has to implement the [`Plotable`] trait
 //! (e.g. `Vec<Vec2>`, `Vec<(f32, f32)>`, `Vec<f32>`, etc.). In the third option,
-//! `my_function` is an explicit function that takes two arguments (x and time) and returns a `f32`.
+//! `my_function` is an explicit function (or a closure) that takes two arguments (x and time) and returns a `f32`.
+//! Parametric curves (x(t), y(t)) are plotted with [`Plot::plot_parametric`], and implicit curves g(x, y) = 0,
+//! optionally together with the region where g(x, y) < 0, with [`Plot::plotopt_implicit`].
+//! Scalar fields are displayed as heatmaps with either [`Plot::plotopt_heatmap`] for a function f(x, y, t)
+//! or [`Plot::heatmap_grid`] for sampled values, see the [`Colormap`] enum.
+//! Histograms with automatic binning are plotted with [`Plot::plotopt_hist`], see the [`BinStrategy`] enum,
+//! and grouped or stacked bar charts with a categorical axis are plotted with [`Plot::plotopt_bars`].
+//! Error bars are added to data points with [`Plot::plot_errorbars`], or with the `Opt::XErr` and `Opt::YErr`
+//! options of [`Plot::plotopt`], see the [`ErrorBars`] enum.
+//! Markers can be given individual colors and sizes, for instance for bubble charts, with the `Opt::MarkerColors`,
+//! `Opt::MarkerSizes` and `Opt::MarkerValues` options of [`Plot::plotopt`].
+//! Groups given a name with `Opt::Label` are listed in a legend, see the [`Legend`] struct.
+//! A title and axis labels are set with the `title`, `x_label` and `y_label` fields of [`Plot`].
+//! A secondary x axis (top) or y axis (right) with its own range is added with [`Plot::set_secondary_x_bounds`]
+//! or [`Plot::set_secondary_y_bounds`], and groups are read on it with `Opt::SecondaryX` or `Opt::SecondaryY`.
+//! Several plots are laid out on a grid, optionally with shared axes, with a [`Figure`].
+//! Live data is appended with [`Plot::push_segment_points`] and [`Plot::push_marker_points`], which redraw
+//! only the updated groups, optionally keeping a fixed number of points with `Opt::Capacity` and scrolling
+//! the x axis with the `follow_latest` field of [`Plot`].
+//! Hovering over a marker or a point of a regular plot can show a tooltip with its coordinates, which is
+//! enabled with the `show_tooltips` field of [`Plot`]. Clicking on a data point sends a [`PlotPointClicked`] event and hovering
+//! over a curve sends [`PlotCurveHovered`] events, and [`Plot::nearest_data_element`] finds the data element
+//! closest to any position. Markers plotted with `Opt::Draggable` can be moved with the mouse, within the
+//! [`DragConstraints`], which sends a [`PlotDataEdited`] event on release.
 //!
 //! The following code can be found in examples/minimal.rs:
 //! ```
@@ -21,7 +44,7 @@
 //!          .run();
 //!  }
 //!  
-//!  fn setup(mut commands: Commands, mut plots: ResMut<Assets<Plot>>) {
+//!  fn setup(mut commands: Commands) {
 //!      commands.spawn_bundle(OrthographicCameraBundle::new_2d());
 //!  
 //!      let mut plot = Plot::default();
@@ -35,30 +58,59 @@
 //!  
 //!      plot.plot(ys);
 //!  
-//!      let plot_handle = plots.add(plot.clone());
-//!      commands.spawn().insert(plot_handle);
+//!      commands.spawn_plot(plot);
 //!  }
 //! ```
 //!
+//! [`SpawnPlotExt::spawn_plot`] adds the plot to the `Assets<Plot>` and returns the entity of its canvas, which
+//! holds the `Handle<Plot>`. Plots can also be added to the assets by hand, and spawned by spawning an entity with
+//! their handle. The font of the text of a plot is set with its `font` field, or for all the plots with the
+//! [`TickLabelFont`] resource.
+//!
 //!
 //! For customizing the look of the curves and markers, see the [`Opt`] enum for the
 //! available options together with the [`Plot::plotopt`] and
 //! [`Plot::plotopt_func`] methods. For customizing the canvas (grid, colors, etc...), see the [`Plot`] fields.
 //! Setting the range of the x and y axes is done with the [`Plot::set_bounds`]`(lo, up)` method, but bevy_plot
-//! panics if `lo.x > up.x or lo.y > up.y`.
+//! panics if `lo.x > up.x or lo.y > up.y`. The fallible variants [`Plot::try_set_bounds`],
+//! [`Plot::try_set_secondary_x_bounds`], [`Plot::try_set_secondary_y_bounds`], [`Plot::try_plot`],
+//! [`Plot::try_plotm`], [`Plot::try_plotopt`], [`Plot::try_plot_parametric`], [`Plot::try_plot_errorbars`],
+//! [`Plot::try_heatmap_grid`], [`Plot::try_plotopt_hist`] and [`Plot::try_plotopt_bars`] return a [`PlotError`] instead,
+//! and points that are NaN or infinite
+//! are skipped when drawing. Logarithmic, custom or date/time axes are set with
+//! [`Plot::set_axis_scales`], see the [`AxisScale`] enum. Polar plots are enabled with [`Plot::set_polar`].
 //!
 //! Note that the library allows the user to
-//! * zoom in and out with the mousewheel,
+//! * zoom in and out with the mousewheel, along x only while holding X or over the x tick labels, and
+//!   along y only while holding Y or over the y tick labels,
+//! * zoom on a rectangle by dragging it out with the left mouse button while holding Shift,
 //! * move the origin with the mouse by pressing and dragging,
-//! * spawn a target and the corresponding coordinates by pressing the middle mouse button, and
+//! * drag the markers of the groups plotted with `Opt::Draggable`,
+//! * spawn a target and the corresponding coordinates by pressing the middle mouse button,
+//! * hide or show a curve by clicking on its legend entry,
+//! * read the coordinates of a data point by hovering over it, and
 //! * change the Plot fields at runtime (see examples/runtime_setter.rs).
+//!
+//! Panning and zooming can be forbidden along either axis with the `lock_pan` and `lock_zoom` fields of a [`Plot`].
 
 mod plot;
 pub use plot::*;
 
+mod bars;
 mod bezier;
+pub use bezier::SpawnBezierCurveEvent;
 mod canvas;
+mod drag;
+pub use drag::PlotDataEdited;
+mod field;
+mod figure;
+pub use figure::{Figure, FigureCell, ShareAxis};
 mod inputs;
+mod legend;
+pub use legend::{Legend, LegendPosition};
 mod markers;
 mod segments;
+mod stream;
+mod tooltip;
+pub use tooltip::{DataElement, DataKind, PlotCurveHovered, PlotPointClicked};
 mod util;
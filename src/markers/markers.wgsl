@@ -195,18 +195,22 @@ var<uniform> uni: MarkerUniform;
 [[stage(fragment)]]
 fn fragment(in: FragmentInput) -> [[location(0)]] vec4<f32> {
  
-    let width = 0.041 ;
+    // the quad of each marker is scaled by its instance scale, so the antialiasing
+    // width is divided by the scale to stay constant in pixels
+    let scale = max(in.pos_scale.w, 0.0001);
+    let width = 0.041 / scale;
     let zoom = uni.zoom;
 
     var w = width * zoom  ;
     var solid = width * zoom  ;
 
 
-    var out_col = uni.color;
+    // color of the instance
+    var out_col = in.color;
 
     var uv = in.uv - float2(0.5,0.5);
 
-    var uv_in_pixels = float2(-uv.x, uv.y) * uni.quad_size - in.pos_scale.xy;
+    var uv_in_pixels = float2(-uv.x, uv.y) * uni.quad_size * scale - in.pos_scale.xy;
 
     let marker_size = uni.marker_size;
 
@@ -68,60 +68,106 @@ fn plot_points(
     plot: &mut Plot,
     plot_handle: &Handle<Plot>,
 ) {
-    let data = plot.data.clone();
-    // let color = data.marker_plot.color;
-    for marker_plot in data.marker_groups.iter() {
-        let ys = marker_plot.data.clone();
-        // let color = marker_plot.color;
-        // let ys_world = plot.plot_to_local(&ys);
-        let ys_world = ys.iter().map(|y| plot.to_local(*y)).collect::<Vec<Vec2>>();
-
-        let quad_size = 30.0;
-
-        commands
-            .spawn_bundle((
-                Mesh2dHandle(meshes.add(Mesh::from(shape::Quad {
-                    size: Vec2::splat(quad_size),
-                    flip: false,
-                }))),
-                GlobalTransform::default(),
-                Transform::from_translation(Vec3::new(0.0, 0.0, 1.12)),
-                Visibility::default(),
-                ComputedVisibility::default(),
-                MarkerInstanceMatData(
-                    ys_world
-                        .iter()
-                        .map(|v| MarkerInstanceData {
-                            //
-                            // TODO: take inner border into account
-                            //
-                            position: Vec3::new(v.x, v.y, 0.0) + plot.canvas_position.extend(0.000),
-                            scale: 1.0,
-                            color: Color::rgba(0.8, 0.6, 0.1, 1.0).as_rgba_f32(),
-                        })
-                        .collect(),
-                ),
-                // NoFrustumCulling,
-            ))
-            .insert(plot_handle.clone())
-            .insert(MarkerUniform {
-                marker_size: marker_plot.size,
-                hole_size: 1.0,
-                zoom: 1.0,
-                marker_type: marker_plot.marker_style.to_int32(),
-                marker_point_color: col_to_vec4(marker_plot.marker_point_color),
-                color: col_to_vec4(marker_plot.color),
-                quad_size,
-                inner_canvas_size_in_pixels: plot.canvas_size / (1.0 + plot.outer_border),
-                // outer_border: plot.outer_border,
-                canvas_position: plot.canvas_position,
-                contour: if marker_plot.draw_contour { 1.0 } else { 0.0 },
-            });
+    for k in 0..plot.data.marker_groups.len() {
+        spawn_marker_group(commands, meshes, plot, plot_handle, k);
     }
 }
 
+/// Spawns the markers of the `k`th marker group, unless it is hidden. The entity is spawned even if
+/// the group has no point, so that streamed points can be added to it later.
+pub(crate) fn spawn_marker_group(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    plot: &Plot,
+    plot_handle: &Handle<Plot>,
+    k: usize,
+) {
+    let marker_plot = match plot.data.marker_groups.get(k) {
+        Some(marker_plot) if !marker_plot.hidden => marker_plot,
+        _ => return,
+    };
+
+    let instances = marker_group_instances(plot, marker_plot);
+
+    let marker_entity = spawn_marker_instances(
+        commands,
+        meshes,
+        instances,
+        MarkerUniform::new(plot, marker_plot),
+        1.12,
+    );
+
+    commands
+        .entity(marker_entity)
+        .insert(plot_handle.clone())
+        .insert(PlotElement)
+        .insert(MarkerGroup(k));
+}
+
+/// Instances of the markers of a group. Points that cannot be displayed with the current axis
+/// scales are skipped.
+pub(crate) fn marker_group_instances(
+    plot: &Plot,
+    marker_plot: &MarkerData,
+) -> Vec<MarkerInstanceData> {
+    let colors = marker_plot.point_colors();
+    let secondary_map = plot.secondary_map(marker_plot.secondary);
+
+    marker_plot
+        .data
+        .iter()
+        .enumerate()
+        .map(|(k, y)| (k, secondary_map.to_primary(*y)))
+        .filter(|(_, y)| plot.is_displayable(*y))
+        .map(|(k, y)| {
+            let v = plot.to_local(y);
+            MarkerInstanceData {
+                //
+                // TODO: take inner border into account
+                //
+                position: Vec3::new(v.x, v.y, 0.0) + plot.canvas_position.extend(0.000),
+                scale: marker_plot.point_size(k),
+                color: col_to_vec4(colors[k]).to_array(),
+            }
+        })
+        .collect::<Vec<MarkerInstanceData>>()
+}
+
+/// Index of a marker group in `data.marker_groups`, used to redraw the group in place when
+/// points are streamed to it
 #[derive(Component)]
-pub(crate) struct MarkerInstanceMatData(Vec<MarkerInstanceData>);
+pub(crate) struct MarkerGroup(pub usize);
+
+// Size of the instanced square quad of one marker, in pixels
+const MARKER_QUAD_SIZE: f32 = 30.0;
+
+/// Spawns markers as instances of a single quad at the z plane `z`
+pub(crate) fn spawn_marker_instances(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    instances: Vec<MarkerInstanceData>,
+    uniform: MarkerUniform,
+    z: f32,
+) -> Entity {
+    commands
+        .spawn_bundle((
+            Mesh2dHandle(meshes.add(Mesh::from(shape::Quad {
+                size: Vec2::splat(MARKER_QUAD_SIZE),
+                flip: false,
+            }))),
+            GlobalTransform::default(),
+            Transform::from_translation(Vec3::new(0.0, 0.0, z)),
+            Visibility::default(),
+            ComputedVisibility::default(),
+            MarkerInstanceMatData(instances),
+            // NoFrustumCulling,
+        ))
+        .insert(uniform)
+        .id()
+}
+
+#[derive(Component)]
+pub(crate) struct MarkerInstanceMatData(pub Vec<MarkerInstanceData>);
 impl ExtractComponent for MarkerInstanceMatData {
     type Query = &'static MarkerInstanceMatData;
     type Filter = ();
@@ -156,15 +202,32 @@ pub(crate) struct MarkerUniform {
     pub marker_point_color: Vec4,
 }
 
-// TODO: we have instance data, but we don't use it at the moment.
-// One use case would be to have marker size as an additional dimension.
+impl MarkerUniform {
+    pub fn new(plot: &Plot, marker_plot: &MarkerData) -> Self {
+        MarkerUniform {
+            marker_size: marker_plot.size,
+            hole_size: 1.0,
+            zoom: 1.0,
+            marker_type: marker_plot.marker_style.to_int32(),
+            marker_point_color: col_to_vec4(marker_plot.marker_point_color),
+            color: col_to_vec4(marker_plot.color),
+            quad_size: MARKER_QUAD_SIZE,
+            inner_canvas_size_in_pixels: plot.canvas_size / (1.0 + plot.outer_border),
+            // outer_border: plot.outer_border,
+            canvas_position: plot.canvas_position,
+            contour: if marker_plot.draw_contour { 1.0 } else { 0.0 },
+        }
+    }
+}
 
+// Position, scale and color of each marker
 #[derive(Clone, Copy, Pod, Zeroable)]
 #[repr(C)]
-struct MarkerInstanceData {
-    position: Vec3,
-    scale: f32,
-    color: [f32; 4],
+pub(crate) struct MarkerInstanceData {
+    /// Position in world coordinates
+    pub position: Vec3,
+    pub scale: f32,
+    pub color: [f32; 4],
 }
 
 /// Custom pipeline for 2d meshes with vertex colors
@@ -0,0 +1,211 @@
+use bevy::prelude::*;
+
+use super::date_time::*;
+
+/// Scale of a single axis of a [`Plot`](crate::Plot). Data points are mapped through the scale before
+/// being laid out on the canvas, so that a `Log10` axis shows equally spaced decades, for instance.
+///
+/// The grid, the tick labels and the `tick_period` field of [`Plot`](crate::Plot) all live in the
+/// scaled space: on a `Log10` axis, a tick period of `1.0` corresponds to one decade.
+#[derive(Debug, Clone, Default)]
+pub enum AxisScale {
+    #[default]
+    Linear,
+
+    /// Base 10 logarithmic axis. Only strictly positive values are displayed.
+    Log10,
+
+    /// Symmetric logarithmic axis. Approximately linear between `-linthresh` and `linthresh`
+    /// and logarithmic outside of this range. Supports negative values.
+    SymLog { linthresh: f32 },
+
+    /// User-defined transform. `forward` must be strictly increasing and `inverse` must be its inverse.
+    Custom {
+        forward: fn(f32) -> f32,
+        inverse: fn(f32) -> f32,
+    },
+
+    /// Linear time axis. A value on the axis is the number of seconds elapsed since `origin`, a Unix
+    /// timestamp in seconds (see [`unix_timestamp`](crate::unix_timestamp)). Since the data is stored in
+    /// `f32`, `origin` should be close to the data: use [`AxisScale::time_coordinate`] to convert
+    /// timestamps. The ticks snap to round times and dates in UTC, and are labeled accordingly.
+    DateTime { origin: f64 },
+}
+
+impl AxisScale {
+    /// Maps a value in plot coordinates to the scaled space.
+    pub fn forward(&self, v: f32) -> f32 {
+        match self {
+            AxisScale::Linear => v,
+            AxisScale::Log10 => v.log10(),
+            AxisScale::SymLog { linthresh } => v.signum() * (1.0 + v.abs() / linthresh).log10(),
+            AxisScale::Custom { forward, .. } => forward(v),
+            AxisScale::DateTime { .. } => v,
+        }
+    }
+
+    /// Maps a value in the scaled space back to plot coordinates.
+    pub fn inverse(&self, s: f32) -> f32 {
+        match self {
+            AxisScale::Linear => s,
+            AxisScale::Log10 => 10_f32.powf(s),
+            AxisScale::SymLog { linthresh } => s.signum() * linthresh * (10_f32.powf(s.abs()) - 1.0),
+            AxisScale::Custom { inverse, .. } => inverse(s),
+            AxisScale::DateTime { .. } => s,
+        }
+    }
+
+    /// Returns false if the value cannot be displayed on this axis (e.g. a negative value on a `Log10` axis).
+    pub fn is_valid(&self, v: f32) -> bool {
+        self.forward(v).is_finite()
+    }
+
+    pub fn is_log(&self) -> bool {
+        matches!(self, AxisScale::Log10)
+    }
+
+    pub fn is_date_time(&self) -> bool {
+        matches!(self, AxisScale::DateTime { .. })
+    }
+
+    /// Converts a Unix timestamp in seconds to a value on this axis. Only a `DateTime` axis
+    /// shifts the timestamp by its origin.
+    pub fn time_coordinate(&self, timestamp: f64) -> f32 {
+        match self {
+            AxisScale::DateTime { origin } => (timestamp - origin) as f32,
+            _ => timestamp as f32,
+        }
+    }
+
+    /// Converts a value on this axis back to a Unix timestamp in seconds.
+    pub fn timestamp(&self, v: f32) -> f64 {
+        match self {
+            AxisScale::DateTime { origin } => origin + v as f64,
+            _ => v as f64,
+        }
+    }
+
+    /// Offset of the ticks from the origin of the axis, in the scaled space. The ticks are placed at
+    /// `offset + k * tick_period`, and only a `DateTime` axis has an offset, so that its ticks fall on
+    /// round times instead of multiples of the period since its origin.
+    pub(crate) fn tick_offset(&self, tick_period: f32) -> f32 {
+        match self {
+            AxisScale::DateTime { origin } => {
+                let offset = tick_anchor(*origin, tick_period) - origin;
+                offset.rem_euclid(tick_period as f64) as f32
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// Tick closest to `v`, in the scaled space. The ticks of a `DateTime` axis fall on round times,
+    /// or on the first day of a month for periods of a month or more.
+    pub(crate) fn nearest_tick(&self, v: f32, tick_period: f32) -> f32 {
+        match self {
+            AxisScale::DateTime { origin } => {
+                (nearest_time_tick(origin + v as f64, tick_period) - origin) as f32
+            }
+            _ => (v / tick_period).round() * tick_period,
+        }
+    }
+
+    /// Number of months between the grid lines and origin of the axis in days since the Unix epoch,
+    /// for a `DateTime` axis whose ticks are months or years, and zero otherwise. Such grid lines
+    /// are not evenly spaced, so the canvas shader places them on the calendar.
+    pub(crate) fn calendar_grid(&self, tick_period: f32) -> Vec2 {
+        match (self, calendar_months(tick_period)) {
+            (AxisScale::DateTime { origin }, Some(months)) => {
+                Vec2::new(months as f32, (origin / DAY) as f32)
+            }
+            _ => Vec2::ZERO,
+        }
+    }
+
+    /// Range used when switching to this scale while the current bounds cannot be displayed.
+    pub(crate) fn default_range(&self) -> (f32, f32) {
+        match self {
+            AxisScale::Log10 => (0.1, 1000.0),
+            AxisScale::DateTime { .. } => (0.0, DAY as f32),
+            _ => (-0.2, 1.2),
+        }
+    }
+
+    /// Computes a tick period, in the scaled space, that yields roughly 10 ticks between `lo` and `up`
+    /// (also given in the scaled space).
+    pub(crate) fn tick_period(&self, lo: f32, up: f32) -> f32 {
+        let span = up - lo;
+        let tick = nice_tick_period(span / 10.0);
+
+        match self {
+            // Decade-aware ticks: as soon as a few decades are visible, ticks are placed on
+            // whole decades only.
+            AxisScale::Log10 if span >= 3.0 => tick.max(1.0).round(),
+            AxisScale::DateTime { .. } => time_tick_period(span),
+            _ => tick,
+        }
+    }
+}
+
+/// Rounds `exact_tick` to the closest number of the form 1, 2 or 5 times a power of ten.
+pub(crate) fn nice_tick_period(exact_tick: f32) -> f32 {
+    // find order of magnitude of the tick
+    let order = exact_tick.log10().floor();
+    let mag = 10_f32.powf(order);
+
+    let candidates = [mag * 1.0, mag * 2.0, mag * 5.0];
+
+    use std::cmp::Ordering;
+    let closest = candidates.iter().min_by(|a, b| {
+        (*a - exact_tick)
+            .abs()
+            .partial_cmp(&(*b - exact_tick).abs())
+            .unwrap_or(Ordering::Equal)
+    });
+
+    *closest.unwrap()
+}
+
+/// Map between the secondary axes (top and right) and the primary axes (bottom and left) of a plot.
+/// A secondary axis has the same scale as the corresponding primary axis, and the map is affine in
+/// the scaled space. Only the axes set in `active` are mapped.
+#[derive(Debug, Clone)]
+pub(crate) struct SecondaryMap {
+    pub x_axis_scale: AxisScale,
+    pub y_axis_scale: AxisScale,
+
+    /// Bounds of the primary axes in the scaled space
+    pub primary: (Vec2, Vec2),
+
+    /// Bounds of the secondary axes in the scaled space
+    pub secondary: (Vec2, Vec2),
+
+    pub active: BVec2,
+}
+
+impl SecondaryMap {
+    fn forward(&self, v: Vec2) -> Vec2 {
+        Vec2::new(self.x_axis_scale.forward(v.x), self.y_axis_scale.forward(v.y))
+    }
+
+    fn inverse(&self, s: Vec2) -> Vec2 {
+        Vec2::new(self.x_axis_scale.inverse(s.x), self.y_axis_scale.inverse(s.y))
+    }
+
+    /// Maps a point read on the secondary axes to the primary axes.
+    pub fn to_primary(&self, v: Vec2) -> Vec2 {
+        let (lo1, up1) = self.primary;
+        let (lo2, up2) = self.secondary;
+        let mapped = self.inverse(lo1 + (self.forward(v) - lo2) / (up2 - lo2) * (up1 - lo1));
+
+        Vec2::select(self.active, mapped, v)
+    }
+
+    /// Maps a point read on the primary axes to the secondary axes.
+    pub fn to_secondary(&self, v: Vec2) -> Vec2 {
+        let (lo1, up1) = self.primary;
+        let (lo2, up2) = self.secondary;
+        let mapped = self.inverse(lo2 + (self.forward(v) - lo1) / (up1 - lo1) * (up2 - lo2));
+
+        Vec2::select(self.active, mapped, v)
+    }
+}
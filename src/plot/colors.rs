@@ -134,3 +134,69 @@ pub fn make_color_palette() -> HashMap<PlotColor, Vec<Color>> {
 
     colors
 }
+
+/// Maps scalar values to colors, for instance in a heatmap. The `Custom` variant interpolates
+/// linearly between the given colors, which are evenly spaced between the lowest and the highest value.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum Colormap {
+    #[default]
+    Viridis,
+    Magma,
+    Coolwarm,
+    Grayscale,
+    Custom(Vec<Color>),
+}
+
+impl Colormap {
+    /// Color corresponding to `t`, which is clamped between 0 and 1.
+    pub fn color(&self, t: f32) -> Color {
+        let hex_stops: &[&str] = match self {
+            Colormap::Viridis => &["440154", "3b528b", "21918c", "5ec962", "fde725"],
+            Colormap::Magma => &["000004", "3b0f70", "8c2981", "de4968", "fe9f6d", "fcfdbf"],
+            Colormap::Coolwarm => &["3b4cc0", "dddddd", "b40426"],
+            Colormap::Grayscale => &["000000", "ffffff"],
+            Colormap::Custom(stops) => return interpolate_stops(stops, t),
+        };
+
+        let stops = hex_stops
+            .iter()
+            .map(|h| Color::hex(h).unwrap())
+            .collect::<Vec<Color>>();
+
+        interpolate_stops(&stops, t)
+    }
+
+    /// Color of `value` when the ends of the colormap correspond to `lo` and `up`.
+    pub fn color_in_range(&self, value: f32, lo: f32, up: f32) -> Color {
+        let t = if up > lo { (value - lo) / (up - lo) } else { 0.5 };
+        self.color(t)
+    }
+}
+
+/// Lowest and highest finite values, or (inf, -inf) if there are none
+pub(crate) fn value_range(values: impl Iterator<Item = f32>) -> (f32, f32) {
+    values
+        .filter(|v| v.is_finite())
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, up), v| {
+            (lo.min(v), up.max(v))
+        })
+}
+
+fn interpolate_stops(stops: &[Color], t: f32) -> Color {
+    match stops.len() {
+        0 => return Color::NONE,
+        1 => return stops[0],
+        _ => {}
+    }
+
+    let t = if t.is_finite() { t.clamp(0.0, 1.0) } else { 0.0 };
+    let x = t * (stops.len() - 1) as f32;
+    let k = (x.floor() as usize).min(stops.len() - 2);
+    let f = x - k as f32;
+
+    let a = Vec4::from(stops[k].as_rgba_f32());
+    let b = Vec4::from(stops[k + 1].as_rgba_f32());
+    let c = a + (b - a) * f;
+
+    Color::rgba(c.x, c.y, c.z, c.w)
+}
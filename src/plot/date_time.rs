@@ -0,0 +1,351 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub(crate) const MINUTE: f64 = 60.0;
+pub(crate) const HOUR: f64 = 60.0 * MINUTE;
+pub(crate) const DAY: f64 = 24.0 * HOUR;
+
+// Mean lengths of a month and of a year in the Gregorian calendar
+pub(crate) const MONTH: f64 = 365.2425 * DAY / 12.0;
+pub(crate) const YEAR: f64 = 365.2425 * DAY;
+
+const MONTH_NAMES: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Number of seconds elapsed between the Unix epoch and `time`, negative for times before the epoch.
+/// See [`AxisScale::DateTime`](crate::AxisScale::DateTime).
+pub fn unix_timestamp(time: SystemTime) -> f64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(duration) => duration.as_secs_f64(),
+        Err(before_epoch) => -before_epoch.duration().as_secs_f64(),
+    }
+}
+
+// (year, month from 1 to 12, day from 1 to 31) of the given number of days since the Unix epoch,
+// from Howard Hinnant's algorithms for the proleptic Gregorian calendar
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
+
+// Number of days since the Unix epoch of the given date
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = (month as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+    era * 146097 + doe - 719468
+}
+
+// Calendar date and number of seconds since midnight of a Unix timestamp
+fn split_timestamp(timestamp: f64) -> ((i64, u32, u32), f64) {
+    let days = (timestamp / DAY).floor();
+    (civil_from_days(days as i64), timestamp - days * DAY)
+}
+
+/// Calendar-aware tick period in seconds, yielding roughly 8 ticks over `span` seconds. Below one
+/// second, the periods are 1, 2 or 5 times a power of ten. Periods of months and years are given
+/// with the mean length of a month, but their ticks fall on the first day of a month, see
+/// [`calendar_months`].
+pub(crate) fn time_tick_period(span: f32) -> f32 {
+    let exact = span as f64 / 8.0;
+
+    let candidates = [
+        1.0,
+        2.0,
+        5.0,
+        10.0,
+        15.0,
+        30.0,
+        MINUTE,
+        2.0 * MINUTE,
+        5.0 * MINUTE,
+        10.0 * MINUTE,
+        15.0 * MINUTE,
+        30.0 * MINUTE,
+        HOUR,
+        2.0 * HOUR,
+        3.0 * HOUR,
+        6.0 * HOUR,
+        12.0 * HOUR,
+        DAY,
+        2.0 * DAY,
+        7.0 * DAY,
+        14.0 * DAY,
+        MONTH,
+        2.0 * MONTH,
+        3.0 * MONTH,
+        6.0 * MONTH,
+        YEAR,
+    ];
+
+    if exact < 1.0 {
+        return super::axis_scale::nice_tick_period(exact as f32);
+    }
+
+    if exact > YEAR {
+        return (super::axis_scale::nice_tick_period((exact / YEAR) as f32) as f64 * YEAR) as f32;
+    }
+
+    // the ratio between consecutive candidates is at most 2.5, so the closest candidate in
+    // logarithmic space changes smoothly with the span
+    let closest = candidates
+        .iter()
+        .min_by(|a, b| {
+            let da = (*a / exact).ln().abs();
+            let db = (*b / exact).ln().abs();
+            da.partial_cmp(&db).unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .unwrap();
+
+    *closest as f32
+}
+
+/// Number of months between two ticks if the tick period is a month or more, in which case the
+/// ticks fall on the first day of the months whose index since January of year 0 is a multiple of
+/// that number: every quarter starts in January, April, July and October, and every 5 years in
+/// years that are multiples of 5.
+pub(crate) fn calendar_months(period: f32) -> Option<i64> {
+    let period = period as f64;
+
+    if period < MONTH * 0.9 {
+        return None;
+    }
+
+    Some(((period / MONTH).round() as i64).max(1))
+}
+
+// Index of the month of a Unix timestamp, counted from January of year 0
+fn month_index(timestamp: f64) -> i64 {
+    let ((year, month, _), _) = split_timestamp(timestamp);
+    year * 12 + month as i64 - 1
+}
+
+// Unix timestamp of the first day of the month with the given index, see `month_index`
+fn month_start(index: i64) -> f64 {
+    days_from_civil(index.div_euclid(12), index.rem_euclid(12) as u32 + 1, 1) as f64 * DAY
+}
+
+/// Unix timestamp of a tick with the given period, at or before `timestamp`. Ticks shorter than a
+/// month fall on multiples of the period since the epoch, which are round times of the day, and
+/// longer ticks on the first day of a month, see [`calendar_months`].
+pub(crate) fn tick_anchor(timestamp: f64, period: f32) -> f64 {
+    match calendar_months(period) {
+        Some(months) => month_start(month_index(timestamp).div_euclid(months) * months),
+        None => {
+            let period = period as f64;
+            (timestamp / period).floor() * period
+        }
+    }
+}
+
+/// Ticks with the given period between the Unix timestamps `lo` and `up`, as pairs of the index
+/// of the tick and its timestamp. Consecutive ticks have consecutive indices.
+pub(crate) fn time_ticks(lo: f64, up: f64, period: f32) -> Vec<(i64, f64)> {
+    if !lo.is_finite() || !up.is_finite() || period <= 0.0 {
+        return Vec::new();
+    }
+
+    match calendar_months(period) {
+        Some(months) => {
+            let first = month_index(lo).div_euclid(months);
+            let last = month_index(up).div_euclid(months);
+
+            (first..=last)
+                .map(|k| (k, month_start(k * months)))
+                .filter(|(_, timestamp)| *timestamp >= lo && *timestamp <= up)
+                .collect()
+        }
+        None => {
+            let period = period as f64;
+            let first = (lo / period).ceil() as i64;
+            let last = (up / period).floor() as i64;
+
+            (first..=last).map(|k| (k, k as f64 * period)).collect()
+        }
+    }
+}
+
+/// Unix timestamp of the tick with the given period closest to `timestamp`
+pub(crate) fn nearest_time_tick(timestamp: f64, period: f32) -> f64 {
+    let before = tick_anchor(timestamp, period);
+    let after = match calendar_months(period) {
+        Some(months) => month_start(month_index(before) + months),
+        None => before + period as f64,
+    };
+
+    if timestamp - before <= after - timestamp {
+        before
+    } else {
+        after
+    }
+}
+
+/// Tick label of the tick at `timestamp`, with a precision depending on the tick period.
+/// Ticks of a month or more are labeled with their month or year. They fall on the first day of
+/// the month, up to the rounding of the coordinates of the axis, which is absorbed by looking at
+/// the date half a day later.
+pub(crate) fn format_time_tick(timestamp: f64, period: f32) -> String {
+    match calendar_months(period) {
+        Some(months) if months >= 12 => {
+            let ((year, _, _), _) = split_timestamp(timestamp + DAY / 2.0);
+            return format!("{}", year);
+        }
+        Some(_) => {
+            let ((year, month, _), _) = split_timestamp(timestamp + DAY / 2.0);
+            return format!("{} {}", MONTH_NAMES[month as usize - 1], year);
+        }
+        None => {}
+    }
+
+    let period = period as f64;
+
+    let ((_, month, day), seconds) = split_timestamp(timestamp);
+
+    // ticks at midnight show the date, unless the ticks are less than a minute apart
+    let at_midnight = seconds.min(DAY - seconds) < 0.5;
+    if period >= DAY || (at_midnight && period >= MINUTE) {
+        return format!("{} {:02}", MONTH_NAMES[month as usize - 1], day);
+    }
+
+    let hours = (seconds / HOUR).floor();
+    let minutes = ((seconds - hours * HOUR) / MINUTE).floor();
+    let secs = seconds - hours * HOUR - minutes * MINUTE;
+
+    if period >= MINUTE {
+        format!("{:02}:{:02}", hours, minutes)
+    } else if period >= 1.0 {
+        format!("{:02}:{:02}:{:02}", hours, minutes, secs.round())
+    } else {
+        let decimals = (-period.log10()).ceil() as usize;
+        format!("{:02}:{:02}:{:0width$.decimals$}", hours, minutes, secs, width = decimals + 3)
+    }
+}
+
+/// Full date and time of a Unix timestamp, in UTC
+pub(crate) fn format_date_time(timestamp: f64) -> String {
+    let ((year, month, day), seconds) = split_timestamp(timestamp.round());
+
+    let hours = (seconds / HOUR).floor();
+    let minutes = ((seconds - hours * HOUR) / MINUTE).floor();
+    let secs = seconds - hours * HOUR - minutes * MINUTE;
+
+    format!(
+        "{}-{:02}-{:02} {:02}:{:02}:{:02}",
+        year, month, day, hours, minutes, secs
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timestamp(year: i64, month: u32, day: u32, seconds: f64) -> f64 {
+        days_from_civil(year, month, day) as f64 * DAY + seconds
+    }
+
+    #[test]
+    fn civil_dates_round_trip() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+        assert_eq!(civil_from_days(19782), (2024, 2, 29));
+        assert_eq!(days_from_civil(2000, 3, 1), 11017);
+        assert_eq!(days_from_civil(1600, 1, 1), -135140);
+
+        for days in (-1_000_000..1_000_000).step_by(7) {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days_from_civil(year, month, day), days);
+        }
+    }
+
+    #[test]
+    fn month_ticks_follow_the_calendar() {
+        let may_17 = timestamp(2023, 5, 17, 12.0 * HOUR);
+
+        assert_eq!(tick_anchor(may_17, MONTH as f32), timestamp(2023, 5, 1, 0.0));
+        assert_eq!(tick_anchor(may_17, (3.0 * MONTH) as f32), timestamp(2023, 4, 1, 0.0));
+        assert_eq!(tick_anchor(may_17, YEAR as f32), timestamp(2023, 1, 1, 0.0));
+        assert_eq!(tick_anchor(may_17, (5.0 * YEAR) as f32), timestamp(2020, 1, 1, 0.0));
+        assert_eq!(tick_anchor(may_17, HOUR as f32), timestamp(2023, 5, 17, 12.0 * HOUR));
+        assert_eq!(
+            tick_anchor(may_17 + 2.5 * HOUR, (3.0 * HOUR) as f32),
+            timestamp(2023, 5, 17, 12.0 * HOUR)
+        );
+
+        let ticks = time_ticks(
+            timestamp(2023, 1, 15, 0.0),
+            timestamp(2023, 6, 1, 0.0),
+            MONTH as f32,
+        );
+        let expected = (2..=6)
+            .map(|month| timestamp(2023, month, 1, 0.0))
+            .collect::<Vec<f64>>();
+
+        assert_eq!(ticks.iter().map(|(_, t)| *t).collect::<Vec<f64>>(), expected);
+        assert!(ticks.windows(2).all(|w| w[1].0 == w[0].0 + 1));
+
+        let ticks = time_ticks(
+            timestamp(2001, 6, 1, 0.0),
+            timestamp(2031, 6, 1, 0.0),
+            (10.0 * YEAR) as f32,
+        );
+        let years = ticks
+            .iter()
+            .map(|(_, t)| civil_from_days((t / DAY) as i64).0)
+            .collect::<Vec<i64>>();
+        assert_eq!(years, vec![2010, 2020, 2030]);
+    }
+
+    #[test]
+    fn nearest_tick_rounds_to_the_closest_month() {
+        let period = MONTH as f32;
+
+        assert_eq!(
+            nearest_time_tick(timestamp(2023, 2, 10, 0.0), period),
+            timestamp(2023, 2, 1, 0.0)
+        );
+        assert_eq!(
+            nearest_time_tick(timestamp(2023, 2, 20, 0.0), period),
+            timestamp(2023, 3, 1, 0.0)
+        );
+        assert_eq!(
+            nearest_time_tick(timestamp(2023, 2, 20, 40.0), MINUTE as f32),
+            timestamp(2023, 2, 20, MINUTE)
+        );
+    }
+
+    #[test]
+    fn tick_labels_depend_on_the_period() {
+        // the coordinates of the axis are rounded, so a tick may land slightly before midnight
+        let march = timestamp(2023, 3, 1, -8.0);
+
+        assert_eq!(format_time_tick(march, MONTH as f32), "Mar 2023");
+        assert_eq!(format_time_tick(timestamp(2025, 1, 1, 0.0), YEAR as f32), "2025");
+        assert_eq!(format_time_tick(timestamp(2023, 3, 5, 0.0), DAY as f32), "Mar 05");
+        assert_eq!(format_time_tick(timestamp(2023, 3, 5, 0.0), HOUR as f32), "Mar 05");
+        assert_eq!(
+            format_time_tick(timestamp(2023, 3, 5, 10.0 * HOUR + 30.0 * MINUTE), HOUR as f32),
+            "10:30"
+        );
+        assert_eq!(
+            format_time_tick(timestamp(2023, 3, 5, 10.0 * HOUR + 15.0), 5.0),
+            "10:00:15"
+        );
+        assert_eq!(
+            format_time_tick(timestamp(2023, 3, 5, 10.0 * HOUR + 15.25), 0.05),
+            "10:00:15.25"
+        );
+    }
+}
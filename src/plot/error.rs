@@ -0,0 +1,61 @@
+use bevy::prelude::Vec2;
+
+use std::fmt;
+
+/// Error returned by the fallible methods of [`Plot`](crate::Plot), such as
+/// [`Plot::try_plot`](crate::Plot::try_plot) and [`Plot::try_set_bounds`](crate::Plot::try_set_bounds).
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlotError {
+    /// The data contains no point
+    EmptyData,
+
+    /// None of the points can be displayed: they are NaN, infinite or outside of the domain of the
+    /// axis scales (e.g. negative values on a logarithmic axis)
+    NoDisplayablePoint,
+
+    /// A bound is NaN, infinite or outside of the domain of the axis scale
+    InvalidBounds { lo: Vec2, up: Vec2 },
+
+    /// The lower bound of an axis is not strictly less than its upper bound
+    InvertedBounds { lo: Vec2, up: Vec2 },
+
+    /// A parametric curve is sampled less than twice, or over a range of the parameter that is
+    /// empty or not finite
+    InvalidSampling { t_range: (f32, f32), num_points: usize },
+
+    /// The number of values does not match the data they belong to, such as the values of a heatmap
+    /// grid or the error bars of the data points
+    LengthMismatch { expected: usize, found: usize },
+}
+
+impl fmt::Display for PlotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlotError::EmptyData => write!(f, "the data contains no point"),
+            PlotError::NoDisplayablePoint => write!(
+                f,
+                "none of the points can be displayed with the current axis scales"
+            ),
+            PlotError::InvalidBounds { lo, up } => write!(
+                f,
+                "the bounds {} and {} must be finite and valid for the axis scales (e.g. strictly positive for a logarithmic axis)",
+                lo, up
+            ),
+            PlotError::InvertedBounds { lo, up } => write!(
+                f,
+                "the lower bound {} must be strictly less than the upper bound {} on both axes",
+                lo, up
+            ),
+            PlotError::InvalidSampling { t_range, num_points } => write!(
+                f,
+                "a parametric curve must be sampled at least twice over a finite and non-empty range, got {} points between {} and {}",
+                num_points, t_range.0, t_range.1
+            ),
+            PlotError::LengthMismatch { expected, found } => {
+                write!(f, "expected {} values, found {}", expected, found)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PlotError {}
@@ -0,0 +1,227 @@
+/// Strategy used by [`Plot::plotopt_hist`](crate::Plot::plotopt_hist) to split the range of the values into bins
+/// of equal width.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum BinStrategy {
+    /// Fixed number of bins between the lowest and the highest value
+    Count(usize),
+
+    /// Fixed bin width, the first bin starting at the lowest value
+    Width(f32),
+
+    /// ceil(log2(n)) + 1 bins, where n is the number of values
+    #[default]
+    Sturges,
+
+    /// Bin width of 2 IQR / n^(1/3), where IQR is the interquartile range. Less sensitive
+    /// to outliers than the other strategies.
+    FreedmanDiaconis,
+}
+
+// Prevents a tiny bin width from allocating millions of bins
+const MAX_NUM_BINS: usize = 10_000;
+
+impl BinStrategy {
+    /// Edges of the bins for the given values, which must be finite and sorted in increasing order.
+    /// There is one more edge than there are bins.
+    pub fn bin_edges(&self, sorted: &[f32]) -> Vec<f32> {
+        if sorted.is_empty() {
+            return vec![];
+        }
+
+        let lo = sorted[0];
+        let up = sorted[sorted.len() - 1];
+
+        // a single bin centered on the value
+        if up <= lo {
+            return vec![lo - 0.5, lo + 0.5];
+        }
+
+        let sturges = || (sorted.len() as f32).log2().ceil() as usize + 1;
+
+        let num_bins = match self {
+            BinStrategy::Count(n) => (*n).max(1),
+
+            BinStrategy::Width(w) if *w > 0.0 => ((up - lo) / w).ceil() as usize,
+
+            BinStrategy::Width(_) => sturges(),
+
+            BinStrategy::Sturges => sturges(),
+
+            BinStrategy::FreedmanDiaconis => {
+                let iqr = quantile(sorted, 0.75) - quantile(sorted, 0.25);
+                let width = 2.0 * iqr / (sorted.len() as f32).cbrt();
+
+                if width > 0.0 {
+                    ((up - lo) / width).ceil() as usize
+                } else {
+                    sturges()
+                }
+            }
+        }
+        .clamp(1, MAX_NUM_BINS);
+
+        let width = match self {
+            BinStrategy::Width(w) if *w > 0.0 && num_bins < MAX_NUM_BINS => *w,
+            _ => (up - lo) / num_bins as f32,
+        };
+
+        (0..=num_bins).map(|k| lo + k as f32 * width).collect()
+    }
+}
+
+// Quantile of sorted values with linear interpolation between the closest ranks
+fn quantile(sorted: &[f32], q: f32) -> f32 {
+    let position = q * (sorted.len() - 1) as f32;
+    let k = position.floor() as usize;
+    let f = position - k as f32;
+
+    match sorted.get(k + 1) {
+        Some(next) => sorted[k] + (next - sorted[k]) * f,
+        None => sorted[k],
+    }
+}
+
+/// Bins of a histogram and the height of the corresponding bars
+pub(crate) struct Histogram {
+    pub edges: Vec<f32>,
+    pub heights: Vec<f32>,
+}
+
+/// Counts the values falling in each bin. Non-finite values are ignored.
+///
+/// With `density`, the heights are normalized such that the total area of the histogram is 1.
+/// With `cumulative`, each bar contains the values of all the previous bins, in which case `density`
+/// yields the empirical cumulative distribution function.
+pub(crate) fn histogram(
+    values: &[f32],
+    strategy: &BinStrategy,
+    density: bool,
+    cumulative: bool,
+) -> Histogram {
+    let mut sorted = values
+        .iter()
+        .copied()
+        .filter(|v| v.is_finite())
+        .collect::<Vec<f32>>();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let edges = strategy.bin_edges(&sorted);
+    if edges.len() < 2 {
+        return Histogram {
+            edges,
+            heights: vec![],
+        };
+    }
+
+    let num_bins = edges.len() - 1;
+    let lo = edges[0];
+    let width = edges[1] - edges[0];
+
+    let mut counts = vec![0.0; num_bins];
+    for v in sorted.iter() {
+        // the highest value belongs to the last bin
+        let k = (((v - lo) / width).floor() as usize).min(num_bins - 1);
+        counts[k] += 1.0;
+    }
+
+    let n = sorted.len() as f32;
+
+    let heights = if cumulative {
+        let mut total = 0.0;
+        counts
+            .iter()
+            .map(|c| {
+                total += c;
+                if density {
+                    total / n
+                } else {
+                    total
+                }
+            })
+            .collect()
+    } else if density {
+        counts.iter().map(|c| c / (n * width)).collect()
+    } else {
+        counts
+    };
+
+    Histogram { edges, heights }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(n: usize) -> Vec<f32> {
+        (0..n).map(|k| k as f32).collect()
+    }
+
+    fn num_bins(strategy: BinStrategy, sorted: &[f32]) -> usize {
+        strategy.bin_edges(sorted).len() - 1
+    }
+
+    #[test]
+    fn bin_counts_follow_the_strategy() {
+        // ceil(log2(16)) + 1
+        assert_eq!(num_bins(BinStrategy::Sturges, &range(16)), 5);
+        assert_eq!(num_bins(BinStrategy::Count(7), &range(16)), 7);
+        assert_eq!(num_bins(BinStrategy::Count(0), &range(16)), 1);
+
+        // IQR = 49.5, width = 99 / 100^(1/3), hence ceil(100^(1/3)) bins
+        assert_eq!(num_bins(BinStrategy::FreedmanDiaconis, &range(100)), 5);
+
+        // a zero IQR falls back to Sturges
+        let outlier = vec![0.0, 0.0, 0.0, 0.0, 10.0];
+        assert_eq!(num_bins(BinStrategy::FreedmanDiaconis, &outlier), 4);
+
+        assert_eq!(BinStrategy::Sturges.bin_edges(&[2.0, 2.0]), vec![1.5, 2.5]);
+        assert!(BinStrategy::Sturges.bin_edges(&[]).is_empty());
+    }
+
+    #[test]
+    fn fixed_width_ends_with_a_partial_bin() {
+        let edges = BinStrategy::Width(0.4).bin_edges(&[0.0, 1.0]);
+
+        assert_eq!(edges.len(), 4);
+        assert!((edges[3] - 1.2).abs() < 1e-6);
+
+        // the top value still lands in the last bin
+        let hist = histogram(&[0.0, 1.0], &BinStrategy::Width(0.4), false, false);
+        assert_eq!(hist.heights, vec![1.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn tiny_bins_are_clamped() {
+        let edges = BinStrategy::Width(1e-6).bin_edges(&[0.0, 1.0]);
+
+        assert_eq!(edges.len(), MAX_NUM_BINS + 1);
+        assert!((edges[MAX_NUM_BINS] - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn top_value_lands_in_the_last_bin() {
+        let hist = histogram(&range(5), &BinStrategy::Count(4), false, false);
+        assert_eq!(hist.heights, vec![1.0, 1.0, 1.0, 2.0]);
+
+        // non-finite values are ignored
+        let hist = histogram(&[f32::NAN, 0.0, 4.0, f32::INFINITY], &BinStrategy::Count(2), false, false);
+        assert_eq!(hist.heights, vec![1.0, 1.0]);
+    }
+
+    #[test]
+    fn density_integrates_to_one() {
+        let values = vec![0.1, 0.5, 0.7, 1.2, 1.3, 2.9, 3.4, 4.0];
+
+        let hist = histogram(&values, &BinStrategy::Count(5), true, false);
+        let width = hist.edges[1] - hist.edges[0];
+        let area = hist.heights.iter().map(|h| h * width).sum::<f32>();
+        assert!((area - 1.0).abs() < 1e-5);
+
+        let cumulative = histogram(&values, &BinStrategy::Count(5), true, true);
+        assert!((cumulative.heights.last().unwrap() - 1.0).abs() < 1e-6);
+        assert!(cumulative.heights.windows(2).all(|h| h[0] <= h[1]));
+
+        let counts = histogram(&values, &BinStrategy::Count(5), false, true);
+        assert_eq!(*counts.heights.last().unwrap(), values.len() as f32);
+    }
+}
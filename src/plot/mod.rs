@@ -1,7 +1,15 @@
+mod axis_scale;
 mod colors;
+mod date_time;
+mod error;
+mod histogram;
 pub mod plot;
 pub mod plot_format;
 
+pub use axis_scale::*;
 pub use colors::*;
+pub use date_time::*;
+pub use error::*;
+pub use histogram::*;
 pub use plot::*;
 pub use plot_format::*;
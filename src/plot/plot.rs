@@ -1,18 +1,32 @@
 use bevy::{
-    prelude::*, reflect::TypeUuid, render::render_resource::std140::AsStd140,
-    sprite::Material2dPlugin,
+    ecs::system::Command, prelude::*, reflect::TypeUuid,
+    render::render_resource::std140::AsStd140, sprite::Material2dPlugin,
 };
 
+use std::sync::Arc;
+
+use itertools_num::linspace;
+
 use super::plot_format::*;
-use super::colors::make_color_palette;
+use super::colors::{make_color_palette, value_range, Colormap};
+use super::axis_scale::*;
+use super::histogram::*;
+use super::error::*;
 
+use crate::bars::*;
 use crate::canvas::*;
 use crate::bezier::*;
+use crate::drag::*;
 
+use crate::field::*;
+use crate::figure::*;
 use crate::inputs::*;
+use crate::legend::*;
 use crate::markers::*;
 use crate::util::*;
 use crate::segments::*;
+use crate::stream::*;
+use crate::tooltip::*;
 
 /// Main plugin for bevy_plot
 pub struct PlotPlugin;
@@ -20,11 +34,15 @@ pub struct PlotPlugin;
 // z planes from bottom to top:
 //
 // canvas: 0.0001
+// heatmaps: 0.4
+// implicit curves: 0.5
+// bars: 0.6
 // text and labels: 1.0001
 // bezier 1.10
 // segments: 1.11
 // markers: 1.12
 // target text: 1.2
+// legend: 1.3 to 1.33
 
 // TODO:
 // 1) Area under the curve
@@ -42,6 +60,10 @@ impl Plugin for PlotPlugin {
             .add_plugin(BezierMesh2dPlugin)
             .add_plugin(SegmentMesh2dPlugin)
             .add_plugin(CanvasMesh2dPlugin)
+            .add_plugin(Material2dPlugin::<FieldMaterial>::default())
+            .add_plugin(FieldMesh2dPlugin)
+            .add_plugin(Material2dPlugin::<BarMaterial>::default())
+            .add_plugin(BarMesh2dPlugin)
             .add_event::<SpawnGraphEvent>()
             .add_event::<ReleaseAllEvent>()
             .add_event::<UpdatePlotLabelsEvent>()
@@ -50,6 +72,9 @@ impl Plugin for PlotPlugin {
             .add_event::<UpdateTargetLabelEvent>()
             .add_event::<UpdateBezierShaderEvent>()
             .add_event::<SpawnBezierCurveEvent>()
+            .add_event::<PlotPointClicked>()
+            .add_event::<PlotCurveHovered>()
+            .add_event::<PlotDataEdited>()
             .add_asset::<Plot>()
             .insert_resource(make_color_palette())
             .insert_resource(Cursor::default())
@@ -59,6 +84,8 @@ impl Plugin for PlotPlugin {
                 SystemSet::new().label("model").before("shader_updates")             
                 .with_system(adjust_graph_axes)
                 .with_system(change_plot)
+                .with_system(layout_figures)
+                .with_system(invalidate_point_index)
             )
 
             .add_system_set(
@@ -66,6 +93,7 @@ impl Plugin for PlotPlugin {
                 .with_system(update_bezier_uniform)
                 .with_system(spawn_bezier_function)
                 .with_system(wait_for_graph_spawn)
+                .with_system(sync_shared_axes)
             )
        
             .add_system_set(
@@ -76,13 +104,24 @@ impl Plugin for PlotPlugin {
                 .with_system(record_mouse_events_system)
                 .with_system(update_mouse_target)
                 .with_system(update_plot_labels)
+                .with_system(update_axis_labels)
                 .with_system(update_target)
                 .with_system(do_spawn_plot)
                 .with_system(animate_bezier)
+                .with_system(animate_fields)
+                .with_system(toggle_legend_entries)
+                .with_system(update_streamed_groups)
+                .with_system(update_tooltips)
+                .with_system(send_picking_events)
+                .with_system(drag_data_points)
+                .with_system(box_zoom)
             )
             .add_system_set(
                 SystemSet::new().label("setups").after("other")
                 .with_system(segments_setup).label("seg")
+                .with_system(fields_setup)
+                .with_system(bars_setup)
+                .with_system(legend_setup)
                 // .with_system(markers_setup)
             )
             // why the markers setup needs to be after the segments setup is a
@@ -96,14 +135,39 @@ impl Plugin for PlotPlugin {
 }
 
 
+// The entity spawned by the user to hold the handle of a plot, as opposed to the canvas and the
+// entities drawing the data, which also hold it
+type DummyPlotFilter = (Without<PendingCanvas>, Without<Canvas>, Without<PlotElement>);
+
 fn do_spawn_plot(
     mut commands: Commands,
     mut plots: ResMut<Assets<Plot>>, 
-    query: Query<(Entity, &Handle<Plot>)>,
+    pending_query: Query<(Entity, &Handle<Plot>), With<PendingCanvas>>,
+    dummy_query: Query<(Entity, &Handle<Plot>), DummyPlotFilter>,
     mut spawn_plot_event: EventWriter<SpawnGraphEvent>
 ) {
-    for (entity, plot_handle) in query.iter() {
-        let plot = plots.get_mut(plot_handle).unwrap();
+    // The canvas is spawned on the entity reserved for it, see `SpawnPlotExt::spawn_plot`
+    for (entity, plot_handle) in pending_query.iter() {
+        if let Some(plot) = plots.get_mut(plot_handle) {
+            if plot.do_spawn_plot {
+                spawn_plot_event.send(SpawnGraphEvent {
+                    canvas: plot.make_canvas(),
+                    plot_handle: plot_handle.clone(),
+                    canvas_entity: Some(entity),
+                });
+
+                plot.do_spawn_plot = false;
+                commands.entity(entity).remove::<PendingCanvas>();
+            }
+        }
+    }
+
+    for (entity, plot_handle) in dummy_query.iter() {
+        let plot = match plots.get_mut(plot_handle) {
+            Some(plot) => plot,
+            None => continue,
+        };
+
         if plot.do_spawn_plot {
 
             let canvas = plot.make_canvas();
@@ -111,6 +175,7 @@ fn do_spawn_plot(
             spawn_plot_event.send(SpawnGraphEvent {
                 canvas,
                 plot_handle: plot_handle.clone(),
+                canvas_entity: None,
             });
 
             plot.do_spawn_plot = false;
@@ -122,8 +187,50 @@ fn do_spawn_plot(
     }
 }
 
+/// Entity on which the canvas of a plot is spawned, instead of a new entity
+#[derive(Component)]
+pub(crate) struct PendingCanvas;
+
+/// Entity spawned by bevy_plot to draw the data of a plot, next to its `Handle<Plot>`
+#[derive(Component)]
+pub(crate) struct PlotElement;
+
+/// Extension trait of [`Commands`] for spawning a [`Plot`] in a single call.
+pub trait SpawnPlotExt {
+    /// Adds `plot` to the `Assets<Plot>` and spawns its canvas, replacing the two steps of adding the
+    /// plot to the assets and spawning an entity holding its handle. Returns the entity of the canvas,
+    /// which holds the `Handle<Plot>` of the plot and is the parent of its labels.
+    fn spawn_plot(&mut self, plot: Plot) -> Entity;
+}
+
+impl SpawnPlotExt for Commands<'_, '_> {
+    fn spawn_plot(&mut self, plot: Plot) -> Entity {
+        let canvas_entity = self.spawn().insert(PendingCanvas).id();
+        self.add(SpawnPlot { canvas_entity, plot });
+        canvas_entity
+    }
+}
+
+struct SpawnPlot {
+    canvas_entity: Entity,
+    plot: Plot,
+}
+
+impl Command for SpawnPlot {
+    fn write(mut self, world: &mut World) {
+        self.plot.do_spawn_plot = true;
+
+        let plot_handle = world
+            .get_resource_mut::<Assets<Plot>>()
+            .expect("the PlotPlugin must be added before spawning plots")
+            .add(self.plot);
+
+        world.entity_mut(self.canvas_entity).insert(plot_handle);
+    }
+}
+
 /// Handle to the type of font to use for tick labels. If None is given (default), 
-/// the tick labels are not rendered.
+/// the tick labels are not rendered. Overridden by the `font` field of each [`Plot`].
 pub struct TickLabelFont {
     pub maybe_font: Option<Handle<Font>>,
 }
@@ -162,18 +269,27 @@ pub(crate) struct PlotCanvasBounds {
     pub lo: Vec2,
 }
 
-#[derive(Debug, Clone)]
+/// Explicit function of `x` and time displayed by a [`BezierData`] group. Since it is a closure,
+/// it can capture runtime state such as coefficients read from a config file.
+pub type PlotFunction = Arc<dyn Fn(f32, f32) -> f32 + Send + Sync>;
+
+#[derive(Clone)]
 /// Struct containing the data to be plotted and metaparameters of any explicit function plot. 
 /// It can be found in  the `data.bezier_groups` sub-field of a [`Plot`]. The reason for its name is
 /// that bevy_plot interpolates between samples of the function using quadratic bezier curves.
 pub struct BezierData {
-    /// Function to be displayed
-    pub function: fn(f32, f32) -> f32,
+    /// Function to be displayed. If the function captures a parameter that changes at runtime,
+    /// either set `show_animation` to true or send a [`SpawnBezierCurveEvent`] to redraw the curve.
+    pub function: PlotFunction,
     /// Thickness of the segments
     pub size: f32,
-    /// Not implemented yet
+    /// Solid, dashed, dotted, ... If set to `LineStyle::None`, the curve is not drawn
     pub line_style: LineStyle,
 
+    /// Length of the dashes in pixels for the dashed line styles. The gaps and the spacing
+    /// of the dots scale with it.
+    pub dash_length: f32,
+
     /// Color of the curve
     pub color: Color,
 
@@ -186,22 +302,244 @@ pub struct BezierData {
 
     /// If true, bevy_plot recomputes the `function` field every frame
     pub show_animation: bool,
+
+    /// If not None, the curve is a parametric curve and the `function` field is ignored.
+    /// See [`Plot::plot_parametric`].
+    pub parametric: Option<ParametricData>,
+
+    /// Name of the curve in the legend. Curves without a label are not listed.
+    pub label: Option<String>,
+
+    /// If true, the curve is not drawn. Toggled by clicking on its legend entry.
+    pub hidden: bool,
+
+    /// Whether the curve is read on the secondary x axis (top) and y axis (right), see `Opt::SecondaryX`
+    pub secondary: BVec2,
+}
+
+/// Parametric function (x(t), y(t)) of the parameter `t` and of time displayed by a [`BezierData`] group.
+pub type ParametricFunction = Arc<dyn Fn(f32, f32) -> Vec2 + Send + Sync>;
+
+/// Parametric curve sampled `num_points` times (see [`BezierData`]) between `t_range.0` and `t_range.1`.
+#[derive(Clone)]
+pub struct ParametricData {
+    pub function: ParametricFunction,
+    pub t_range: (f32, f32),
+}
+
+impl std::fmt::Debug for BezierData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BezierData")
+            .field("size", &self.size)
+            .field("line_style", &self.line_style)
+            .field("dash_length", &self.dash_length)
+            .field("color", &self.color)
+            .field("mech", &self.mech)
+            .field("num_points", &self.num_points)
+            .field("show_animation", &self.show_animation)
+            .field("t_range", &self.parametric.as_ref().map(|p| p.t_range))
+            .field("label", &self.label)
+            .field("hidden", &self.hidden)
+            .field("secondary", &self.secondary)
+            .finish_non_exhaustive()
+    }
 }
 
 impl Default for BezierData {
     fn default() -> Self {
         BezierData {
-            function: |x: f32, _t: f32| x, 
+            function: Arc::new(|x: f32, _t: f32| x),
             color: Color::rgb(0.2, 0.3, 0.8),
             size: 1.0,
             line_style: LineStyle::Solid,
+            dash_length: 10.0,
             mech: false,
             num_points: 256,
             show_animation: false,
+            parametric: None,
+            label: None,
+            hidden: false,
+            secondary: BVec2::new(false, false),
+        }
+    }
+}
+
+
+/// Implicit function g(x, y, time) displayed by an [`ImplicitData`] group.
+pub type ImplicitFunction = Arc<dyn Fn(f32, f32, f32) -> f32 + Send + Sync>;
+
+#[derive(Clone)]
+/// Struct containing the implicit function and metaparameters of an implicit curve g(x, y) = 0.
+/// It can be found in the `data.implicit_groups` sub-field of a [`Plot`]. The function is sampled
+/// over the whole canvas, so the curve can have several disjoint parts.
+pub struct ImplicitData {
+    /// Function whose zero level set is displayed. The arguments are x, y and time.
+    pub function: ImplicitFunction,
+
+    /// Color of the curve
+    pub color: Color,
+
+    /// Thickness of the curve
+    pub size: f32,
+
+    /// If set to `LineStyle::None`, only the region where g(x, y) < 0 is shown. The dashed
+    /// styles are drawn as solid curves.
+    pub line_style: LineStyle,
+
+    /// If not None, the region where g(x, y) < 0 is filled with this color. Use the alpha
+    /// channel to keep the grid visible.
+    pub fill_color: Option<Color>,
+
+    /// If true, bevy_plot recomputes the `function` field every frame
+    pub show_animation: bool,
+}
+
+impl std::fmt::Debug for ImplicitData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ImplicitData")
+            .field("color", &self.color)
+            .field("size", &self.size)
+            .field("line_style", &self.line_style)
+            .field("fill_color", &self.fill_color)
+            .field("show_animation", &self.show_animation)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for ImplicitData {
+    fn default() -> Self {
+        ImplicitData {
+            function: Arc::new(|x: f32, y: f32, _t: f32| x * x + y * y - 1.0),
+            color: Color::rgb(0.9, 0.5, 0.2),
+            size: 1.0,
+            line_style: LineStyle::Solid,
+            fill_color: None,
+            show_animation: false,
+        }
+    }
+}
+
+/// Scalar field f(x, y, time) displayed by a [`HeatmapData`] group.
+pub type HeatmapFunction = Arc<dyn Fn(f32, f32, f32) -> f32 + Send + Sync>;
+
+/// Values displayed by a heatmap.
+#[derive(Clone)]
+pub enum HeatmapSource {
+    /// Function of x, y and time, evaluated for each pixel of the canvas.
+    Function(HeatmapFunction),
+
+    /// Row-major grid of `width` x `height` values covering the rectangle from `lo` to `up` in
+    /// plot coordinates. The first row lies at `lo.y`, and each value is located at the center of its cell.
+    Grid {
+        values: Vec<f32>,
+        width: usize,
+        height: usize,
+        lo: Vec2,
+        up: Vec2,
+    },
+}
+
+impl std::fmt::Debug for HeatmapSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HeatmapSource::Function(_) => f.write_str("Function"),
+            HeatmapSource::Grid {
+                width,
+                height,
+                lo,
+                up,
+                ..
+            } => f
+                .debug_struct("Grid")
+                .field("width", width)
+                .field("height", height)
+                .field("lo", lo)
+                .field("up", up)
+                .finish_non_exhaustive(),
+        }
+    }
+}
+
+/// Struct containing the data to be plotted and metaparameters of a heatmap.
+/// It can be found in the `data.heatmap_groups` sub-field of a [`Plot`].
+#[derive(Debug, Clone)]
+pub struct HeatmapData {
+    pub source: HeatmapSource,
+
+    /// Maps the values to colors
+    pub colormap: Colormap,
+
+    /// Values mapped to the ends of the colormap. If None, the range of the values of the grid,
+    /// or of the values of the function visible on the canvas, is used.
+    pub value_range: Option<(f32, f32)>,
+
+    /// If true, the values of a grid are interpolated linearly between the centers of the cells.
+    /// Else, each cell has a uniform color. Ignored for functions.
+    pub interpolate: bool,
+
+    /// Opacity of the heatmap, so that the grid can still be seen through it
+    pub alpha: f32,
+
+    /// If true, bevy_plot recomputes the function every frame. Ignored for grids.
+    pub show_animation: bool,
+}
+
+impl Default for HeatmapData {
+    fn default() -> Self {
+        HeatmapData {
+            source: HeatmapSource::Function(Arc::new(|x: f32, y: f32, _t: f32| x * y)),
+            colormap: Colormap::Viridis,
+            value_range: None,
+            interpolate: false,
+            alpha: 0.85,
+            show_animation: false,
+        }
+    }
+}
+
+/// Struct containing the rectangles and metaparameters of a histogram or of a series of a bar chart.
+/// It can be found in the `data.bar_groups` sub-field of a [`Plot`].
+#[derive(Debug, Clone)]
+pub struct BarData {
+    /// Lower left and upper right corners of each bar, in plot coordinates
+    pub rects: Vec<(Vec2, Vec2)>,
+
+    /// Fill color of the bars
+    pub color: Color,
+
+    /// If true, the bars are displayed with a thin border of color `edge_color`
+    pub draw_contour: bool,
+
+    /// Color of the border of the bars
+    pub edge_color: Color,
+
+    /// Name of the bars in the legend. Bars without a label are not listed.
+    pub label: Option<String>,
+
+    /// If true, the bars are not drawn. Toggled by clicking on their legend entry.
+    pub hidden: bool,
+}
+
+impl Default for BarData {
+    fn default() -> Self {
+        BarData {
+            rects: vec![],
+            color: Color::rgba(0.33, 0.56, 0.82, 0.85),
+            draw_contour: true,
+            edge_color: Color::rgb(0.1, 0.1, 0.15),
+            label: None,
+            hidden: false,
         }
     }
 }
 
+// Default color of the kth series of a bar chart
+fn series_color(k: usize) -> Color {
+    let colors = ["5590d1", "e4683f", "8eb274", "9e6ea2", "f2b44f", "66656a"];
+    let mut color = Color::hex(colors[k % colors.len()]).unwrap();
+    color.set_a(0.85);
+    color
+}
 
 /// Struct containing the data to be plotted and metaparameters of a marker (or scatter) plot.
 /// It can be found in the `data.marker_groups` sub-field of a [`Plot`].
@@ -224,6 +562,44 @@ pub struct MarkerData {
 
     /// If true, the markers are displayed with a black border
     pub draw_contour: bool,
+
+    /// Color of each marker. If empty, all the markers have the color `color`.
+    pub colors: Vec<Color>,
+
+    /// Scale factor of each marker, for instance to display a bubble chart. If empty, all the
+    /// markers have the same size.
+    pub sizes: Vec<f32>,
+
+    /// Value of each marker, mapped to a color through `colormap`. Takes precedence over `colors`
+    /// when not empty.
+    pub values: Vec<f32>,
+
+    /// Colormap used to color the markers according to `values`
+    pub colormap: Colormap,
+
+    /// Values mapped to the ends of `colormap`. Defaults to the range of `values`.
+    pub value_range: Option<(f32, f32)>,
+
+    /// Name of the markers in the legend. Markers without a label are not listed.
+    pub label: Option<String>,
+
+    /// If true, the markers are not drawn. Toggled by clicking on their legend entry.
+    pub hidden: bool,
+
+    /// Whether the markers are read on the secondary x axis (top) and y axis (right)
+    pub secondary: BVec2,
+
+    /// Maximum number of markers kept when points are appended with [`Plot::push_marker_points`].
+    /// The oldest markers are dropped first. If None, the markers are never dropped.
+    pub capacity: Option<usize>,
+
+    /// If not None, the markers can be dragged with the left mouse button instead of panning the
+    /// axes, which edits `data` and sends a [`PlotDataEdited`] event on release.
+    pub draggable: Option<DragConstraints>,
+
+    /// Index in `data.segment_groups` of the line going through the markers, whose points are moved
+    /// along with the dragged markers. Set by [`Plot::plotopt`] when it draws both a line and markers.
+    pub segment_group: Option<usize>,
 }
 
 impl Default for MarkerData {
@@ -235,6 +611,82 @@ impl Default for MarkerData {
             marker_style: MarkerStyle::Circle,
             size: 1.0,
             draw_contour: false,
+            colors: vec![],
+            sizes: vec![],
+            values: vec![],
+            colormap: Colormap::default(),
+            value_range: None,
+            label: None,
+            hidden: false,
+            secondary: BVec2::new(false, false),
+            capacity: None,
+            draggable: None,
+            segment_group: None,
+        }
+    }
+}
+
+impl MarkerData {
+    /// Color of each marker, taking `values` and `colors` into account
+    pub fn point_colors(&self) -> Vec<Color> {
+        let (lo, up) = self
+            .value_range
+            .unwrap_or_else(|| value_range(self.values.iter().copied()));
+
+        (0..self.data.len())
+            .map(|k| match self.values.get(k) {
+                Some(value) => self.colormap.color_in_range(*value, lo, up),
+                None => self.colors.get(k).copied().unwrap_or(self.color),
+            })
+            .collect()
+    }
+
+    /// Scale factor of the kth marker
+    pub fn point_size(&self, k: usize) -> f32 {
+        self.sizes.get(k).map_or(1.0, |s| s.max(0.0))
+    }
+}
+
+/// Restricts how the markers of a group move when they are dragged with the mouse, see `Opt::Draggable`.
+/// The default lets the markers move freely.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DragConstraints {
+    /// Whether the markers move along the x axis and along the y axis
+    pub axes: BVec2,
+
+    /// If true, a marker cannot be dragged past the x coordinate of its neighbours, which keeps
+    /// sorted data sorted
+    pub monotonic_x: bool,
+
+    /// If true, the dragged marker snaps to the intersections of the grid, which are spaced by the
+    /// `tick_period` of the [`Plot`]
+    pub snap_to_grid: bool,
+}
+
+impl Default for DragConstraints {
+    fn default() -> Self {
+        DragConstraints {
+            axes: BVec2::new(true, true),
+            monotonic_x: false,
+            snap_to_grid: false,
+        }
+    }
+}
+
+impl DragConstraints {
+    /// The markers only move horizontally
+    pub fn x_only() -> Self {
+        DragConstraints {
+            axes: BVec2::new(true, false),
+            ..Default::default()
+        }
+    }
+
+    /// The markers only move vertically
+    pub fn y_only() -> Self {
+        DragConstraints {
+            axes: BVec2::new(false, true),
+            ..Default::default()
         }
     }
 }
@@ -251,8 +703,20 @@ pub struct SegmentData {
     pub size: f32,
     /// If the `line_style` is set to `LineStyle::None`, the segments are not drawn
     pub line_style: LineStyle,
+    /// Length of the dashes in pixels for the dashed line styles
+    pub dash_length: f32,
     pub draw_contour: bool,
     pub mech: bool,
+    /// Name of the segments in the legend. Segments without a label are not listed.
+    pub label: Option<String>,
+    /// If true, the segments are not drawn. Toggled by clicking on their legend entry.
+    pub hidden: bool,
+    /// Whether the segments are read on the secondary x axis (top) and y axis (right)
+    pub secondary: BVec2,
+
+    /// Maximum number of points kept when points are appended with [`Plot::push_segment_points`].
+    /// The oldest points are dropped first. If None, the points are never dropped.
+    pub capacity: Option<usize>,
 }
 
 impl Default for SegmentData {
@@ -262,9 +726,104 @@ impl Default for SegmentData {
             color: Color::hex("8eb274").unwrap(),
             size: 1.0,
             line_style: LineStyle::Solid,
+            dash_length: 10.0,
             draw_contour: false,
             mech: false,
+            label: None,
+            hidden: false,
+            secondary: BVec2::new(false, false),
+            capacity: None,
+        }
+    }
+}
+
+/// Size of the error bars around each data point, in plot coordinates.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErrorBars {
+    /// The same error below and above each point
+    Symmetric(Vec<f32>),
+
+    /// (lower, upper) errors for each point
+    Asymmetric(Vec<(f32, f32)>),
+}
+
+impl ErrorBars {
+    /// (lower, upper) errors for each point. Negative errors are taken as positive.
+    pub fn lower_upper(&self) -> Vec<(f32, f32)> {
+        match self {
+            ErrorBars::Symmetric(errors) => errors.iter().map(|e| (e.abs(), e.abs())).collect(),
+            ErrorBars::Asymmetric(errors) => {
+                errors.iter().map(|(lo, up)| (lo.abs(), up.abs())).collect()
+            }
+        }
+    }
+}
+
+/// Struct containing the data points, their errors and the metaparameters of error bars.
+/// It can be found in the `data.errorbar_groups` sub-field of a [`Plot`].
+#[derive(Debug, Clone)]
+pub struct ErrorBarData {
+    /// Center of each error bar
+    pub data: Vec<Vec2>,
+
+    /// (left, right) errors along the x axis. Empty if there are no horizontal error bars.
+    pub x_errors: Vec<(f32, f32)>,
+
+    /// (below, above) errors along the y axis. Empty if there are no vertical error bars.
+    pub y_errors: Vec<(f32, f32)>,
+
+    /// Color of the error bars and of their caps
+    pub color: Color,
+
+    /// Thickness of the error bars
+    pub size: f32,
+
+    /// Width of the caps in pixels. No cap is drawn if zero.
+    pub cap_width: f32,
+
+    /// Label shared with the data points, so that the error bars are hidden together with them
+    pub label: Option<String>,
+
+    /// If true, the error bars are not drawn
+    pub hidden: bool,
+
+    /// Whether the error bars are read on the secondary x axis (top) and y axis (right)
+    pub secondary: BVec2,
+}
+
+impl Default for ErrorBarData {
+    fn default() -> Self {
+        ErrorBarData {
+            data: vec![],
+            x_errors: vec![],
+            y_errors: vec![],
+            color: Color::rgb(0.25, 0.25, 0.3),
+            size: 0.6,
+            cap_width: 8.0,
+            label: None,
+            hidden: false,
+            secondary: BVec2::new(false, false),
+        }
+    }
+}
+
+impl ErrorBarData {
+    /// Ends of all the error bars, in plot coordinates
+    fn extremities(&self) -> Vec<Vec2> {
+        let mut ends = self.data.clone();
+
+        for (k, p) in self.data.iter().enumerate() {
+            if let Some((lo, up)) = self.x_errors.get(k) {
+                ends.push(Vec2::new(p.x - lo, p.y));
+                ends.push(Vec2::new(p.x + up, p.y));
+            }
+            if let Some((lo, up)) = self.y_errors.get(k) {
+                ends.push(Vec2::new(p.x, p.y - lo));
+                ends.push(Vec2::new(p.x, p.y + up));
+            }
         }
+
+        ends
     }
 }
 
@@ -275,7 +834,10 @@ pub struct PlotData {
     pub marker_groups: Vec<MarkerData>,
     pub segment_groups: Vec<SegmentData>,
     pub bezier_groups: Vec<BezierData>,
-    
+    pub implicit_groups: Vec<ImplicitData>,
+    pub heatmap_groups: Vec<HeatmapData>,
+    pub bar_groups: Vec<BarData>,
+    pub errorbar_groups: Vec<ErrorBarData>,
 }
 
 impl Default for PlotData {
@@ -284,6 +846,10 @@ impl Default for PlotData {
             marker_groups: Vec::new(),
             segment_groups: Vec::new(),
             bezier_groups: Vec::new(), 
+            implicit_groups: Vec::new(),
+            heatmap_groups: Vec::new(),
+            bar_groups: Vec::new(),
+            errorbar_groups: Vec::new(),
         }
     }
 }
@@ -327,11 +893,17 @@ pub enum LineStyle{
     None,
     Solid,
 
-    // // unimplemented
-    // Dashed,
-    // Dotted,
-    // DashDot,
-    // DashDotDot,
+    /// Dashes of length `dash_length` separated by gaps of half that length
+    Dashed,
+
+    /// Round dots spaced by a third of `dash_length`
+    Dotted,
+
+    /// Alternating dashes and dots
+    DashDot,
+
+    /// A dash followed by two dots
+    DashDotDot,
 }
 
 impl LineStyle {
@@ -339,14 +911,26 @@ impl LineStyle {
         match self {
             LineStyle::None => -1,
             LineStyle::Solid => 0,
-            // LineStyle::Dashed => 1,
-            // LineStyle::Dotted => 2,
-            // LineStyle::DashDot => 3,
-            // LineStyle::DashDotDot => 4,
+            LineStyle::Dashed => 1,
+            LineStyle::Dotted => 2,
+            LineStyle::DashDot => 3,
+            LineStyle::DashDotDot => 4,
         }
     }
 }
 
+/// Arrangement of the bars of a bar chart with several series.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum BarLayout {
+    /// The bars of each category are placed side by side
+    #[default]
+    Grouped,
+
+    /// The bars of each category are stacked on top of each other. Positive and negative values
+    /// are stacked separately.
+    Stacked,
+}
+
 /// Options for customizing the appearance of the plot.
 #[derive(Debug, Clone, PartialEq)]
 // Options as the second argument the of plotop method
@@ -357,16 +941,22 @@ pub enum Opt {
     /// Thickness of a curve or segment. Shared between [`Plot::plotopt_func`]` and [`Plot::plotopt`]
     Size(f32),
 
-    /// Either [`LineStyle::None`] or [`LineStyle::Solid`]. The former can be used to
+    /// Solid, dashed or dotted lines. [`LineStyle::None`] can be used to
     /// avoid spawning either the segments or the bezier curves, depending on the type of plot.
     LineStyle(LineStyle),
 
+    /// Length of the dashes of the dashed line styles, in pixels.
+    DashLength(f32),
+
+    /// Name of the curve or of the data in the legend. See [`Plot::legend`].
+    Label(String),
+
     /// If true, the shader will draw joints between the segments of a regular plot or the 
     /// parts of a func curve.
     Mech(bool),
     
     /// Determines the number of separate parts in a func plot. 
-    /// Works with [`Plot::plotopt`] only.
+    /// Works with [`Plot::plot_parametric`] only. Values below 2 are raised to 2.
     NumPoints(usize),
 
     /// If true, bevy_plot computes the `function` field of [`BezierData`] at every frame.
@@ -388,7 +978,78 @@ pub enum Opt {
 
     /// If true, the markers are displayed with a black border.
     Contour(bool),
-    
+
+    /// Color of each marker.
+    MarkerColors(Vec<Color>),
+
+    /// Scale factor of each marker, for bubble charts.
+    MarkerSizes(Vec<f32>),
+
+    /// Value of each marker, mapped to a color through `Opt::Colormap` and `Opt::ValueRange`.
+    MarkerValues(Vec<f32>),
+
+    /// Fills the region where g(x, y) < 0 with the given color. Works with [`Plot::plotopt_implicit`] only.
+    FillColor(Color),
+
+    /// Colormap of a heatmap, or of markers colored with `Opt::MarkerValues`.
+    Colormap(Colormap),
+
+    /// Values mapped to the ends of the colormap of a heatmap or of markers.
+    ValueRange(f32, f32),
+
+    /// If true, the values of a heatmap grid are interpolated between the cells.
+    Interpolate(bool),
+
+    /// Opacity of a heatmap.
+    Alpha(f32),
+
+    /// Determines the bins of a histogram.
+    Bins(BinStrategy),
+
+    /// If true, a histogram is normalized such that its total area is 1.
+    Density(bool),
+
+    /// If true, each bar of a histogram also counts the values of the previous bins.
+    Cumulative(bool),
+
+    /// Arrangement of the series of a bar chart.
+    BarLayout(BarLayout),
+
+    /// If true, the bars of a bar chart are horizontal and the categories lie on the y axis.
+    Horizontal(bool),
+
+    /// Fraction of the space allotted to each category that is covered by bars, between 0 and 1.
+    BarWidth(f32),
+
+    /// Horizontal error bars, one error per data point.
+    XErr(ErrorBars),
+
+    /// Vertical error bars, one error per data point.
+    YErr(ErrorBars),
+
+    /// Width of the caps of the error bars in pixels. Set to zero to remove the caps.
+    CapWidth(f32),
+
+    /// Color of the error bars.
+    ErrorColor(Color),
+
+    /// Thickness of the error bars.
+    ErrorSize(f32),
+
+    /// If true, the x coordinates of the data are read on the secondary x axis, at the top of the
+    /// canvas. See [`Plot::set_secondary_x_bounds`].
+    SecondaryX(bool),
+
+    /// If true, the y coordinates of the data are read on the secondary y axis, on the right of the
+    /// canvas. See [`Plot::set_secondary_y_bounds`].
+    SecondaryY(bool),
+
+    /// Maximum number of points kept by the segments and markers of a group when points are
+    /// appended to it, see [`Plot::push_segment_points`]. The oldest points are dropped first.
+    Capacity(usize),
+
+    /// Lets the markers be dragged with the left mouse button, within the given constraints.
+    Draggable(DragConstraints),
 }
 
 /// Contains all relevant information to both the look of the canvas and the data to be plotted.
@@ -401,7 +1062,8 @@ pub struct Plot {
     /// Position of the canvas in `World` coordinates
     pub canvas_position: Vec2,
 
-    /// Distance between consecutive grid lines
+    /// Distance between consecutive grid lines. When an axis is not linear, the tick period
+    /// is expressed in the scaled space of that axis (e.g. in decades for [`AxisScale::Log10`]).
     pub tick_period: Vec2,
     
     /// Size of the margins with respect to the canvas_size. The default is set to `Vec2::new(0.03 * size.y / size.x, 0.03)`
@@ -455,6 +1117,18 @@ pub struct Plot {
     /// Number of significant digits for the target coordinates
     pub target_significant_digits: usize,
 
+    /// If true, hovering over a marker or over a point of a regular plot highlights it and shows a
+    /// tooltip with its coordinates, the label of its group and its index. Tooltips are opt-in and
+    /// false by default, as they add a text entity and a highlighted marker on top of the canvas.
+    pub show_tooltips: bool,
+
+    /// Distance in pixels from the cursor within which data points and curves are picked, for
+    /// showing tooltips and sending the [`PlotPointClicked`] and [`PlotCurveHovered`] events
+    pub pick_radius: f32,
+
+    /// Color of the tooltip text
+    pub tooltip_color: Color,
+
     /// Axes are shown by default
     pub show_axes: bool,
 
@@ -463,10 +1137,79 @@ pub struct Plot {
 
     /// Contains the data and metaparameters needed for drawing each kind of plot
     pub data: PlotData,
+
+    /// Scale of the horizontal axis. Use [`Plot::set_axis_scales`] to change it.
+    pub x_axis_scale: AxisScale,
+
+    /// Scale of the vertical axis. Use [`Plot::set_axis_scales`] to change it.
+    pub y_axis_scale: AxisScale,
+
+    /// If true, the data is given in polar coordinates (θ, r) and the canvas shows a polar grid.
+    /// The axis scales are ignored in polar mode. Use [`Plot::set_polar`] to change it.
+    pub polar: bool,
+
+    /// If not empty, the x axis is categorical: the nth category lies at x = n and its name
+    /// replaces the numeric tick labels. Set by [`Plot::plotopt_bars`].
+    pub x_categories: Vec<String>,
+
+    /// If not empty, the y axis is categorical. See `x_categories`.
+    pub y_categories: Vec<String>,
+
+    /// Placement and appearance of the legend, listing the groups that have a label
+    pub legend: Legend,
+
+    /// Title shown above the canvas
+    pub title: Option<String>,
+
+    /// Label shown below the canvas, under the x tick labels
+    pub x_label: Option<String>,
+
+    /// Label shown to the left of the canvas, rotated by 90 degrees
+    pub y_label: Option<String>,
+
+    /// Label shown above the canvas, for the secondary x axis
+    pub secondary_x_label: Option<String>,
+
+    /// Label shown to the right of the canvas, for the secondary y axis
+    pub secondary_y_label: Option<String>,
+
+    /// Font size of the title in pixels
+    pub title_font_size: f32,
+
+    /// Font size of the axis labels in pixels
+    pub axis_label_font_size: f32,
+
+    /// Color of the title and of the axis labels
+    pub axis_label_color: Color,
+
+    /// Font of the tick labels, of the title and axis labels, of the legend and of the target coordinates.
+    /// If None, the font of the [`TickLabelFont`] resource is used, and no text is shown if neither is set.
+    pub font: Option<Handle<Font>>,
+
+    /// Distance between consecutive tick labels of the secondary axes, in the scaled space of each axis.
+    /// See [`Plot::set_secondary_x_bounds`] and [`Plot::set_secondary_y_bounds`].
+    pub secondary_tick_period: Vec2,
+
+    /// If true, the x axis scrolls like a strip chart so that the latest point appended with
+    /// [`Plot::push_segment_points`] or [`Plot::push_marker_points`] stays at the right edge of the canvas.
+    /// The range of the x axis is kept.
+    pub follow_latest: bool,
+
+    /// Axes along which the canvas cannot be panned with the mouse. Setting the bounds from code is
+    /// still allowed. For a time series, `BVec2::new(false, true)` only lets the x axis scroll.
+    pub lock_pan: BVec2,
+
+    /// Axes along which the canvas cannot be zoomed with the mousewheel or with a box zoom.
+    /// Setting the bounds from code is still allowed.
+    pub lock_zoom: BVec2,
     
     pub(crate) target_position: Vec2,
     pub(crate) target_toggle: bool,
     pub(crate) bounds: PlotCanvasBounds,
+    pub(crate) secondary_bounds: PlotCanvasBounds,
+    pub(crate) secondary_axes: BVec2,
+    pub(crate) stream_updates: StreamUpdates,
+    pub(crate) point_index: Option<PointIndex>,
     pub(crate) bezier_dummy: f32,
     pub(crate) do_spawn_plot: bool,
 }
@@ -508,11 +1251,41 @@ impl Default for Plot {
             target_color: Color::GRAY,
             target_position: Vec2::new(0.0, 0.0),
             target_significant_digits: 2,
+            show_tooltips: false,
+            pick_radius: 8.0,
+            tooltip_color: Color::rgb(0.85, 0.85, 0.85),
 
             canvas_position: Vec2::ZERO,
 
             data: PlotData::default(),
 
+            x_axis_scale: AxisScale::Linear,
+            y_axis_scale: AxisScale::Linear,
+            polar: false,
+            x_categories: Vec::new(),
+            y_categories: Vec::new(),
+            legend: Legend::default(),
+            title: None,
+            x_label: None,
+            y_label: None,
+            secondary_x_label: None,
+            secondary_y_label: None,
+            title_font_size: 24.0,
+            axis_label_font_size: 18.0,
+            axis_label_color: Color::BLACK,
+            font: None,
+            secondary_tick_period: Vec2::new(0.2, 0.2),
+            secondary_bounds: PlotCanvasBounds {
+                up: Vec2::new(1.2, 1.2),
+                lo: Vec2::new(-0.2, -0.2),
+            },
+            secondary_axes: BVec2::new(false, false),
+            follow_latest: false,
+            lock_pan: BVec2::new(false, false),
+            lock_zoom: BVec2::new(false, false),
+            stream_updates: StreamUpdates::default(),
+            point_index: None,
+
             bezier_num_points: 100,
             bezier_dummy: 0.0,
 
@@ -533,9 +1306,13 @@ impl Plot {
         //
         let data_in_plot_format: PlotFormat = v.into_plot_format();
 
+        let secondary = Plot::secondary_option(&options);
+        let mut segment_group = None;
+
         if !options.contains(&Opt::LineStyle(LineStyle::None)) {
             let mut data = SegmentData {
                 data: data_in_plot_format.data.clone(),
+                secondary,
                 ..Default::default() 
             };
 
@@ -548,28 +1325,43 @@ impl Plot {
                     },
                     Opt::LineStyle(style)=> { data.line_style = style.clone(); },
 
+                    Opt::DashLength(length)=> { data.dash_length = length.max(1.0); },
+
                     Opt::Mech(mech)=> { data.mech = *mech; },
 
+                    Opt::Label(label)=> { data.label = Some(label.clone()); },
+
+                    Opt::Capacity(capacity)=> { data.capacity = Some(*capacity); },
+
                     _ => {},
 
                 }
             }
                 
+            segment_group = Some(self.data.segment_groups.len());
             self.data.segment_groups.push(data);
 
         }
 
         // Decide whether to draw markers using the options.
-        // If any of MarkerStyle or MarkerSize is specified, draw markers
-        let draw_markers = options.iter().map(|opt| {
-            if let &Opt::MarkerStyle(_) = opt  { true } 
-            else if let &Opt::MarkerSize(_) = opt  { true } 
-            else { false }
-        }).any(|x| x);
+        // If any of MarkerStyle, MarkerSize, a per-point marker option or Draggable is specified, draw markers
+        let draw_markers = options.iter().any(|opt| {
+            matches!(
+                opt,
+                Opt::MarkerStyle(_)
+                    | Opt::MarkerSize(_)
+                    | Opt::MarkerColors(_)
+                    | Opt::MarkerSizes(_)
+                    | Opt::MarkerValues(_)
+                    | Opt::Draggable(_)
+            )
+        });
 
         if draw_markers {
             let mut data = MarkerData {
                 data: data_in_plot_format.data.clone(),
+                secondary,
+                segment_group,
                 ..Default::default() 
             };
 
@@ -584,6 +1376,14 @@ impl Plot {
                     Opt::MarkerStyle(style)=> { data.marker_style = style.clone(); },
                     Opt::MarkerInnerPointColor(col) => { data.marker_point_color = col.clone();},
                     Opt::Contour(cont)=> { data.draw_contour = *cont; },
+                    Opt::MarkerColors(colors) => { data.colors = colors.clone(); },
+                    Opt::MarkerSizes(sizes) => { data.sizes = sizes.clone(); },
+                    Opt::MarkerValues(values) => { data.values = values.clone(); },
+                    Opt::Colormap(colormap) => { data.colormap = colormap.clone(); },
+                    Opt::ValueRange(lo, up) => { data.value_range = Some((*lo, *up)); },
+                    Opt::Label(label) => { data.label = Some(label.clone()); },
+                    Opt::Capacity(capacity) => { data.capacity = Some(*capacity); },
+                    Opt::Draggable(constraints) => { data.draggable = Some(constraints.clone()); },
                     _ => {},
 
                 }
@@ -591,43 +1391,123 @@ impl Plot {
             
             self.data.marker_groups.push(data);
         }
+
+        self.push_errorbars(&data_in_plot_format.data, &options);
     } 
 
-    /// Quickly plot data points using segments to connect consecutive points. Takes any type 
-    /// that implements [`Plotable`], namely `Vec<Vec2>`, `Vec<(f64, f64)>`, `Vec<f32>`, ...
-    pub fn plot(&mut self, v: impl Plotable) {
+    /// Plot data points as markers with vertical error bars, using the same options as [`Plot::plotopt`].
+    /// Horizontal error bars can be added with `Opt::XErr`. The bounds are adjusted so that the
+    /// error bars are visible.
+    pub fn plot_errorbars(&mut self, v: impl Plotable, y_errors: ErrorBars, mut options: Vec<Opt>) {
         //
-        let pf: PlotFormat = v.into_plot_format();
+        let data = v.into_plot_format().data;
 
-        let data = &pf.data;
+        options.retain(|opt| !matches!(opt, Opt::YErr(_)));
+        options.push(Opt::YErr(y_errors));
 
-        let lo_x = data
-            .iter()
-            .min_by(|q, r| q.x.partial_cmp(&r.x).unwrap())
-            .unwrap().x;
+        if !options.iter().any(|opt| matches!(opt, Opt::LineStyle(_))) {
+            options.push(Opt::LineStyle(LineStyle::None));
+        }
 
-        let lo_y = data
-            .iter()
-            .min_by(|q, r| q.y.partial_cmp(&r.y).unwrap())
-            .unwrap().y;
+        if !options.iter().any(|opt| matches!(opt, Opt::MarkerStyle(_) | Opt::MarkerSize(_))) {
+            options.push(Opt::MarkerStyle(MarkerStyle::Circle));
+        }
 
-        let up_x = data
-            .iter()
-            .max_by(|q, r| q.x.partial_cmp(&r.x).unwrap())
-            .unwrap().x;
-        
-        let up_y = data
-            .iter()
-            .max_by(|q, r| q.y.partial_cmp(&r.y).unwrap())
-            .unwrap().y;
+        self.plotopt(data, options);
 
-        let dx = (up_x - lo_x).abs() * 0.1;
-        let dy = (up_y - lo_y).abs() * 0.1;
-        
+        if let Some(error_bars) = self.data.errorbar_groups.last() {
+            let ends = error_bars.extremities();
+            // the bounds of the secondary axes are set by the user
+            if !ends.is_empty() && !error_bars.secondary.any() {
+                self.fit_bounds(&ends);
+            }
+        }
+    }
 
-        self.set_bounds(Vec2::new(lo_x - dx, lo_y - dy) , Vec2::new(up_x +dx, up_y + dy));
+    /// Same as [`Plot::plot_errorbars`], but returns an error instead of adding groups if the data is empty,
+    /// if none of its points can be displayed, or if the number of errors along an axis is not the
+    /// number of points.
+    pub fn try_plot_errorbars(
+        &mut self,
+        v: impl Plotable,
+        y_errors: ErrorBars,
+        options: Vec<Opt>,
+    ) -> Result<(), PlotError> {
+        let data = v.into_plot_format().data;
+        self.check_data(&data)?;
+
+        let x_errors = options.iter().find_map(|opt| match opt {
+            Opt::XErr(errors) => Some(errors),
+            _ => None,
+        });
+
+        for errors in std::iter::once(&y_errors).chain(x_errors) {
+            let found = errors.lower_upper().len();
+            if found != data.len() {
+                return Err(PlotError::LengthMismatch { expected: data.len(), found });
+            }
+        }
 
-        let new_data = SegmentData {
+        self.plot_errorbars(data, y_errors, options);
+        Ok(())
+    }
+
+    // Pushes error bars to `data.errorbar_groups` if `Opt::XErr` or `Opt::YErr` is present
+    fn push_errorbars(&mut self, points: &[Vec2], options: &[Opt]) {
+        let mut data = ErrorBarData {
+            data: points.to_vec(),
+            secondary: Plot::secondary_option(options),
+            ..Default::default()
+        };
+
+        let mut has_errors = false;
+
+        for option in options.iter() {
+            match option {
+                Opt::XErr(errors) => {
+                    data.x_errors = errors.lower_upper();
+                    has_errors = true;
+                }
+                Opt::YErr(errors) => {
+                    data.y_errors = errors.lower_upper();
+                    has_errors = true;
+                }
+                Opt::CapWidth(w) => { data.cap_width = w.max(0.0); },
+                Opt::ErrorColor(col) => { data.color = *col; },
+                Opt::ErrorSize(si) => { data.size = *si; },
+                Opt::Label(label) => { data.label = Some(label.clone()); },
+                _ => {},
+            }
+        }
+
+        if !has_errors {
+            return;
+        }
+
+        for errors in [&data.x_errors, &data.y_errors] {
+            if !errors.is_empty() && errors.len() != points.len() {
+                eprintln!(
+                    "{} errors were given for {} data points, the missing error bars are not drawn",
+                    errors.len(),
+                    points.len()
+                );
+            }
+        }
+
+        self.data.errorbar_groups.push(data);
+    }
+
+    /// Quickly plot data points using segments to connect consecutive points. Takes any type 
+    /// that implements [`Plotable`], namely `Vec<Vec2>`, `Vec<(f64, f64)>`, `Vec<f32>`, ...
+    /// Points that cannot be displayed (NaN, infinite, ...) are skipped. See [`Plot::try_plot`] to
+    /// detect data that cannot be displayed at all.
+    pub fn plot(&mut self, v: impl Plotable) {
+        //
+        let pf: PlotFormat = v.into_plot_format();
+
+        self.fit_bounds(&pf.data);
+
+        let new_data = SegmentData {
             data: pf.data,
             ..Default::default()  
         };
@@ -636,53 +1516,208 @@ impl Plot {
         
     }
 
-    /// Quickly plot data points using markers (scatter plot). 
+    /// Quickly plot data points using markers (scatter plot). Points that cannot be displayed are skipped.
     pub fn plotm<T: Plotable>(&mut self, v: T) {
         //
         let pf: PlotFormat = v.into_plot_format();
 
         let data = pf.data;
 
-        let lo_x = data
-            .iter()
-            .min_by(|q, r| q.x.partial_cmp(&r.x).unwrap())
-            .unwrap().x;
+        self.fit_bounds(&data);
 
-        let lo_y = data
-            .iter()
-            .min_by(|q, r| q.y.partial_cmp(&r.y).unwrap())
-            .unwrap().y;
+        let new_data = MarkerData {
+            data,
+            ..Default::default()                   
+        };
 
-        let up_x = data
-            .iter()
-            .max_by(|q, r| q.x.partial_cmp(&r.x).unwrap())
-            .unwrap().x;
-        
-        let up_y = data
-            .iter()
-            .max_by(|q, r| q.y.partial_cmp(&r.y).unwrap())
-            .unwrap().y;
+        self.data.marker_groups.push(new_data);        
+    }
 
-        let dx = (up_x - lo_x).abs() * 0.1;
-        let dy = (up_y - lo_y).abs() * 0.1;
+    /// Same as [`Plot::plot`], but returns an error instead of adding a group if the data is empty or if
+    /// none of its points can be displayed.
+    pub fn try_plot(&mut self, v: impl Plotable) -> Result<(), PlotError> {
+        let data = v.into_plot_format().data;
+        self.check_data(&data)?;
+        self.plot(data);
+        Ok(())
+    }
 
-        self.set_bounds(Vec2::new(lo_x - dx, lo_y - dy) , Vec2::new(up_x +dx, up_y + dy));
+    /// Same as [`Plot::plotm`], but returns an error instead of adding a group if the data is empty or if
+    /// none of its points can be displayed.
+    pub fn try_plotm(&mut self, v: impl Plotable) -> Result<(), PlotError> {
+        let data = v.into_plot_format().data;
+        self.check_data(&data)?;
+        self.plotm(data);
+        Ok(())
+    }
 
-        
-        let new_data = MarkerData {
-            data: data,
-            ..Default::default()                   
+    /// Same as [`Plot::plotopt`], but returns an error instead of adding groups if the data is empty or if
+    /// none of its points can be displayed.
+    pub fn try_plotopt(&mut self, v: impl Plotable, options: Vec<Opt>) -> Result<(), PlotError> {
+        let data = v.into_plot_format().data;
+        self.check_data(&data)?;
+        self.plotopt(data, options);
+        Ok(())
+    }
+
+    // Font of the text of the plot, falling back on the global `TickLabelFont`
+    pub(crate) fn label_font<'a>(&'a self, tick_label_font: &'a TickLabelFont) -> Option<&'a Handle<Font>> {
+        self.font.as_ref().or(tick_label_font.maybe_font.as_ref())
+    }
+
+    // Checks that at least one of the points can be displayed with the current axis scales
+    fn check_data(&self, data: &[Vec2]) -> Result<(), PlotError> {
+        if data.is_empty() {
+            Err(PlotError::EmptyData)
+        } else if !data.iter().any(|p| self.is_displayable(*p)) {
+            Err(PlotError::NoDisplayablePoint)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Appends points to the `group`th entry of `data.segment_groups`. If the group has a `capacity`,
+    /// the oldest points are dropped. Instead of respawning the whole plot, the segments of the new
+    /// points are added to the mesh of the group and those of the dropped points are removed from it.
+    /// The work done on the CPU only depends on the number of pushed and dropped points, but the
+    /// mesh is still uploaded again as a whole.
+    ///
+    /// With `follow_latest`, the x axis scrolls to the latest point. Since every point then moves on
+    /// the canvas, every group is rebuilt from all its points, every function is respawned, and so is
+    /// the whole plot if it contains error bars, bars, heatmaps or implicit curves.
+    pub fn push_segment_points(&mut self, group: usize, points: &[Vec2]) {
+        let segment_plot = match self.data.segment_groups.get_mut(group) {
+            Some(segment_plot) => segment_plot,
+            None => {
+                eprintln!("there is no segment group number {}, the points are not pushed", group);
+                return;
+            }
         };
 
-        self.data.marker_groups.push(new_data);        
+        segment_plot.data.extend_from_slice(points);
+
+        let mut dropped = 0;
+        if let Some(capacity) = segment_plot.capacity {
+            dropped = segment_plot.data.len().saturating_sub(capacity);
+            segment_plot.data.drain(..dropped);
+        }
+
+        let secondary = segment_plot.secondary;
+
+        self.point_index = None;
+        self.stream_updates.push_segments(group, points.len(), dropped);
+        self.follow_points(points, secondary);
+    }
+
+    /// Appends points to the `group`th entry of `data.marker_groups`, see [`Plot::push_segment_points`].
+    /// When the oldest markers are dropped, so are their entries in `colors`, `sizes` and `values`.
+    /// The instances of all the markers of the group are recomputed, without respawning the plot.
+    pub fn push_marker_points(&mut self, group: usize, points: &[Vec2]) {
+        let marker_plot = match self.data.marker_groups.get_mut(group) {
+            Some(marker_plot) => marker_plot,
+            None => {
+                eprintln!("there is no marker group number {}, the points are not pushed", group);
+                return;
+            }
+        };
+
+        marker_plot.data.extend_from_slice(points);
+
+        if let Some(capacity) = marker_plot.capacity {
+            let excess = marker_plot.data.len().saturating_sub(capacity);
+            marker_plot.data.drain(..excess);
+            marker_plot.colors.drain(..excess.min(marker_plot.colors.len()));
+            marker_plot.sizes.drain(..excess.min(marker_plot.sizes.len()));
+            marker_plot.values.drain(..excess.min(marker_plot.values.len()));
+        }
+
+        let secondary = marker_plot.secondary;
+
+        self.point_index = None;
+        self.stream_updates.redraw_markers(group);
+        self.follow_points(points, secondary);
+    }
+
+    /// Moves the marker number `index` of the marker group number `group` to `value`, together with the
+    /// point at the same position on the line going through the markers, see the `segment_group` field of
+    /// [`MarkerData`]. Both groups are redrawn in place, like with [`Plot::push_marker_points`].
+    pub fn set_marker_point(&mut self, group: usize, index: usize, value: Vec2) {
+        let marker_plot = match self.data.marker_groups.get_mut(group) {
+            Some(marker_plot) => marker_plot,
+            None => {
+                eprintln!("there is no marker group number {}, the point is not moved", group);
+                return;
+            }
+        };
+
+        let previous = match marker_plot.data.get_mut(index) {
+            Some(point) => std::mem::replace(point, value),
+            None => {
+                eprintln!("there is no marker number {} in group {}, the point is not moved", index, group);
+                return;
+            }
+        };
+
+        let segment_group = marker_plot.segment_group;
+
+        self.point_index = None;
+        self.stream_updates.redraw_markers(group);
+
+        // the line is only edited if its points still match the markers
+        let segment_plot = segment_group.and_then(|k| self.data.segment_groups.get_mut(k));
+        if let Some(point) = segment_plot.and_then(|s| s.data.get_mut(index)) {
+            if *point == previous {
+                *point = value;
+
+                self.stream_updates.redraw_segments(segment_group.unwrap());
+            }
+        }
+    }
+
+    // If `follow_latest` is set, scrolls the x axis such that the rightmost of the given points lies
+    // at the right edge of the canvas, leaving a margin of 5% of the range of the axis
+    fn follow_points(&mut self, points: &[Vec2], secondary: BVec2) {
+        if !self.follow_latest || self.polar {
+            return;
+        }
+
+        let secondary_map = self.secondary_map(secondary);
+
+        let latest = points
+            .iter()
+            .map(|p| secondary_map.to_primary(*p))
+            .filter(|p| self.is_displayable(*p))
+            .map(|p| self.to_scaled(p).x)
+            .fold(f32::NEG_INFINITY, f32::max);
+
+        if !latest.is_finite() {
+            return;
+        }
+
+        let previous = self.scaled_bounds();
+        let range = previous.up.x - previous.lo.x;
+        let up = latest + range * 0.05;
+
+        if up == previous.up.x {
+            return;
+        }
+
+        self.bounds.lo.x = self.x_axis_scale.inverse(up - range);
+        self.bounds.up.x = self.x_axis_scale.inverse(up);
+        self.update_secondary_bounds(&previous);
+        self.compute_zeros();
+        self.point_index = None;
+
+        self.stream_updates.scrolled = true;
     }
 
     /// Quickly plot a function by providing said function. Defaults to a range on the both axes from `-0.2` to `1.2`.
-    pub fn plot_func(&mut self, f: fn(f32, f32) -> f32) {
+    /// Either a function or a closure can be given, the latter being able to capture runtime parameters.
+    pub fn plot_func(&mut self, f: impl Fn(f32, f32) -> f32 + Send + Sync + 'static) {
         //
 
         let new_data = BezierData {
-            function: f,
+            function: Arc::new(f),
             ..Default::default()
         };
                 
@@ -690,60 +1725,555 @@ impl Plot {
     }
 
     /// Plot a function by providing said function and options.
-    pub fn plotopt_func(&mut self, f: fn(f32, f32) -> f32, options: Vec<Opt>) {
+    pub fn plotopt_func(
+        &mut self,
+        f: impl Fn(f32, f32) -> f32 + Send + Sync + 'static,
+        options: Vec<Opt>,
+    ) {
         //
         let mut data = BezierData {
-            function: f,
+            function: Arc::new(f),
+            ..Default::default()
+        };
+
+
+        for option in options.iter() {
+            Plot::apply_bezier_option(&mut data, option);
+        }
+        data.secondary = Plot::secondary_option(&options);
+        self.data.bezier_groups.push(data);
+
+    }
+
+    /// Plot a parametric curve (x(t), y(t)) by sampling `f(t, time)` `num_points` times between
+    /// `t_range.0` and `t_range.1`. The second argument of `f` is the time, which is only used in
+    /// conjunction with `Opt::Animate(true)`. The bounds are adjusted so that the whole curve is visible.
+    /// The curve is sampled at least twice, and nothing is drawn if `t_range.0 == t_range.1`.
+    pub fn plot_parametric(
+        &mut self,
+        f: impl Fn(f32, f32) -> Vec2 + Send + Sync + 'static,
+        t_range: (f32, f32),
+        num_points: usize,
+        options: Vec<Opt>,
+    ) {
+        // at least two samples are needed to draw a curve
+        let num_points = num_points.max(2);
+
+        let samples = linspace(t_range.0, t_range.1, num_points)
+            .map(|t| f(t, 0.0))
+            .collect::<Vec<Vec2>>();
+
+        let secondary = Plot::secondary_option(&options);
+
+        // the bounds of the secondary axes are set by the user
+        if !secondary.any() {
+            self.fit_bounds(&samples);
+        }
+
+        let mut data = BezierData {
+            num_points,
+            secondary,
+            parametric: Some(ParametricData {
+                function: Arc::new(f),
+                t_range,
+            }),
+            ..Default::default()
+        };
+
+        for option in options.iter() {
+            match option {
+                Opt::NumPoints(n) => { data.num_points = (*n).max(2); },
+                _ => Plot::apply_bezier_option(&mut data, option),
+            }
+        }
+        self.data.bezier_groups.push(data);
+    }
+
+    /// Same as [`Plot::plot_parametric`], but returns an error instead of adding a curve if it is sampled
+    /// less than twice (`Opt::NumPoints` included), if `t_range` is empty or not finite, or if none of
+    /// the samples can be displayed.
+    pub fn try_plot_parametric(
+        &mut self,
+        f: impl Fn(f32, f32) -> Vec2 + Send + Sync + 'static,
+        t_range: (f32, f32),
+        num_points: usize,
+        options: Vec<Opt>,
+    ) -> Result<(), PlotError> {
+        let num_points = options.iter().fold(num_points, |n, option| match option {
+            Opt::NumPoints(n) => *n,
+            _ => n,
+        });
+
+        let (t0, t1) = t_range;
+        if num_points < 2 || !t0.is_finite() || !t1.is_finite() || t0 == t1 {
+            return Err(PlotError::InvalidSampling { t_range, num_points });
+        }
+
+        let samples = linspace(t0, t1, num_points)
+            .map(|t| f(t, 0.0))
+            .collect::<Vec<Vec2>>();
+        self.check_data(&samples)?;
+
+        self.plot_parametric(f, t_range, num_points, options);
+        Ok(())
+    }
+
+    /// Quickly plot the implicit curve g(x, y) = 0 by providing g. The arguments of g are x, y and time,
+    /// the latter being only used in conjunction with `Opt::Animate(true)`.
+    pub fn plot_implicit(&mut self, g: impl Fn(f32, f32, f32) -> f32 + Send + Sync + 'static) {
+        //
+        let new_data = ImplicitData {
+            function: Arc::new(g),
             ..Default::default()
         };
 
+        self.data.implicit_groups.push(new_data);
+    }
+
+    /// Plot the implicit curve g(x, y) = 0 with options. The region where g(x, y) < 0 can be shaded
+    /// with `Opt::FillColor`, and the curve itself can be hidden with `Opt::LineStyle(LineStyle::None)`
+    /// to only display the inequality region.
+    pub fn plotopt_implicit(
+        &mut self,
+        g: impl Fn(f32, f32, f32) -> f32 + Send + Sync + 'static,
+        options: Vec<Opt>,
+    ) {
+        //
+        let mut data = ImplicitData {
+            function: Arc::new(g),
+            ..Default::default()
+        };
 
         for option in options.iter() {
             match option {
                 Opt::Color(col) => { data.color = *col; },
+                Opt::Size(si) => { data.size = *si; },
+                Opt::LineStyle(style) => { data.line_style = style.clone(); },
+                Opt::FillColor(col) => { data.fill_color = Some(*col); },
+                Opt::Animate(animate) => { data.show_animation = *animate; },
+                _ => { eprintln!("{:?} is not a valid option for implicit curves", option); },
+            }
+        }
 
-                Opt::Size(si)=> {
-                    data.size = *si;
-                },
-                Opt::LineStyle(style)=> { data.line_style = style.clone(); },
+        self.data.implicit_groups.push(data);
+    }
 
-                Opt::Mech(mech)=> { data.mech = *mech; },
+    /// Quickly plot a heatmap of the scalar field f(x, y, time), evaluated for each pixel of the canvas.
+    pub fn heatmap(&mut self, f: impl Fn(f32, f32, f32) -> f32 + Send + Sync + 'static) {
+        self.plotopt_heatmap(f, vec![]);
+    }
+
+    /// Plot a heatmap of the scalar field f(x, y, time) with options, for instance `Opt::Colormap`,
+    /// `Opt::ValueRange` or `Opt::Animate(true)` for a field that depends on time.
+    pub fn plotopt_heatmap(
+        &mut self,
+        f: impl Fn(f32, f32, f32) -> f32 + Send + Sync + 'static,
+        options: Vec<Opt>,
+    ) {
+        let data = HeatmapData {
+            source: HeatmapSource::Function(Arc::new(f)),
+            ..Default::default()
+        };
 
-                Opt::Animate(animate) => { data.show_animation = *animate; }
+        self.push_heatmap(data, options);
+    }
 
-                Opt::MarkerStyle(_)=> { 
-                    eprintln!("MarkerStyle is not a valid option for segments"); 
-                },
+    /// Plot a heatmap of a row-major grid of `width` x `height` values covering the rectangle from `lo`
+    /// to `up` in plot coordinates. The first row lies at `lo.y`. Use `Opt::Interpolate(true)`
+    /// for a smooth heatmap. Nothing is plotted if `values.len()` is not equal to `width * height`, see
+    /// [`Plot::try_heatmap_grid`].
+    pub fn heatmap_grid(
+        &mut self,
+        values: Vec<f32>,
+        width: usize,
+        height: usize,
+        lo: Vec2,
+        up: Vec2,
+        options: Vec<Opt>,
+    ) {
+        if let Err(error) = self.try_heatmap_grid(values, width, height, lo, up, options) {
+            eprintln!("the heatmap is not plotted: {}", error);
+        }
+    }
 
-                Opt::MarkerInnerPointColor(_)=> {  
-                    eprintln!("MarkerInnerPointColor is not a valid option for segments"); 
-                },
+    /// Same as [`Plot::heatmap_grid`], but returns an error instead of adding a heatmap if the grid is empty,
+    /// if `values.len()` is not equal to `width * height`, if none of the values is finite, or if `lo`
+    /// and `up` do not form a finite rectangle with `lo` below and left of `up`.
+    pub fn try_heatmap_grid(
+        &mut self,
+        values: Vec<f32>,
+        width: usize,
+        height: usize,
+        lo: Vec2,
+        up: Vec2,
+        options: Vec<Opt>,
+    ) -> Result<(), PlotError> {
+        if values.is_empty() {
+            return Err(PlotError::EmptyData);
+        } else if values.len() != width * height {
+            return Err(PlotError::LengthMismatch { expected: width * height, found: values.len() });
+        } else if !values.iter().any(|v| v.is_finite()) {
+            return Err(PlotError::NoDisplayablePoint);
+        } else if !lo.is_finite() || !up.is_finite() {
+            return Err(PlotError::InvalidBounds { lo, up });
+        } else if lo.x >= up.x || lo.y >= up.y {
+            return Err(PlotError::InvertedBounds { lo, up });
+        }
 
-                Opt::Contour(_)=> { 
-                     println!("Contour is not a valid option for segments");
-                },
-                
-                Opt::NumPoints(_) => { 
-                    eprintln!("NumPoints is not a valid option for segments"); 
-                },
+        let data = HeatmapData {
+            source: HeatmapSource::Grid {
+                values,
+                width,
+                height,
+                lo,
+                up,
+            },
+            ..Default::default()
+        };
+
+        self.push_heatmap(data, options);
+        Ok(())
+    }
+
+    fn push_heatmap(&mut self, mut data: HeatmapData, options: Vec<Opt>) {
+        for option in options.iter() {
+            match option {
+                Opt::Colormap(colormap) => { data.colormap = colormap.clone(); },
+                Opt::ValueRange(lo, up) => { data.value_range = Some((*lo, *up)); },
+                Opt::Interpolate(interpolate) => { data.interpolate = *interpolate; },
+                Opt::Alpha(alpha) => { data.alpha = alpha.clamp(0.0, 1.0); },
+                Opt::Animate(animate) => { data.show_animation = *animate; },
+                _ => { eprintln!("{:?} is not a valid option for heatmaps", option); },
+            }
+        }
+
+        self.data.heatmap_groups.push(data);
+    }
 
-                Opt::MarkerColor(_) => { 
-                    eprintln!("MarkerColor is not a valid option for segments"); 
-                },
+    /// Quickly plot a histogram of the y values of `v`. Takes any type that implements [`Plotable`],
+    /// for instance a `Vec<f32>` of samples. The bins are chosen with [`BinStrategy::Sturges`].
+    pub fn hist(&mut self, v: impl Plotable) {
+        self.plotopt_hist(v, vec![]);
+    }
+
+    /// Plot a histogram of the y values of `v` with options, namely `Opt::Bins`, `Opt::Density`,
+    /// `Opt::Cumulative`, `Opt::Color`, `Opt::Contour` and `Opt::Label`. The bounds are adjusted so that all the bars
+    /// are visible. Non-finite values are ignored.
+    pub fn plotopt_hist(&mut self, v: impl Plotable, options: Vec<Opt>) {
+        //
+        let values = v
+            .into_plot_format()
+            .data
+            .iter()
+            .map(|p| p.y)
+            .collect::<Vec<f32>>();
 
-                Opt::MarkerSize(_) => { 
-                    eprintln!("MarkerSize is not a valid option for segments"); 
-                },
+        let mut strategy = BinStrategy::default();
+        let mut density = false;
+        let mut cumulative = false;
+        let mut data = BarData::default();
 
-                // _ => {},
+        for option in options.iter() {
+            match option {
+                Opt::Bins(bins) => { strategy = bins.clone(); },
+                Opt::Density(d) => { density = *d; },
+                Opt::Cumulative(c) => { cumulative = *c; },
+                Opt::Color(col) => { data.color = *col; },
+                Opt::Contour(cont) => { data.draw_contour = *cont; },
+                Opt::Label(label) => { data.label = Some(label.clone()); },
+                _ => { eprintln!("{:?} is not a valid option for histograms", option); },
             }
         }
-        self.data.bezier_groups.push(data);
 
+        let hist = histogram(&values, &strategy, density, cumulative);
+
+        data.rects = hist
+            .heights
+            .iter()
+            .enumerate()
+            .map(|(k, h)| (Vec2::new(hist.edges[k], 0.0), Vec2::new(hist.edges[k + 1], *h)))
+            .collect();
+
+        if !data.rects.is_empty() {
+            let corners = data
+                .rects
+                .iter()
+                .flat_map(|(lo, up)| [*lo, *up])
+                .collect::<Vec<Vec2>>();
+
+            self.fit_bounds(&corners);
+        }
+
+        self.data.bar_groups.push(data);
+    }
+
+    /// Same as [`Plot::plotopt_hist`], but returns an error instead of adding a histogram if there are no
+    /// values or if none of them is finite.
+    pub fn try_plotopt_hist(&mut self, v: impl Plotable, options: Vec<Opt>) -> Result<(), PlotError> {
+        let data = v.into_plot_format().data;
+
+        if data.is_empty() {
+            return Err(PlotError::EmptyData);
+        } else if !data.iter().any(|p| p.y.is_finite()) {
+            return Err(PlotError::NoDisplayablePoint);
+        }
+
+        self.plotopt_hist(data, options);
+        Ok(())
+    }
+
+    /// Quickly plot a vertical bar chart with one bar per category.
+    pub fn bar<S: Into<String>>(&mut self, categories: impl IntoIterator<Item = S>, values: Vec<f32>) {
+        self.plotopt_bars(categories, vec![values], vec![]);
+    }
+
+    /// Plot a bar chart with one or several series of values, the nth value of a series corresponding
+    /// to the nth category. Missing values are not displayed and extra values are ignored. The axis
+    /// holding the categories becomes categorical (see [`Plot::x_categories`]) and the bounds are adjusted
+    /// so that all the bars are visible.
+    ///
+    /// Each series is pushed to `data.bar_groups` with its own color, unless `Opt::Color` is given.
+    /// The other options are `Opt::BarLayout`, `Opt::Horizontal`, `Opt::BarWidth`, `Opt::Contour` and
+    /// `Opt::Label`, which gives a single legend entry to all the series.
+    pub fn plotopt_bars<S: Into<String>>(
+        &mut self,
+        categories: impl IntoIterator<Item = S>,
+        series: Vec<Vec<f32>>,
+        options: Vec<Opt>,
+    ) {
+        //
+        let categories = categories.into_iter().map(|c| c.into()).collect::<Vec<String>>();
+        let num_categories = categories.len();
+
+        let mut layout = BarLayout::default();
+        let mut horizontal = false;
+        let mut bar_width = 0.8;
+        let mut color = None;
+        let mut draw_contour = BarData::default().draw_contour;
+        let mut label = None;
+
+        for option in options.iter() {
+            match option {
+                Opt::BarLayout(l) => { layout = l.clone(); },
+                Opt::Horizontal(h) => { horizontal = *h; },
+                Opt::BarWidth(w) => { bar_width = w.clamp(0.0, 1.0); },
+                Opt::Color(col) => { color = Some(*col); },
+                Opt::Contour(cont) => { draw_contour = *cont; },
+                Opt::Label(l) => { label = Some(l.clone()); },
+                _ => { eprintln!("{:?} is not a valid option for bar charts", option); },
+            }
+        }
+
+        let num_series = series.len().max(1) as f32;
+
+        // bases of the stacked bars, for positive and negative values
+        let mut positive_base = vec![0.0; num_categories];
+        let mut negative_base = vec![0.0; num_categories];
+
+        let (mut lo_value, mut up_value) = (0.0_f32, 0.0_f32);
+
+        for (s, values) in series.iter().enumerate() {
+            let mut data = BarData {
+                color: color.unwrap_or_else(|| series_color(s)),
+                draw_contour,
+                label: label.clone(),
+                ..Default::default()
+            };
+
+            for (k, value) in values.iter().take(num_categories).enumerate() {
+                if !value.is_finite() {
+                    continue;
+                }
+
+                // extent of the bar along the category axis and along the value axis
+                let (left, right, bottom, top) = match layout {
+                    BarLayout::Grouped => {
+                        let width = bar_width / num_series;
+                        let left = k as f32 - bar_width / 2.0 + s as f32 * width;
+                        (left, left + width, 0.0, *value)
+                    }
+                    BarLayout::Stacked => {
+                        let base = if *value >= 0.0 {
+                            &mut positive_base[k]
+                        } else {
+                            &mut negative_base[k]
+                        };
+                        let bottom = *base;
+                        *base += value;
+                        (k as f32 - bar_width / 2.0, k as f32 + bar_width / 2.0, bottom, *base)
+                    }
+                };
+
+                lo_value = lo_value.min(bottom.min(top));
+                up_value = up_value.max(bottom.max(top));
+
+                data.rects.push(if horizontal {
+                    (Vec2::new(bottom, left), Vec2::new(top, right))
+                } else {
+                    (Vec2::new(left, bottom), Vec2::new(right, top))
+                });
+            }
+
+            self.data.bar_groups.push(data);
+        }
+
+        // categorical axes are linear
+        if horizontal {
+            self.y_axis_scale = AxisScale::Linear;
+            self.y_categories = categories;
+        } else {
+            self.x_axis_scale = AxisScale::Linear;
+            self.x_categories = categories;
+        }
+
+        if up_value <= lo_value {
+            up_value = lo_value + 1.0;
+        }
+        let margin = (up_value - lo_value) * 0.1;
+        let value_range = Vec2::new(lo_value - margin, up_value + margin);
+        let category_range = Vec2::new(-0.6, num_categories.max(1) as f32 - 0.4);
+
+        let bounds = if horizontal {
+            self.try_set_bounds(
+                Vec2::new(value_range.x, category_range.x),
+                Vec2::new(value_range.y, category_range.y),
+            )
+        } else {
+            self.try_set_bounds(
+                Vec2::new(category_range.x, value_range.x),
+                Vec2::new(category_range.y, value_range.y),
+            )
+        };
+
+        if let Err(error) = bounds {
+            eprintln!("the bounds could not be fitted to the bars: {}", error);
+        }
+    }
+
+    /// Same as [`Plot::plotopt_bars`], but returns an error instead of adding bars if there are no
+    /// categories or values, or if none of the values of the categories is finite.
+    pub fn try_plotopt_bars<S: Into<String>>(
+        &mut self,
+        categories: impl IntoIterator<Item = S>,
+        series: Vec<Vec<f32>>,
+        options: Vec<Opt>,
+    ) -> Result<(), PlotError> {
+        let categories = categories.into_iter().map(|c| c.into()).collect::<Vec<String>>();
+
+        // extra values are ignored
+        let values = series
+            .iter()
+            .flat_map(|values| values.iter().take(categories.len()))
+            .copied()
+            .collect::<Vec<f32>>();
+
+        if values.is_empty() {
+            return Err(PlotError::EmptyData);
+        } else if !values.iter().any(|v| v.is_finite()) {
+            return Err(PlotError::NoDisplayablePoint);
+        }
+
+        self.plotopt_bars(categories, series, options);
+        Ok(())
+    }
+
+    fn apply_bezier_option(data: &mut BezierData, option: &Opt) {
+        match option {
+            Opt::Color(col) => { data.color = *col; },
+
+            Opt::Size(si)=> {
+                data.size = *si;
+            },
+            Opt::LineStyle(style)=> { data.line_style = style.clone(); },
+
+            Opt::DashLength(length)=> { data.dash_length = length.max(1.0); },
+
+            Opt::Mech(mech)=> { data.mech = *mech; },
+
+            Opt::Label(label)=> { data.label = Some(label.clone()); },
+
+            Opt::Animate(animate) => { data.show_animation = *animate; }
+
+            Opt::MarkerStyle(_)=> { 
+                eprintln!("MarkerStyle is not a valid option for segments"); 
+            },
+
+            Opt::MarkerInnerPointColor(_)=> {  
+                eprintln!("MarkerInnerPointColor is not a valid option for segments"); 
+            },
+
+            Opt::Contour(_)=> { 
+                 println!("Contour is not a valid option for segments");
+            },
+            
+            Opt::NumPoints(_) => { 
+                eprintln!("NumPoints is not a valid option for segments"); 
+            },
+
+            Opt::MarkerColor(_) => { 
+                eprintln!("MarkerColor is not a valid option for segments"); 
+            },
+
+            Opt::MarkerSize(_) => { 
+                eprintln!("MarkerSize is not a valid option for segments"); 
+            },
+
+            Opt::MarkerColors(_) | Opt::MarkerSizes(_) | Opt::MarkerValues(_) => { 
+                eprintln!("{:?} is only a valid option for markers", option); 
+            },
+
+            Opt::FillColor(_) => { 
+                eprintln!("FillColor is not a valid option for functions"); 
+            },
+
+            Opt::Colormap(_) | Opt::ValueRange(..) | Opt::Interpolate(_) | Opt::Alpha(_) => { 
+                eprintln!("{:?} is only a valid option for heatmaps", option); 
+            },
+
+            Opt::Bins(_) | Opt::Density(_) | Opt::Cumulative(_) => { 
+                eprintln!("{:?} is only a valid option for histograms", option); 
+            },
+
+            Opt::BarLayout(_) | Opt::Horizontal(_) | Opt::BarWidth(_) => { 
+                eprintln!("{:?} is only a valid option for bar charts", option); 
+            },
+
+            Opt::XErr(_) | Opt::YErr(_) | Opt::CapWidth(_) | Opt::ErrorColor(_) | Opt::ErrorSize(_) => { 
+                eprintln!("{:?} is only a valid option for data points", option); 
+            },
+
+            Opt::Capacity(_) => { 
+                eprintln!("Capacity is only a valid option for segments and markers"); 
+            },
+
+            Opt::Draggable(_) => { 
+                eprintln!("Draggable is only a valid option for markers"); 
+            },
+
+            // read by Plot::secondary_option
+            Opt::SecondaryX(_) | Opt::SecondaryY(_) => {},
+
+            // _ => {},
+        }
     }
 
+
+    // Axes on which a group is read, given by the `Opt::SecondaryX` and `Opt::SecondaryY` options
+    fn secondary_option(options: &[Opt]) -> BVec2 {
+        let mut secondary = [false, false];
+
+        for option in options.iter() {
+            match option {
+                Opt::SecondaryX(on) => secondary[0] = *on,
+                Opt::SecondaryY(on) => secondary[1] = *on,
+                _ => {}
+            }
+        }
+
+        BVec2::new(secondary[0], secondary[1])
+    }
     
-    fn make_canvas(&self) -> Canvas {
+    pub(crate) fn make_canvas(&self) -> Canvas {
 
         let canvas = Canvas {
             position: self.canvas_position,
@@ -758,30 +2288,263 @@ impl Plot {
 
     }
 
+    /// Sets the bounds so that all the points in `data` are visible, with a 10% margin on each side.
+    /// The margin is computed in the scaled space of each axis, and points that cannot be displayed
+    /// on a given axis (e.g. negative values on a logarithmic axis) are ignored.
+    fn fit_bounds(&mut self, data: &[Vec2]) {
+        if self.polar {
+            // the pole stays at the center of the canvas
+            let r_max = data
+                .iter()
+                .map(|p| p.y.abs())
+                .filter(|r| r.is_finite())
+                .fold(0.0, f32::max);
+
+            self.set_polar_extent(r_max * 1.1);
+            return;
+        }
+
+        let scaled = data
+            .iter()
+            .map(|p| self.to_scaled(*p))
+            .filter(|p| p.is_finite())
+            .collect::<Vec<Vec2>>();
+
+        // the bounds are kept if none of the points can be displayed
+        if scaled.is_empty() {
+            return;
+        }
+
+        let (lo, up) = scaled.iter().fold(
+            (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY)),
+            |(lo, up), p| (lo.min(*p), up.max(*p)),
+        );
+
+        // a single point, or points on a horizontal or vertical line, have an empty range on one axis,
+        // in which case the margin is 10% of the coordinate, or 1 if the coordinate is zero
+        let margin = |lo: f32, up: f32| {
+            if up > lo {
+                (up - lo) * 0.1
+            } else if lo != 0.0 {
+                lo.abs() * 0.1
+            } else {
+                1.0
+            }
+        };
+        let margin = Vec2::new(margin(lo.x, up.x), margin(lo.y, up.y));
+
+        let lo = self.from_scaled(lo - margin);
+        let up = self.from_scaled(up + margin);
+
+        if let Err(error) = self.try_set_bounds(lo, up) {
+            eprintln!("the bounds could not be fitted to the data: {}", error);
+        }
+    }
+
+    /// Changes the scale of the axes. If the current bounds cannot be displayed with the new scale
+    /// (e.g. a negative lower bound on a logarithmic axis), the bounds of that axis are reset.
+    pub fn set_axis_scales(&mut self, x_axis_scale: AxisScale, y_axis_scale: AxisScale) {
+        let mut lo = self.bounds.lo;
+        let mut up = self.bounds.up;
+
+        if !x_axis_scale.is_valid(lo.x) || !x_axis_scale.is_valid(up.x) {
+            let (l, u) = x_axis_scale.default_range();
+            lo.x = l;
+            up.x = u;
+        }
+
+        if !y_axis_scale.is_valid(lo.y) || !y_axis_scale.is_valid(up.y) {
+            let (l, u) = y_axis_scale.default_range();
+            lo.y = l;
+            up.y = u;
+        }
+
+        self.x_axis_scale = x_axis_scale;
+        self.y_axis_scale = y_axis_scale;
+
+        self.set_bounds(lo, up);
+    }
+
+    /// Switches between cartesian and polar coordinates. In polar mode, the data is given as (θ, r) pairs,
+    /// explicit functions are interpreted as r = f(θ) with θ going from 0 to 2π, and the canvas is
+    /// centered on the pole with a radius of 1.2.
+    pub fn set_polar(&mut self, polar: bool) {
+        self.polar = polar;
+
+        if polar {
+            self.set_polar_extent(1.2);
+        } else {
+            self.set_bounds(Vec2::new(-0.2, -0.2), Vec2::new(1.2, 1.2));
+        }
+    }
+
+    // Centers the polar canvas on the pole such that the smallest of the two half axes is `r`,
+    // keeping circles round.
+    fn set_polar_extent(&mut self, r: f32) {
+        let r = if r > 0.0 { r } else { 1.2 };
+
+        let inner_size = self.canvas_size / (1.0 + self.outer_border);
+        let aspect = inner_size / inner_size.min_element();
+
+        if let Err(error) = self.try_set_bounds(-aspect * r, aspect * r) {
+            eprintln!("the polar bounds could not be set: {}", error);
+        }
+    }
+
+    /// Maps a point in plot coordinates to the scaled space of the axes. The axis scales are ignored
+    /// in polar mode.
+    pub fn to_scaled(&self, v: Vec2) -> Vec2 {
+        if self.polar {
+            return v;
+        }
+        Vec2::new(self.x_axis_scale.forward(v.x), self.y_axis_scale.forward(v.y))
+    }
+
+    /// Maps a point in the scaled space of the axes back to plot coordinates.
+    pub fn from_scaled(&self, v: Vec2) -> Vec2 {
+        if self.polar {
+            return v;
+        }
+        Vec2::new(self.x_axis_scale.inverse(v.x), self.y_axis_scale.inverse(v.y))
+    }
+
+    // Maps a data point to the cartesian coordinates of the canvas: (θ, r) pairs are converted
+    // to (x, y) in polar mode, otherwise the axis scales are applied.
+    pub(crate) fn to_view(&self, v: Vec2) -> Vec2 {
+        if self.polar {
+            Vec2::new(v.y * v.x.cos(), v.y * v.x.sin())
+        } else {
+            self.to_scaled(v)
+        }
+    }
+
+    pub(crate) fn view_to_plot(&self, v: Vec2) -> Vec2 {
+        if self.polar {
+            let theta = v.y.atan2(v.x);
+            let theta = if theta < 0.0 { theta + 2.0 * std::f32::consts::PI } else { theta };
+            Vec2::new(theta, v.length())
+        } else {
+            self.from_scaled(v)
+        }
+    }
+
+    /// Returns true if the point can be displayed with the current axis scales.
+    pub fn is_displayable(&self, v: Vec2) -> bool {
+        self.to_view(v).is_finite()
+    }
+
+    /// Bounds of the canvas in the scaled space of the axes.
+    pub(crate) fn scaled_bounds(&self) -> PlotCanvasBounds {
+        PlotCanvasBounds {
+            up: self.to_scaled(self.bounds.up),
+            lo: self.to_scaled(self.bounds.lo),
+        }
+    }
+
+    /// Offset of the ticks of each axis in the scaled space, see [`AxisScale::DateTime`].
+    pub(crate) fn tick_offset(&self) -> Vec2 {
+        if self.polar {
+            return Vec2::ZERO;
+        }
+        Vec2::new(
+            self.x_axis_scale.tick_offset(self.tick_period.x),
+            self.y_axis_scale.tick_offset(self.tick_period.y),
+        )
+    }
+
+    /// Placement of the grid lines, see the `ticks` field of `CanvasMaterial`: (0, tick offset) for an
+    /// axis with evenly spaced ticks, and (months, origin) for a date/time axis whose ticks are months
+    /// or years, see [`AxisScale::calendar_grid`].
+    pub(crate) fn grid_ticks(&self) -> Vec4 {
+        let offset = self.tick_offset();
+        let (x, y) = if self.polar {
+            (Vec2::ZERO, Vec2::ZERO)
+        } else {
+            (
+                self.x_axis_scale.calendar_grid(self.tick_period.x),
+                self.y_axis_scale.calendar_grid(self.tick_period.y),
+            )
+        };
+
+        let x = if x.x > 0.0 { x } else { Vec2::new(0.0, offset.x) };
+        let y = if y.x > 0.0 { y } else { Vec2::new(0.0, offset.y) };
+        Vec4::new(x.x, x.y, y.x, y.y)
+    }
+
     pub(crate) fn delta_axes(&self) -> Vec2 {
-        self.bounds.up - self.bounds.lo
+        let bounds = self.scaled_bounds();
+        bounds.up - bounds.lo
     }
 
-    pub(crate) fn zoom_axes(&mut self, direction: f32) {
+    /// Zooms around the mouse position along the given axes, leaving out the axes in `lock_zoom`.
+    /// Returns false if no axis was zoomed.
+    pub(crate) fn zoom_axes(&mut self, direction: f32, axes: BVec2) -> bool {
+        let axes = axes & !self.lock_zoom;
+        if !axes.any() {
+            return false;
+        }
+
         let percent_factor = 10.0;
 
         let multiplier = 1.0 + direction * percent_factor / 100.0;
 
-        self.bounds.up =
-            self.plot_coord_mouse_pos + (self.bounds.up - self.plot_coord_mouse_pos) * multiplier;
-        self.bounds.lo =
-            self.plot_coord_mouse_pos - (self.plot_coord_mouse_pos - self.bounds.lo) * multiplier;
+        let bounds = self.scaled_bounds();
+        let mouse_pos = self.to_view(self.plot_coord_mouse_pos);
+
+        let up = self.from_scaled(mouse_pos + (bounds.up - mouse_pos) * multiplier);
+        let lo = self.from_scaled(mouse_pos - (mouse_pos - bounds.lo) * multiplier);
 
-        self.zoom *= multiplier;
+        // the other axis keeps its exact bounds
+        self.bounds.up = Vec2::select(axes, up, self.bounds.up);
+        self.bounds.lo = Vec2::select(axes, lo, self.bounds.lo);
+        self.update_secondary_bounds(&bounds);
+        self.snap_date_time_tick_period();
+        self.point_index = None;
+
+        // the zoom value only follows zooms along both axes
+        if axes.all() {
+            self.zoom *= multiplier;
+        }
+
+        true
     }
 
-    pub(crate) fn move_axes(&mut self, mouse_delta: Vec2) {
+    /// Pans by `mouse_delta` pixels, leaving out the axes in `lock_pan`.
+    /// Returns false if no axis was moved.
+    pub(crate) fn move_axes(&mut self, mouse_delta: Vec2) -> bool {
+        let mouse_delta = Vec2::select(self.lock_pan, Vec2::ZERO, mouse_delta);
+        if mouse_delta == Vec2::ZERO {
+            return false;
+        }
+
         let mut axes = self.delta_axes();
         axes.x *= -1.0;
         let size = self.canvas_size / (1. + self.outer_border);
 
-        self.bounds.up += mouse_delta * axes / size;
-        self.bounds.lo += mouse_delta * axes / size;
+        let bounds = self.scaled_bounds();
+
+        let up = self.from_scaled(bounds.up + mouse_delta * axes / size);
+        let lo = self.from_scaled(bounds.lo + mouse_delta * axes / size);
+
+        self.bounds.up = Vec2::select(self.lock_pan, self.bounds.up, up);
+        self.bounds.lo = Vec2::select(self.lock_pan, self.bounds.lo, lo);
+        self.update_secondary_bounds(&bounds);
+        self.point_index = None;
+
+        true
+    }
+
+    // The tick period of a date/time axis follows the zoom level, switching to the next calendar
+    // interval (seconds, minutes, hours, days, months) as the range of the axis changes.
+    fn snap_date_time_tick_period(&mut self) {
+        let bounds = self.scaled_bounds();
+
+        if self.x_axis_scale.is_date_time() {
+            self.tick_period.x = self.x_axis_scale.tick_period(bounds.lo.x, bounds.up.x);
+        }
+        if self.y_axis_scale.is_date_time() {
+            self.tick_period.y = self.y_axis_scale.tick_period(bounds.lo.y, bounds.up.y);
+        }
     }
 
     // TODO: make a smarter tick period adjuster
@@ -789,6 +2552,18 @@ impl Plot {
         let max_num_ticks = 15.0;
         let min_num_ticks = 0.000001;
 
+        if self.polar {
+            // angular ticks between 5 and 90 degrees
+            use std::f32::consts::PI;
+            self.tick_period.x = self.tick_period.x.clamp(PI / 36.0, PI / 2.0);
+
+            self.tick_period.y = self.tick_period.y.clamp(
+                self.delta_axes().max_element() / max_num_ticks,
+                self.delta_axes().max_element() / min_num_ticks,
+            );
+            return;
+        }
+
         self.tick_period.x = self.tick_period.x.clamp(
             self.delta_axes().x / max_num_ticks,
             self.delta_axes().x / min_num_ticks,
@@ -796,8 +2571,27 @@ impl Plot {
 
         self.tick_period.y = self.tick_period.y.clamp(
             self.delta_axes().y / max_num_ticks,
-            self.delta_axes().x / min_num_ticks,
+            self.delta_axes().y / min_num_ticks,
         );
+
+        let secondary = self.secondary_scaled_bounds();
+        let secondary_delta = secondary.up - secondary.lo;
+        self.secondary_tick_period = self.secondary_tick_period.clamp(
+            secondary_delta / max_num_ticks,
+            secondary_delta / min_num_ticks,
+        );
+
+        self.fix_categorical_tick_period();
+    }
+
+    // On a categorical axis, there is a tick at the center of each category
+    fn fix_categorical_tick_period(&mut self) {
+        if !self.x_categories.is_empty() {
+            self.tick_period.x = 1.0;
+        }
+        if !self.y_categories.is_empty() {
+            self.tick_period.y = 1.0;
+        }
     }
 
     /// Override the default plot bounds: x axis goes from bounds.lo.x to bounds.up.x. 
@@ -806,113 +2600,437 @@ impl Plot {
     ///
     /// # Panics
     ///
-    /// Panics if `lo.x >= up.x` or `lo.y >= up.y`.
+    /// Panics if `lo.x >= up.x` or `lo.y >= up.y`, or if a bound cannot be displayed with the
+    /// current axis scale (e.g. `lo.x <= 0` with a logarithmic x axis). See [`Plot::try_set_bounds`]
+    /// for a version that does not panic.
     pub fn set_bounds(&mut self, lo: Vec2, up: Vec2) {
+        if let Err(error) = self.try_set_bounds(lo, up) {
+            panic!("when using plot.set_bounds(), {}", error);
+        }
+    }
 
-        if lo.x >= up.x {
-            panic!("when using plot.set_bounds(), lo.x must be strictly less than up.x");
-        } else if lo.y >= up.y {
-            panic!("when using plot.set_bounds(), lo.y must be strictly less than up.y");
-        };
+    /// Same as [`Plot::set_bounds`], but returns an error and keeps the current bounds if a bound is
+    /// NaN, infinite or invalid for the axis scales, or if `lo` is not strictly less than `up`.
+    pub fn try_set_bounds(&mut self, lo: Vec2, up: Vec2) -> Result<(), PlotError> {
+        if !self.is_displayable(lo) || !self.is_displayable(up) {
+            return Err(PlotError::InvalidBounds { lo, up });
+        } else if lo.x >= up.x || lo.y >= up.y {
+            return Err(PlotError::InvertedBounds { lo, up });
+        }
 
         self.bounds = PlotCanvasBounds {
             lo,
             up,
         };
 
-        let delta = up - lo;
-        let exact_tick = delta / 10.0;
+        let scaled = self.scaled_bounds();
 
-        // find order of magnitude of dx
-        let order_x = exact_tick.x.log10().floor();
-        let mag_x = 10_f32.powf(order_x);
+        if self.polar {
+            // In polar mode, tick_period.x is the angle between two radial lines and
+            // tick_period.y is the distance between two rings
+            let r_max = lo.abs().max(up.abs()).max_element();
+            self.tick_period = Vec2::new(std::f32::consts::PI / 6.0, nice_tick_period(r_max / 5.0));
+        } else {
+            let tick_x = self.x_axis_scale.tick_period(scaled.lo.x, scaled.up.x);
+            let tick_y = self.y_axis_scale.tick_period(scaled.lo.y, scaled.up.y);
 
+            self.tick_period = Vec2::new(tick_x, tick_y);
+            self.fix_categorical_tick_period();
+        }
 
-        let p1x = mag_x * 1.0;
-        let p2x = mag_x * 2.0;
-        let p5x = mag_x * 5.0;
+        self.compute_zeros();
 
-        let psx = [p1x, p2x, p5x];
+        // the data points have moved on the canvas
+        self.point_index = None;
 
+        Ok(())
+    }
 
-        let vx = vec! [(p1x-exact_tick.x).abs() , (p2x-exact_tick.x).abs(), (p5x-exact_tick.x).abs()];
-        
-        use std::cmp::Ordering;
-        let min_x_index = vx.iter()
-            .enumerate()
-            .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
-            .map(|(index, _)| index);
+    /// Shows a secondary x axis at the top of the canvas, ranging from `lo` to `up`. Groups plotted with
+    /// `Opt::SecondaryX(true)` are read on this axis. The secondary axis has the same scale as the primary
+    /// x axis, and follows it when panning and zooming.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lo >= up` or if a bound cannot be displayed with the x axis scale. See
+    /// [`Plot::try_set_secondary_x_bounds`] for a version that does not panic.
+    pub fn set_secondary_x_bounds(&mut self, lo: f32, up: f32) {
+        if let Err(error) = self.try_set_secondary_x_bounds(lo, up) {
+            panic!("when using plot.set_secondary_x_bounds(), {}", error);
+        }
+    }
 
-        let tick_x = psx[min_x_index.unwrap()]; 
+    /// Same as [`Plot::set_secondary_x_bounds`], but returns an error and keeps the current secondary
+    /// axes if a bound is NaN, infinite or invalid for the x axis scale, or if `lo` is not strictly
+    /// less than `up`.
+    pub fn try_set_secondary_x_bounds(&mut self, lo: f32, up: f32) -> Result<(), PlotError> {
+        let (lo_y, up_y) = (self.secondary_bounds.lo.y, self.secondary_bounds.up.y);
+        let (lo_2d, up_2d) = (Vec2::new(lo, lo_y), Vec2::new(up, up_y));
+
+        if !self.x_axis_scale.is_valid(lo) || !self.x_axis_scale.is_valid(up) {
+            return Err(PlotError::InvalidBounds { lo: lo_2d, up: up_2d });
+        } else if lo >= up {
+            return Err(PlotError::InvertedBounds { lo: lo_2d, up: up_2d });
+        }
 
+        self.secondary_bounds.lo.x = lo;
+        self.secondary_bounds.up.x = up;
+        self.secondary_tick_period.x = self
+            .x_axis_scale
+            .tick_period(self.x_axis_scale.forward(lo), self.x_axis_scale.forward(up));
 
-        let order_y = exact_tick.y.log10().floor();
-        let mag_y = 10_f32.powf(order_y);
+        let [_, y] = <[bool; 2]>::from(self.secondary_axes);
+        self.secondary_axes = BVec2::new(true, y);
 
+        Ok(())
+    }
+
+    /// Shows a secondary y axis on the right of the canvas, ranging from `lo` to `up`. Groups plotted with
+    /// `Opt::SecondaryY(true)` are read on this axis. The secondary axis has the same scale as the primary
+    /// y axis, and follows it when panning and zooming.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lo >= up` or if a bound cannot be displayed with the y axis scale. See
+    /// [`Plot::try_set_secondary_y_bounds`] for a version that does not panic.
+    pub fn set_secondary_y_bounds(&mut self, lo: f32, up: f32) {
+        if let Err(error) = self.try_set_secondary_y_bounds(lo, up) {
+            panic!("when using plot.set_secondary_y_bounds(), {}", error);
+        }
+    }
 
-        let p1y = mag_y * 1.0;
-        let p2y = mag_y * 2.0;
-        let p5y = mag_y * 5.0;
+    /// Same as [`Plot::set_secondary_y_bounds`], but returns an error and keeps the current secondary
+    /// axes if a bound is NaN, infinite or invalid for the y axis scale, or if `lo` is not strictly
+    /// less than `up`.
+    pub fn try_set_secondary_y_bounds(&mut self, lo: f32, up: f32) -> Result<(), PlotError> {
+        let (lo_x, up_x) = (self.secondary_bounds.lo.x, self.secondary_bounds.up.x);
+        let (lo_2d, up_2d) = (Vec2::new(lo_x, lo), Vec2::new(up_x, up));
+
+        if !self.y_axis_scale.is_valid(lo) || !self.y_axis_scale.is_valid(up) {
+            return Err(PlotError::InvalidBounds { lo: lo_2d, up: up_2d });
+        } else if lo >= up {
+            return Err(PlotError::InvertedBounds { lo: lo_2d, up: up_2d });
+        }
 
-        let psy = [p1y, p2y, p5y];
+        self.secondary_bounds.lo.y = lo;
+        self.secondary_bounds.up.y = up;
+        self.secondary_tick_period.y = self
+            .y_axis_scale
+            .tick_period(self.y_axis_scale.forward(lo), self.y_axis_scale.forward(up));
 
+        let [x, _] = <[bool; 2]>::from(self.secondary_axes);
+        self.secondary_axes = BVec2::new(x, true);
 
-        let vy = vec! [(p1y-exact_tick.y).abs() , (p2y-exact_tick.y).abs(), (p5y-exact_tick.y).abs()];
-        
-        let min_y_index = vy.iter()
-            .enumerate()
-            .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
-            .map(|(index, _)| index);
+        Ok(())
+    }
 
-        let tick_y = psy[min_y_index.unwrap()]; 
+    /// Bounds of the secondary axes in the scaled space of the axes.
+    pub(crate) fn secondary_scaled_bounds(&self) -> PlotCanvasBounds {
+        PlotCanvasBounds {
+            up: self.to_scaled(self.secondary_bounds.up),
+            lo: self.to_scaled(self.secondary_bounds.lo),
+        }
+    }
 
+    // Map from the secondary axes to the primary axes for a group read on the `secondary` axes.
+    // The secondary axes that are not shown, and all of them in polar mode, are ignored.
+    pub(crate) fn secondary_map(&self, secondary: BVec2) -> SecondaryMap {
+        let primary = self.scaled_bounds();
+        let other = self.secondary_scaled_bounds();
+
+        SecondaryMap {
+            x_axis_scale: self.x_axis_scale.clone(),
+            y_axis_scale: self.y_axis_scale.clone(),
+            primary: (primary.lo, primary.up),
+            secondary: (other.lo, other.up),
+            active: if self.polar {
+                BVec2::new(false, false)
+            } else {
+                secondary & self.secondary_axes
+            },
+        }
+    }
 
-  
+    // Moves the secondary axes along with the primary axes, given the scaled bounds of the primary
+    // axes before they were panned or zoomed, such that the map between them stays the same.
+    pub(crate) fn update_secondary_bounds(&mut self, previous: &PlotCanvasBounds) {
+        let primary = self.scaled_bounds();
+        let secondary = self.secondary_scaled_bounds();
 
-        self.tick_period = Vec2::new(tick_x, tick_y);
+        let ratio = (secondary.up - secondary.lo) / (previous.up - previous.lo);
 
-        self.compute_zeros();
-    }
+        let lo = secondary.lo + (primary.lo - previous.lo) * ratio;
+        let up = secondary.up + (primary.up - previous.up) * ratio;
 
+        self.secondary_bounds.lo = self.from_scaled(lo);
+        self.secondary_bounds.up = self.from_scaled(up);
+    }
 
     pub(crate) fn compute_zeros(&mut self) {
         let lo_world = -self.canvas_size / 2.0 / (1.0 + self.outer_border);
 
-        let v = Vec2::new(
-            self.bounds.lo.x * self.canvas_size.x
-                / (1.0 + self.outer_border.x)
-                / (self.bounds.up.x - self.bounds.lo.x),
-            self.bounds.lo.y * self.canvas_size.y
-                / (1.0 + self.outer_border.y)
-                / (self.bounds.up.y - self.bounds.lo.y),
-        );
+        let bounds = self.scaled_bounds();
+
+        let v = bounds.lo * self.canvas_size
+            / (1.0 + self.outer_border)
+            / (bounds.up - bounds.lo);
 
         self.zero_world = lo_world - v ;
     }
 
     pub(crate) fn compute_bounds_world(&self) -> PlotCanvasBounds {
 
-        let lo = self.to_local(self.bounds.lo);
-        let up = self.to_local(self.bounds.up);
+        let bounds = self.scaled_bounds();
+
+        let lo = self.view_to_local(bounds.lo);
+        let up = self.view_to_local(bounds.up);
 
         PlotCanvasBounds { up, lo }
     }
 
     /// Convert a point in plot coordinates to a point in world coordinates modulo the canvas position
     pub fn to_local(&self, v: Vec2) -> Vec2 {
+        self.view_to_local(self.to_view(v))
+    }
 
-                self.zero_world
-                    + v * self.canvas_size
-                        / (self.bounds.up - self.bounds.lo)
-                        / (1.0 + self.outer_border.x)
-
+    // Convert a point in the cartesian coordinates of the canvas to world coordinates modulo the canvas position
+    pub(crate) fn view_to_local(&self, v: Vec2) -> Vec2 {
+        let bounds = self.scaled_bounds();
 
+        self.zero_world
+            + v * self.canvas_size
+                / (bounds.up - bounds.lo)
+                / (1.0 + self.outer_border)
     }
 
     /// Convert a point in world coordinates to a point in the graph coordinates.
     pub fn world_to_plot(&self, y: Vec2) -> Vec2 {
-        (y - self.zero_world - self.canvas_position) * (self.bounds.up - self.bounds.lo)
-            / self.canvas_size
-            * (1.0 + self.outer_border) 
+        let bounds = self.scaled_bounds();
+
+        self.view_to_plot(
+            (y - self.zero_world - self.canvas_position) * (bounds.up - bounds.lo)
+                / self.canvas_size
+                * (1.0 + self.outer_border),
+        )
+    }
+
+    /// The data point or point on a curve closest to `world_position`, within `radius` pixels.
+    /// Markers and points of regular plots take precedence over the curves going through them.
+    /// Hidden groups, elements outside of the canvas and functions animated with `show_animation`
+    /// are ignored. The elements are looked up in a spatial index, which is built on the first
+    /// query after the plot is respawned.
+    pub fn nearest_data_element(&mut self, world_position: Vec2, radius: f32) -> Option<DataElement> {
+        self.update_point_index();
+
+        let local = world_position - self.canvas_position;
+        self.point_index.as_ref()?.nearest_element(self, local, radius)
+    }
+
+    // Builds the spatial index of the data if it was dropped, in which case true is returned
+    pub(crate) fn update_point_index(&mut self) -> bool {
+        if self.point_index.is_some() {
+            return false;
+        }
+
+        self.point_index = Some(PointIndex::new(self));
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::{asset::AssetPlugin, ecs::event::Events};
+
+    fn num_groups(plot: &Plot) -> usize {
+        plot.data.segment_groups.len() + plot.data.marker_groups.len()
+    }
+
+    #[test]
+    fn empty_data_is_rejected() {
+        let mut plot = Plot::default();
+        let empty: Vec<Vec2> = Vec::new();
+
+        assert_eq!(plot.try_plot(empty.clone()), Err(PlotError::EmptyData));
+        assert_eq!(plot.try_plotm(empty.clone()), Err(PlotError::EmptyData));
+        assert_eq!(plot.try_plotopt(empty, vec![]), Err(PlotError::EmptyData));
+        assert_eq!(num_groups(&plot), 0);
+    }
+
+    #[test]
+    fn data_without_displayable_point_is_rejected() {
+        let mut plot = Plot::default();
+        let nans = vec![Vec2::new(f32::NAN, 1.0), Vec2::new(0.5, f32::NAN)];
+        assert_eq!(plot.try_plot(nans), Err(PlotError::NoDisplayablePoint));
+
+        plot.set_axis_scales(AxisScale::Log10, AxisScale::Log10);
+        let negatives = vec![Vec2::new(-1.0, -2.0), Vec2::new(-3.0, -4.0)];
+        assert_eq!(plot.try_plotm(negatives), Err(PlotError::NoDisplayablePoint));
+        assert_eq!(num_groups(&plot), 0);
+
+        assert_eq!(plot.try_plot(vec![Vec2::new(1.0, 2.0)]), Ok(()));
+        assert_eq!(num_groups(&plot), 1);
+    }
+
+    #[test]
+    fn invalid_bounds_are_rejected_and_kept() {
+        let mut plot = Plot::default();
+        let (lo, up) = (plot.bounds.lo, plot.bounds.up);
+
+        let inverted = plot.try_set_bounds(Vec2::new(1.0, 0.0), Vec2::new(0.0, 1.0));
+        assert!(matches!(inverted, Err(PlotError::InvertedBounds { .. })));
+
+        let nan = plot.try_set_bounds(Vec2::new(f32::NAN, 0.0), Vec2::ONE);
+        assert!(matches!(nan, Err(PlotError::InvalidBounds { .. })));
+
+        assert_eq!((plot.bounds.lo, plot.bounds.up), (lo, up));
+    }
+
+    #[test]
+    fn invalid_secondary_bounds_are_rejected_and_kept() {
+        let mut plot = Plot::default();
+
+        let inverted = plot.try_set_secondary_x_bounds(2.0, 1.0);
+        assert!(matches!(inverted, Err(PlotError::InvertedBounds { .. })));
+
+        let nan = plot.try_set_secondary_y_bounds(0.0, f32::NAN);
+        assert!(matches!(nan, Err(PlotError::InvalidBounds { .. })));
+
+        assert!(!plot.secondary_axes.any());
+
+        assert_eq!(plot.try_set_secondary_y_bounds(0.0, 10.0), Ok(()));
+        assert_eq!(<[bool; 2]>::from(plot.secondary_axes), [false, true]);
+    }
+
+    #[test]
+    fn heatmap_grids_of_the_wrong_size_are_skipped() {
+        let mut plot = Plot::default();
+        let (lo, up) = (Vec2::ZERO, Vec2::ONE);
+
+        let mismatch = plot.try_heatmap_grid(vec![1.0; 5], 2, 3, lo, up, vec![]);
+        assert_eq!(mismatch, Err(PlotError::LengthMismatch { expected: 6, found: 5 }));
+
+        let inverted = plot.try_heatmap_grid(vec![1.0; 6], 2, 3, up, lo, vec![]);
+        assert!(matches!(inverted, Err(PlotError::InvertedBounds { .. })));
+
+        // heatmap_grid skips the grid with a warning instead of panicking
+        plot.heatmap_grid(vec![1.0; 5], 2, 3, lo, up, vec![]);
+        assert!(plot.data.heatmap_groups.is_empty());
+
+        assert_eq!(plot.try_heatmap_grid(vec![1.0; 6], 2, 3, lo, up, vec![]), Ok(()));
+        assert_eq!(plot.data.heatmap_groups.len(), 1);
+    }
+
+    #[test]
+    fn histograms_and_bars_need_finite_values() {
+        let mut plot = Plot::default();
+
+        assert_eq!(plot.try_plotopt_hist(Vec::<f32>::new(), vec![]), Err(PlotError::EmptyData));
+        assert_eq!(
+            plot.try_plotopt_hist(vec![f32::NAN, f32::INFINITY], vec![]),
+            Err(PlotError::NoDisplayablePoint)
+        );
+
+        let categories = vec!["a", "b"];
+        assert_eq!(
+            plot.try_plotopt_bars(categories.clone(), vec![], vec![]),
+            Err(PlotError::EmptyData)
+        );
+        assert_eq!(
+            plot.try_plotopt_bars(categories.clone(), vec![vec![f32::NAN, f32::NAN, 1.0]], vec![]),
+            Err(PlotError::NoDisplayablePoint)
+        );
+        assert!(plot.data.bar_groups.is_empty());
+
+        assert_eq!(plot.try_plotopt_bars(categories, vec![vec![1.0, 2.0]], vec![]), Ok(()));
+        assert_eq!(plot.try_plotopt_hist(vec![1.0, 2.0, 2.5], vec![]), Ok(()));
+        assert_eq!(plot.data.bar_groups.len(), 2);
+    }
+
+    #[test]
+    fn error_bars_need_one_error_per_point() {
+        let mut plot = Plot::default();
+        let points = vec![Vec2::new(0.2, 0.3), Vec2::new(0.6, 0.9)];
+
+        let missing = plot.try_plot_errorbars(points.clone(), ErrorBars::Symmetric(vec![0.1]), vec![]);
+        assert_eq!(missing, Err(PlotError::LengthMismatch { expected: 2, found: 1 }));
+
+        let x_errors = vec![Opt::XErr(ErrorBars::Symmetric(vec![0.1; 3]))];
+        let extra = plot.try_plot_errorbars(points.clone(), ErrorBars::Symmetric(vec![0.1; 2]), x_errors);
+        assert_eq!(extra, Err(PlotError::LengthMismatch { expected: 2, found: 3 }));
+        assert!(plot.data.errorbar_groups.is_empty());
+
+        assert_eq!(plot.try_plot_errorbars(points, ErrorBars::Symmetric(vec![0.1; 2]), vec![]), Ok(()));
+        assert_eq!(plot.data.errorbar_groups.len(), 1);
+    }
+
+    #[test]
+    fn point_index_is_dropped_when_the_bounds_or_the_data_change() {
+        let mut plot = Plot::default();
+        plot.plot(vec![Vec2::new(0.2, 0.3), Vec2::new(0.6, 0.9)]);
+
+        assert!(plot.update_point_index());
+        plot.set_bounds(Vec2::new(0.0, 0.0), Vec2::new(2.0, 2.0));
+        assert!(plot.point_index.is_none());
+
+        plot.update_point_index();
+        assert!(plot.move_axes(Vec2::new(10.0, 0.0)));
+        assert!(plot.point_index.is_none());
+
+        plot.update_point_index();
+        assert!(plot.zoom_axes(1.0, BVec2::new(true, true)));
+        assert!(plot.point_index.is_none());
+
+        plot.update_point_index();
+        plot.push_segment_points(0, &[Vec2::new(1.0, 1.0)]);
+        assert!(plot.point_index.is_none());
+    }
+
+    #[test]
+    fn parametric_curves_need_two_samples_over_a_non_empty_range() {
+        let mut plot = Plot::default();
+        let circle = |t: f32, _: f32| Vec2::new(t.cos(), t.sin());
+
+        let one_point = plot.try_plot_parametric(circle, (0.0, 1.0), 1, vec![]);
+        assert!(matches!(one_point, Err(PlotError::InvalidSampling { .. })));
+
+        let one_point_option = plot.try_plot_parametric(circle, (0.0, 1.0), 100, vec![Opt::NumPoints(1)]);
+        assert!(matches!(one_point_option, Err(PlotError::InvalidSampling { .. })));
+
+        let empty_range = plot.try_plot_parametric(circle, (1.0, 1.0), 100, vec![]);
+        assert!(matches!(empty_range, Err(PlotError::InvalidSampling { .. })));
+        assert!(plot.data.bezier_groups.is_empty());
+
+        assert_eq!(plot.try_plot_parametric(circle, (0.0, 1.0), 100, vec![]), Ok(()));
+        assert_eq!(plot.data.bezier_groups.len(), 1);
+    }
+
+    #[test]
+    fn only_the_dummy_entity_of_a_plot_is_despawned() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugin(AssetPlugin)
+            .add_asset::<Plot>()
+            .add_event::<SpawnGraphEvent>()
+            .add_system(do_spawn_plot);
+
+        let plot = Plot::default();
+        let canvas = plot.make_canvas();
+        let plot_handle = app.world.resource_mut::<Assets<Plot>>().add(plot);
+
+        let canvas_entity = app.world.spawn().insert(canvas).insert(plot_handle.clone()).id();
+        let element_entity = app.world.spawn().insert(PlotElement).insert(plot_handle.clone()).id();
+        let dummy_entity = app.world.spawn().insert(plot_handle.clone()).id();
+
+        app.update();
+
+        assert!(app.world.get_entity(canvas_entity).is_some());
+        assert!(app.world.get_entity(element_entity).is_some());
+        assert!(app.world.get_entity(dummy_entity).is_none());
+
+        let events = app.world.resource::<Events<SpawnGraphEvent>>();
+        let spawned = events.get_reader().iter(events).map(|event| event.canvas_entity).collect::<Vec<_>>();
+        assert_eq!(spawned, vec![None]);
+        assert!(!app.world.resource::<Assets<Plot>>().get(&plot_handle).unwrap().do_spawn_plot);
     }
 }
@@ -6,7 +6,7 @@ use bevy::{
     prelude::*,
     reflect::TypeUuid,
     render::{
-        mesh::{Indices, MeshVertexAttribute, MeshVertexBufferLayout},
+        mesh::{Indices, MeshVertexAttribute, MeshVertexBufferLayout, VertexAttributeValues},
         render_asset::RenderAssets,
         render_component::{ComponentUniforms, DynamicUniformIndex, UniformComponentPlugin},
         render_phase::{
@@ -25,6 +25,8 @@ use bevy::{
     },
 };
 
+use std::collections::VecDeque;
+
 use crate::plot::*;
 use crate::util::*;
 
@@ -51,167 +53,435 @@ pub(crate) fn segments_setup(
     }
 }
 
-// Compute derivatives at each point
-fn make_df(ys: &Vec<Vec2>) -> (Vec<Vec2>, Vec<Vec2>) {
-    let df0 = (ys[1].y - ys[0].y) / (ys[1].x - ys[0].x);
-    let mut dfs = vec![df0];
-    for i in 1..ys.len() - 1 {
-        let y_m1 = ys[i - 1];
-        // let x0 = ys[i];
-        let y1 = ys[i + 1];
-        let dfi = (y1.y - y_m1.y) / (y1.x - y_m1.x);
-
-        dfs.push(dfi);
+fn plot_segments(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    plot: &mut Plot,
+    plot_handle: &Handle<Plot>,
+) {
+    let data = plot.data.clone();
+    plot.compute_zeros();
+
+    for k in 0..data.segment_groups.len() {
+        spawn_segment_group(commands, meshes, plot, plot_handle, k);
     }
 
-    // for the first and last points, we need to extrapolate the first derivative using the second derivative
-    dfs[0] = dfs[1] - (ys[1].x - ys[0].x) * (dfs[2] - dfs[1]) / (ys[2].x - ys[1].x);
+    for error_bars in data.errorbar_groups.iter().filter(|group| !group.hidden) {
+        let pairs = make_error_bars(plot, error_bars);
+
+        if let Some(mesh) = make_segment_mesh(&pairs) {
+            spawn_segment_mesh(
+                commands,
+                meshes,
+                plot,
+                plot_handle,
+                mesh,
+                SegmentUniform {
+                    mech: 0.0,
+                    segment_thickness: error_bars.size,
+                    hole_size: 1.0,
+                    zoom: 1.0,
+                    color: col_to_vec4(error_bars.color),
+                    inner_canvas_size_in_pixels: plot.canvas_size / (1.0 + plot.outer_border),
+                    canvas_position: plot.canvas_position,
+                    style: LineStyle::Solid.to_int32(),
+                    dash_length: 10.0,
+                },
+            );
+        }
+    }
+}
+
+/// Spawns the mesh of the `k`th segment group, unless it is hidden. The entity is spawned even if the
+/// group has less than two points, invisible, so that streamed points can be appended to it later.
+pub(crate) fn spawn_segment_group(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    plot: &Plot,
+    plot_handle: &Handle<Plot>,
+    k: usize,
+) {
+    let segment_plot = match plot.data.segment_groups.get(k) {
+        Some(segment_plot) if !segment_plot.hidden => segment_plot,
+        _ => return,
+    };
+
+    let (quads, tail) = SegmentTail::build(plot, segment_plot);
+    let is_visible = !quads.is_empty();
+
+    let entity = spawn_segment_mesh(
+        commands,
+        meshes,
+        plot,
+        plot_handle,
+        quads.into_mesh(),
+        SegmentUniform {
+            mech: if segment_plot.mech { 1.0 } else { 0.0 },
+            segment_thickness: segment_plot.size,
+            hole_size: 1.0,
+            zoom: 1.0,
+            color: col_to_vec4(segment_plot.color),
+            inner_canvas_size_in_pixels: plot.canvas_size / (1.0 + plot.outer_border),
+            canvas_position: plot.canvas_position,
+            style: segment_plot.line_style.to_int32(),
+            dash_length: segment_plot.dash_length,
+        },
+    );
+
+    commands
+        .entity(entity)
+        .insert(Visibility { is_visible })
+        .insert(SegmentGroup(k))
+        .insert(tail);
+}
 
-    let la = ys.len() - 1;
-    let df_final = dfs[la - 1]
-        - (ys[la - 1].x - ys[la].x) * (dfs[la - 2] - dfs[la - 1]) / (ys[la - 2].x - ys[la - 1].x);
+// Error bars and their caps as disjoint segments, in world coordinates modulo the canvas position.
+// The caps are computed in pixels so that their width does not change when zooming.
+fn make_error_bars(plot: &Plot, error_bars: &ErrorBarData) -> Vec<(Vec2, Vec2)> {
+    let bounds_world = plot.compute_bounds_world();
+    let half_cap = error_bars.cap_width / 2.0;
+    let secondary_map = plot.secondary_map(error_bars.secondary);
 
-    dfs.push(df_final);
+    let mut pairs = Vec::new();
 
-    // derivatives
-    let dfs_vec2 = dfs
-        .iter()
-        .map(|q| Vec2::new(1.0, *q).normalize())
-        .collect::<Vec<Vec2>>();
+    for (k, point) in error_bars.data.iter().enumerate() {
+        if !plot.is_displayable(secondary_map.to_primary(*point)) {
+            continue;
+        }
+        let center = plot.to_local(secondary_map.to_primary(*point));
+
+        // (end of the bar, whether a cap is drawn, direction of the bar)
+        let mut ends = Vec::new();
+
+        if let Some((left, right)) = error_bars.x_errors.get(k) {
+            ends.push((Vec2::new(point.x - left, point.y), Vec2::X, bounds_world.lo.x));
+            ends.push((Vec2::new(point.x + right, point.y), Vec2::X, bounds_world.up.x));
+        }
+
+        if let Some((below, above)) = error_bars.y_errors.get(k) {
+            ends.push((Vec2::new(point.x, point.y - below), Vec2::Y, bounds_world.lo.y));
+            ends.push((Vec2::new(point.x, point.y + above), Vec2::Y, bounds_world.up.y));
+        }
 
-    // normals
-    let ns_vec2 = dfs
-        .iter()
-        .map(|q| Vec2::new(*q, -1.0).normalize())
-        .collect::<Vec<Vec2>>();
+        for (end, direction, canvas_edge) in ends {
+            let end_world = plot.to_local(secondary_map.to_primary(end));
 
-    return (dfs_vec2, ns_vec2);
+            if end_world.is_finite() {
+                if end_world != center {
+                    pairs.push((center, end_world));
+                }
+
+                if half_cap > 0.0 {
+                    let cap = Vec2::new(direction.y, direction.x) * half_cap;
+                    pairs.push((end_world - cap, end_world + cap));
+                }
+            } else {
+                // the end cannot be displayed (e.g. a negative value on a logarithmic axis),
+                // so the bar extends to the edge of the canvas without a cap
+                let edge = center * (Vec2::ONE - direction) + direction * canvas_edge;
+                pairs.push((center, edge));
+            }
+        }
+    }
+
+    pairs
 }
 
-fn plot_segments(
+// Spawns a segment mesh whose positions are in world coordinates modulo the canvas position
+fn spawn_segment_mesh(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
-    plot: &mut Plot,
+    plot: &Plot,
     plot_handle: &Handle<Plot>,
-) {
-    let data = plot.data.clone();
-    plot.compute_zeros();
+    mesh: Mesh,
+    uniform: SegmentUniform,
+) -> Entity {
+    commands
+        .spawn_bundle((
+            SegmentMesh2d::default(),
+            Mesh2dHandle(meshes.add(mesh)),
+            GlobalTransform::default(),
+            Transform::from_translation(plot.canvas_position.extend(1.11)),
+            Visibility::default(),
+            ComputedVisibility::default(),
+        ))
+        .insert(plot_handle.clone())
+        .insert(PlotElement)
+        .insert(uniform)
+        .id()
+}
 
-    for segment_plot in data.segment_groups.iter() {
-        let ys = segment_plot.data.clone();
+/// Index of a segment group in `data.segment_groups`, used to redraw the group in place when
+/// points are streamed to it
+#[derive(Component)]
+pub(crate) struct SegmentGroup(pub usize);
 
-        // TODO: is this still needed?
-        // derivatives and normals
-        let (_dfs, _ns) = make_df(&ys);
+/// What a data point of a segment group added to the mesh of the group
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TailPoint {
+    /// The point cannot be displayed with the current axis scales
+    Skipped,
 
-        let num_pts = ys.len();
+    /// The point is displayed but no segment ends on it: it is the first displayed point of the
+    /// group, or it lies at the same position as the previous one
+    Start,
 
-        let ys_world = ys.iter().map(|y| plot.to_local(*y)).collect::<Vec<Vec2>>();
+    /// The quad of the segment from the previous displayed point ends on this point
+    Quad,
+}
 
-        let mut mesh0 = Vec::new();
-        let mut mesh_attr_uvs = Vec::new();
-        let mut inds = Vec::new();
-        let mut ends = Vec::new();
-        let mut mesh_attr_controls: Vec<[f32; 4]> = Vec::new();
+/// The data points of a segment group as they were drawn, so that the points streamed with
+/// [`Plot::push_segment_points`] only add their own quads to the mesh of the group, and the points
+/// dropped because of the `capacity` of the group only remove theirs.
+#[derive(Component, Default)]
+pub(crate) struct SegmentTail {
+    points: VecDeque<TailPoint>,
 
-        let line_width = 5.0;
-        for k in 0..num_pts - 1 {
-            let y0 = ys_world[k];
-            let y1 = ys_world[k + 1];
-
-            let dy = (y1 - y0).normalize();
-            let n = Vec2::new(-dy.y, dy.x);
-
-            // // short segments
-            // let mut p0 = y0 + n * line_width;
-            // let mut p1 = y0 - n * line_width;
-            // let mut p2 = y1 + n * line_width;
-            // let mut p3 = y1 - n * line_width;
-
-            // if segment_plot.mech {
-            //     p0 = y0 + n * line_width - dy * line_width * 1.0;
-            //     p1 = y0 - n * line_width - dy * line_width * 1.0;
-            //     p2 = y1 + n * line_width + dy * line_width * 1.0;
-            //     p3 = y1 - n * line_width + dy * line_width * 1.0;
-            // }
-
-            // overlapping segments
-            let p0 = y0 + n * line_width - dy * line_width * 1.0;
-            let p1 = y0 - n * line_width - dy * line_width * 1.0;
-            let p2 = y1 + n * line_width + dy * line_width * 1.0;
-            let p3 = y1 - n * line_width + dy * line_width * 1.0;
-
-            mesh0.push(p0);
-            mesh0.push(p1);
-            mesh0.push(p2);
-            mesh0.push(p3);
-
-            ends.push([y0.x, y0.y, y1.x, y1.y]);
-            ends.push([y0.x, y0.y, y1.x, y1.y]);
-            ends.push([y0.x, y0.y, y1.x, y1.y]);
-            ends.push([y0.x, y0.y, y1.x, y1.y]);
-
-            mesh_attr_controls.push([p0.x, p0.y, p1.x, p1.y]);
-            mesh_attr_controls.push([p0.x, p0.y, p1.x, p1.y]);
-            mesh_attr_controls.push([p0.x, p0.y, p1.x, p1.y]);
-            mesh_attr_controls.push([p0.x, p0.y, p1.x, p1.y]);
-
-            mesh_attr_uvs.push([p0.x, p0.y]);
-            mesh_attr_uvs.push([p1.x, p1.y]);
-            mesh_attr_uvs.push([p2.x, p2.y]);
-            mesh_attr_uvs.push([p3.x, p3.y]);
-
-            let ki = k * 4;
-
-            inds.push(ki as u32);
-            inds.push((ki + 1) as u32);
-            inds.push((ki + 2) as u32);
-
-            inds.push((ki + 3) as u32);
-            inds.push((ki + 2) as u32);
-            inds.push((ki + 1) as u32);
+    /// Last displayed point, in world coordinates modulo the canvas position
+    last: Option<Vec2>,
+
+    /// Arc length at the last displayed point, which keeps the dash pattern continuous
+    arc_length: f32,
+}
+
+impl SegmentTail {
+    /// Quads of all the points of a segment group
+    pub fn build(plot: &Plot, segment_plot: &SegmentData) -> (SegmentQuads, Self) {
+        let mut tail = SegmentTail::default();
+        let quads = tail.append(plot, segment_plot, &segment_plot.data);
+        (quads, tail)
+    }
+
+    /// True if the points of `segment_plot` are the drawn ones, with `dropped` points removed from
+    /// the front and `appended` points added at the back
+    pub fn can_append(&self, segment_plot: &SegmentData, dropped: usize, appended: usize) -> bool {
+        dropped <= self.points.len()
+            && self.points.len() - dropped + appended == segment_plot.data.len()
+    }
+
+    /// Forgets the first `dropped` points, returning the number of quads to remove from the front
+    /// of the mesh
+    pub fn drop_front(&mut self, dropped: usize) -> usize {
+        let mut removed = self
+            .points
+            .drain(..dropped)
+            .filter(|point| *point == TailPoint::Quad)
+            .count();
+
+        // the first displayed point left no longer has a segment leading to it
+        match self.points.iter_mut().find(|point| **point != TailPoint::Skipped) {
+            Some(point) if *point == TailPoint::Quad => {
+                *point = TailPoint::Start;
+                removed += 1;
+            }
+            Some(_) => {}
+            None => self.last = None,
+        }
+
+        removed
+    }
+
+    /// Quads of the segments going through `points`, which follow the drawn ones
+    pub fn append(&mut self, plot: &Plot, segment_plot: &SegmentData, points: &[Vec2]) -> SegmentQuads {
+        let secondary_map = plot.secondary_map(segment_plot.secondary);
+
+        let mut quads = SegmentQuads {
+            arc_length: self.arc_length,
+            previous_end: self.last,
+            ..Default::default()
+        };
+
+        for point in points.iter() {
+            let point = secondary_map.to_primary(*point);
+
+            if !plot.is_displayable(point) {
+                self.points.push_back(TailPoint::Skipped);
+                continue;
+            }
+
+            let position = plot.to_local(point);
+            let added = match self.last {
+                Some(last) => quads.push(last, position),
+                None => false,
+            };
+
+            self.points.push_back(if added { TailPoint::Quad } else { TailPoint::Start });
+            self.last = Some(position);
+        }
+
+        self.arc_length = quads.arc_length;
+
+        quads
+    }
+}
+
+// Attributes of the segment meshes, interleaved in the order of their ids, see `specialize`
+const ATTRIBUTE_ENDS: MeshVertexAttribute =
+    MeshVertexAttribute::new("Ends", 1, VertexFormat::Float32x4);
+const ATTRIBUTE_CONTROLS: MeshVertexAttribute =
+    MeshVertexAttribute::new("Vertext_Control", 3, VertexFormat::Float32x4);
+const ATTRIBUTE_ARC_LENGTHS: MeshVertexAttribute =
+    MeshVertexAttribute::new("Arc_Length", 4, VertexFormat::Float32x2);
+
+/// Vertex attributes of the quads of a segment mesh, one quad per segment, as expected by segments.wgsl
+#[derive(Default)]
+pub(crate) struct SegmentQuads {
+    positions: Vec<[f32; 3]>,
+    ends: Vec<[f32; 4]>,
+    uvs: Vec<[f32; 2]>,
+    controls: Vec<[f32; 4]>,
+    arc_lengths: Vec<[f32; 2]>,
+
+    // arc length at the end of the last segment, which keeps the dash pattern continuous
+    // across consecutive segments
+    arc_length: f32,
+    previous_end: Option<Vec2>,
+}
+
+impl SegmentQuads {
+    pub fn len(&self) -> usize {
+        self.positions.len() / 4
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Adds the quad of the segment from `y0` to `y1`, unless the segment is degenerate. Returns true
+    /// if a quad was added.
+    pub fn push(&mut self, y0: Vec2, y1: Vec2) -> bool {
+        // degenerate segments have no direction
+        if y0 == y1 {
+            return false;
         }
 
-        let mut mesh_pos_attributes: Vec<[f32; 3]> = Vec::new();
-        // let mut normals = Vec::new();
-        // TODO: z position is here
-        for position in mesh0 {
-            mesh_pos_attributes.push([position.x, position.y, 0.0]);
-            // normals.push([0.0, 0.0, 1.0]);
+        // disjoint segments start a new pattern
+        if self.previous_end != Some(y0) {
+            self.arc_length = 0.0;
+        }
+
+        let line_width = 5.0;
+
+        let segment_length = (y1 - y0).length();
+        let dy = (y1 - y0).normalize();
+        let n = Vec2::new(-dy.y, dy.x);
+
+        // overlapping segments
+        let p0 = y0 + n * line_width - dy * line_width * 1.0;
+        let p1 = y0 - n * line_width - dy * line_width * 1.0;
+        let p2 = y1 + n * line_width + dy * line_width * 1.0;
+        let p3 = y1 - n * line_width + dy * line_width * 1.0;
+
+        for p in [p0, p1, p2, p3] {
+            // TODO: z position is here
+            self.positions.push([p.x, p.y, 0.0]);
+            self.uvs.push([p.x, p.y]);
+            self.ends.push([y0.x, y0.y, y1.x, y1.y]);
+            self.controls.push([p0.x, p0.y, p1.x, p1.y]);
+            self.arc_lengths.push([self.arc_length, segment_length]);
         }
 
+        self.arc_length += segment_length;
+        self.previous_end = Some(y1);
+
+        true
+    }
+
+    pub fn into_mesh(self) -> Mesh {
         let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
 
-        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, mesh_pos_attributes.clone());
-
-        let mva_ends = MeshVertexAttribute::new("Ends", 1, VertexFormat::Float32x4);
-        mesh.insert_attribute(mva_ends, ends);
-
-        mesh.set_indices(Some(Indices::U32(inds)));
-        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, mesh_attr_uvs);
-
-        let mva_controls = MeshVertexAttribute::new("Vertext_Control", 3, VertexFormat::Float32x4);
-        mesh.insert_attribute(mva_controls, mesh_attr_controls);
-
-        commands
-            .spawn_bundle((
-                SegmentMesh2d::default(),
-                Mesh2dHandle(meshes.add(mesh)),
-                GlobalTransform::default(),
-                Transform::from_translation(plot.canvas_position.extend(1.11)),
-                Visibility::default(),
-                ComputedVisibility::default(),
-            ))
-            .insert(plot_handle.clone())
-            .insert(SegmentUniform {
-                mech: if segment_plot.mech { 1.0 } else { 0.0 },
-                segment_thickness: segment_plot.size,
-                hole_size: 1.0,
-                zoom: 1.0,
-                color: col_to_vec4(segment_plot.color),
-                inner_canvas_size_in_pixels: plot.canvas_size / (1.0 + plot.outer_border),
-                canvas_position: plot.canvas_position,
-            });
+        mesh.set_indices(Some(Indices::U32(quad_indices(0..self.len()))));
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, self.positions);
+        mesh.insert_attribute(ATTRIBUTE_ENDS, self.ends);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, self.uvs);
+        mesh.insert_attribute(ATTRIBUTE_CONTROLS, self.controls);
+        mesh.insert_attribute(ATTRIBUTE_ARC_LENGTHS, self.arc_lengths);
+
+        mesh
+    }
+
+    /// Removes the first `removed` quads of `mesh`, a mesh built by [`SegmentQuads::into_mesh`], and
+    /// appends these quads to it. Returns the number of quads of the mesh.
+    pub fn splice_into(self, mesh: &mut Mesh, removed: usize) -> usize {
+        let added = self.len();
+        let removed_vertices = 4 * removed;
+
+        splice_attribute(
+            mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION),
+            removed_vertices,
+            self.positions.into(),
+        );
+        splice_attribute(mesh.attribute_mut(ATTRIBUTE_ENDS), removed_vertices, self.ends.into());
+        splice_attribute(mesh.attribute_mut(Mesh::ATTRIBUTE_UV_0), removed_vertices, self.uvs.into());
+        splice_attribute(
+            mesh.attribute_mut(ATTRIBUTE_CONTROLS),
+            removed_vertices,
+            self.controls.into(),
+        );
+        splice_attribute(
+            mesh.attribute_mut(ATTRIBUTE_ARC_LENGTHS),
+            removed_vertices,
+            self.arc_lengths.into(),
+        );
+
+        let num_quads = mesh.count_vertices() / 4;
+
+        // every quad has the same indices relative to its first vertex
+        if let Some(Indices::U32(indices)) = mesh.indices_mut() {
+            let kept = num_quads - added;
+            indices.truncate(6 * kept);
+            indices.extend(quad_indices(kept..num_quads));
+        }
+
+        num_quads
+    }
+}
+
+// Removes the first `removed` values of a vertex attribute and appends `new` values of the same format
+fn splice_attribute(
+    values: Option<&mut VertexAttributeValues>,
+    removed: usize,
+    new: VertexAttributeValues,
+) {
+    match (values, new) {
+        (Some(VertexAttributeValues::Float32x2(values)), VertexAttributeValues::Float32x2(new)) => {
+            values.drain(..removed.min(values.len()));
+            values.extend(new);
+        }
+        (Some(VertexAttributeValues::Float32x3(values)), VertexAttributeValues::Float32x3(new)) => {
+            values.drain(..removed.min(values.len()));
+            values.extend(new);
+        }
+        (Some(VertexAttributeValues::Float32x4(values)), VertexAttributeValues::Float32x4(new)) => {
+            values.drain(..removed.min(values.len()));
+            values.extend(new);
+        }
+        _ => {}
+    }
+}
+
+// Indices of the two triangles of each quad in `quads`
+fn quad_indices(quads: std::ops::Range<usize>) -> Vec<u32> {
+    quads
+        .flat_map(|k| {
+            let ki = 4 * k as u32;
+            [ki, ki + 1, ki + 2, ki + 3, ki + 2, ki + 1]
+        })
+        .collect()
+}
+
+/// Builds one quad per segment, each pair containing the ends of a segment, with the vertex
+/// attributes expected by segments.wgsl. Returns None if there is no segment to draw.
+pub(crate) fn make_segment_mesh(pairs: &[(Vec2, Vec2)]) -> Option<Mesh> {
+    let mut quads = SegmentQuads::default();
+    for (y0, y1) in pairs.iter() {
+        quads.push(*y0, *y1);
+    }
+
+    if quads.is_empty() {
+        None
+    } else {
+        Some(quads.into_mesh())
     }
 }
 
@@ -231,6 +501,10 @@ pub(crate) struct SegmentUniform {
     pub zoom: f32,
     pub inner_canvas_size_in_pixels: Vec2,
     pub canvas_position: Vec2,
+    /// see [`LineStyle::to_int32`]
+    pub style: i32,
+    /// length of the dashes in pixels
+    pub dash_length: f32,
 }
 
 struct SegmentMesh2dPipeline {
@@ -295,40 +569,25 @@ impl SpecializedMeshPipeline for SegmentMesh2dPipeline {
     fn specialize(
         &self,
         key: Self::Key,
-        layout: &MeshVertexBufferLayout,
+        _layout: &MeshVertexBufferLayout,
     ) -> Result<RenderPipelineDescriptor, SpecializedMeshPipelineError> {
-        // Customize how to store the meshes' vertex attributes in the vertex buffer
-        // Our meshes only have position and color
-        let vertex_attributes = vec![
-            // Position (GOTCHA! Vertex_Position isn't first in the buffer due to how Mesh sorts attributes (alphabetically))
-            VertexAttribute {
-                format: VertexFormat::Float32x3,
-                // this offset is the size of the color attribute, which is stored first
-                offset: 16,
-                // position is available at location 0 in the shader
-                shader_location: 0,
-            },
-            // Color
-            VertexAttribute {
-                format: VertexFormat::Float32x4,
-                offset: 0,
-                shader_location: 1,
-            },
-            // uv
-            VertexAttribute {
-                format: VertexFormat::Float32x2,
-                offset: 28,
-                shader_location: 2,
-            },
-            // Control Point
-            VertexAttribute {
-                format: VertexFormat::Float32x4,
-                offset: 36,
-                shader_location: 3,
-            },
+        // Customize how to store the meshes' vertex attributes in the vertex buffer.
+        // The attributes are interleaved in the order of their ids.
+        let formats = vec![
+            // Position
+            VertexFormat::Float32x3,
+            // Ends
+            VertexFormat::Float32x4,
+            // UV
+            VertexFormat::Float32x2,
+            // Controls
+            VertexFormat::Float32x4,
+            // Arc length at the start of the segment and length of the segment
+            VertexFormat::Float32x2,
         ];
-        // This is the sum of the size of position, color uv attributes (12 + 16 + 8 + 8 = 44)
-        let vertex_array_stride = 52;
+
+        let vertex_layout =
+            VertexBufferLayout::from_vertex_formats(VertexStepMode::Vertex, formats);
 
         Ok(RenderPipelineDescriptor {
             vertex: VertexState {
@@ -337,11 +596,7 @@ impl SpecializedMeshPipeline for SegmentMesh2dPipeline {
                 entry_point: "vertex".into(),
                 shader_defs: Vec::new(),
                 // Use our custom vertex buffer
-                buffers: vec![VertexBufferLayout {
-                    array_stride: vertex_array_stride,
-                    step_mode: VertexStepMode::Vertex,
-                    attributes: vertex_attributes,
-                }],
+                buffers: vec![vertex_layout],
             },
             fragment: Some(FragmentState {
                 // Use our custom shader
@@ -18,6 +18,7 @@ struct Vertex {
     [[location(1)]] ends: vec4<f32>;
     [[location(2)]] uv: vec2<f32>;
     [[location(3)]] control: vec4<f32>;
+    [[location(4)]] arc: vec2<f32>;
 };
 
 struct VertexOutput {
@@ -27,6 +28,7 @@ struct VertexOutput {
     [[location(0)]] ends: vec4<f32>;
     [[location(1)]] uv: vec2<f32>;
     [[location(2)]] control: vec4<f32>;
+    [[location(3)]] arc: vec2<f32>;
 };
 
 [[stage(vertex)]]
@@ -39,6 +41,7 @@ fn vertex(vertex: Vertex) -> VertexOutput {
     out.ends = vertex.ends;
     out.uv = vertex.uv;
     out.control = vertex.control;
+    out.arc = vertex.arc;
 
 
 
@@ -69,6 +72,8 @@ struct FragmentInput {
     [[location(0)]] ends: vec4<f32>;
     [[location(1)]] uv: vec2<f32>;
     [[location(2)]] control: vec4<f32>;
+    // arc length at the start of the segment and length of the segment
+    [[location(3)]] arc: vec2<f32>;
 };
 
 fn cla(mi: f32, ma: f32, x: f32) -> f32 {
@@ -115,6 +120,42 @@ fn sdRoundedBox(p: vec2<f32>, b: vec2<f32>, r: vec4<f32>) -> f32 {
   return min(max(q.x, q.y), 0.) + length(max(q, vec2<f32>(0.))) - x;
 }
 
+// Distance along the line between the arc length s and the closest dash or dot of the
+// line style (1: dashed, 2: dotted, 3: dash-dot, 4: dash-dot-dot). Dots are dashes of
+// zero length. The gaps account for the round caps of radius r.
+fn dash_distance(s: f32, style: i32, dash_length: f32, r: f32) -> f32 {
+    let gap = 0.5 * dash_length + 2.0 * r;
+
+    if (style == 2) {
+        let dot_gap = dash_length / 3.0 + 2.0 * r;
+        let m = s - floor(s / dot_gap) * dot_gap;
+        return min(m, dot_gap - m);
+    }
+
+    var num_dots = 0.0;
+    if (style == 3) {
+        num_dots = 1.0;
+    }
+    if (style == 4) {
+        num_dots = 2.0;
+    }
+
+    let period = dash_length + gap * (1.0 + num_dots);
+    let m = s - floor(s / period) * period;
+
+    // the dash covers [0, dash_length] and the next one starts at the end of the period
+    var d = min(max(m - dash_length, 0.0), period - m);
+
+    if (num_dots > 0.5) {
+        d = min(d, abs(m - dash_length - gap));
+    }
+    if (num_dots > 1.5) {
+        d = min(d, abs(m - dash_length - 2.0 * gap));
+    }
+
+    return d;
+}
+
 // fn sdBox(p: vec2<f32>, b: vec2<f32>) -> f32 {
 //   let d = (abs(p) - b) ;
 //   return length(max(d, vec2<f32>(0.))) + min(max(d.x, d.y), 0.);
@@ -129,6 +170,8 @@ struct SegmentUniform {
     zoom: f32;
     inner_canvas_size_in_pixels: float2;
     canvas_position_in_pixels: float2;    
+    style: i32;
+    dash_length: f32;
 };
 
 [[group(2), binding(0)]]
@@ -167,8 +210,16 @@ fn fragment(in: FragmentInput) -> [[location(0)]] vec4<f32> {
     }
 
 
-    let d = sdSegment(in.uv, y0, y1) ;
-    let s = smoothStep(solid, solid + w, d);
+    var dist = sdSegment(in.uv, y0, y1) ;
+
+    // dashed and dotted styles
+    if (uni.style > 0) {
+        let arc_length = in.arc.x + clamp(dot(in.uv - y0, dy), 0.0, in.arc.y);
+        let along = dash_distance(arc_length, uni.style, uni.dash_length, solid + 0.5 * w);
+        dist = length(float2(dist, along));
+    }
+
+    let s = smoothStep(solid, solid + w, dist);
     out_col = out_col * (1.0 - s);
 
 
@@ -0,0 +1,3 @@
+pub mod stream;
+#[allow(unused_imports)]
+pub use stream::*;
@@ -0,0 +1,210 @@
+use bevy::{prelude::*, sprite::Mesh2dHandle};
+
+use crate::bezier::*;
+use crate::canvas::*;
+use crate::markers::*;
+use crate::plot::*;
+use crate::segments::*;
+
+/// Points appended to a segment group and dropped from its front since it was last drawn
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct SegmentPush {
+    pub appended: usize,
+    pub dropped: usize,
+}
+
+/// Groups of a [`Plot`] that received points since they were last drawn, see
+/// [`Plot::push_segment_points`] and [`Plot::push_marker_points`].
+#[derive(Debug, Clone, Default)]
+pub(crate) struct StreamUpdates {
+    /// Indices of the updated groups in `data.segment_groups`, with the points pushed to each group,
+    /// or None if points were edited and the group is redrawn from scratch
+    pub segments: Vec<(usize, Option<SegmentPush>)>,
+
+    /// Indices of the updated groups in `data.marker_groups`
+    pub markers: Vec<usize>,
+
+    /// True if the x axis scrolled to follow the latest point, in which case every group is redrawn
+    pub scrolled: bool,
+}
+
+impl StreamUpdates {
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty() && self.markers.is_empty() && !self.scrolled
+    }
+
+    /// Records that `appended` points were pushed to a segment group, and that `dropped` points were
+    /// removed from its front
+    pub fn push_segments(&mut self, group: usize, appended: usize, dropped: usize) {
+        match self.segments.iter_mut().find(|(k, _)| *k == group) {
+            Some((_, Some(push))) => {
+                push.appended += appended;
+                push.dropped += dropped;
+            }
+            Some((_, None)) => {}
+            None => self
+                .segments
+                .push((group, Some(SegmentPush { appended, dropped }))),
+        }
+    }
+
+    /// Records that points of a segment group were edited
+    pub fn redraw_segments(&mut self, group: usize) {
+        match self.segments.iter_mut().find(|(k, _)| *k == group) {
+            Some((_, push)) => *push = None,
+            None => self.segments.push((group, None)),
+        }
+    }
+
+    pub fn redraw_markers(&mut self, group: usize) {
+        if !self.markers.contains(&group) {
+            self.markers.push(group);
+        }
+    }
+}
+
+type SegmentGroupQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Handle<Plot>,
+        &'static SegmentGroup,
+        &'static Mesh2dHandle,
+        &'static mut SegmentTail,
+        &'static mut Visibility,
+    ),
+>;
+
+// Redraws the groups that received streamed points without despawning any entity. The quads of the
+// pushed points are appended to the mesh of their segment group, and the quads of the dropped points
+// are removed from it, while the instances of a marker group are all recomputed. A group without an
+// entity, which was added to the plot after it was spawned, gets one. When the x axis scrolled, the
+// positions of all the points change: every group is rebuilt, every function is respawned, and the
+// whole plot is respawned if it contains groups that are not redrawn in place (error bars, bars,
+// heatmaps and implicit curves).
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn update_streamed_groups(
+    mut commands: Commands,
+    mut plots: ResMut<Assets<Plot>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut canvas_materials: ResMut<Assets<CanvasMaterial>>,
+    canvas_query: Query<(Entity, &Handle<Plot>, &Handle<CanvasMaterial>)>,
+    mut segment_query: SegmentGroupQuery,
+    mut marker_query: Query<(&Handle<Plot>, &MarkerGroup, &mut MarkerInstanceMatData)>,
+    mut update_plot_labels_event: EventWriter<UpdatePlotLabelsEvent>,
+    mut respawn_all_event: EventWriter<RespawnAllEvent>,
+    mut spawn_beziercurve_event: EventWriter<SpawnBezierCurveEvent>,
+) {
+    for (canvas_entity, plot_handle, material_handle) in canvas_query.iter() {
+        let plot = match plots.get_mut(plot_handle) {
+            Some(plot) if !plot.stream_updates.is_empty() => plot,
+            _ => continue,
+        };
+
+        let mut updates = std::mem::take(&mut plot.stream_updates);
+        plot.compute_zeros();
+
+        if updates.scrolled {
+            if let Some(canvas_material) = canvas_materials.get_mut(material_handle) {
+                canvas_material.update_all(plot);
+            }
+
+            update_plot_labels_event.send(UpdatePlotLabelsEvent {
+                plot_handle: plot_handle.clone(),
+                canvas_entity,
+            });
+
+            for (k, bezier_curve) in plot.data.bezier_groups.iter().enumerate() {
+                // So as to not spawn twice when show_animation is turned on
+                if !bezier_curve.show_animation {
+                    spawn_beziercurve_event.send(SpawnBezierCurveEvent {
+                        group_number: k,
+                        plot_handle: plot_handle.clone(),
+                    });
+                }
+            }
+
+            let data = &plot.data;
+            if !data.errorbar_groups.is_empty()
+                || !data.bar_groups.is_empty()
+                || !data.heatmap_groups.is_empty()
+                || !data.implicit_groups.is_empty()
+            {
+                respawn_all_event.send(RespawnAllEvent {
+                    plot_handle: plot_handle.clone(),
+                });
+                continue;
+            }
+
+            // the positions of all the points changed
+            updates.segments = (0..plot.data.segment_groups.len())
+                .map(|k| (k, None))
+                .collect();
+            updates.markers = (0..plot.data.marker_groups.len()).collect();
+        }
+
+        for &(k, push) in updates.segments.iter() {
+            let segment_plot = match plot.data.segment_groups.get(k) {
+                Some(segment_plot) if !segment_plot.hidden => segment_plot,
+                _ => continue,
+            };
+
+            let entity = segment_query
+                .iter_mut()
+                .find(|(handle, group, ..)| *handle == plot_handle && group.0 == k);
+
+            let (mesh_handle, mut tail, mut visibility) = match entity {
+                Some((_, _, mesh_handle, tail, visibility)) => (mesh_handle, tail, visibility),
+                None => {
+                    spawn_segment_group(&mut commands, &mut meshes, plot, plot_handle, k);
+                    continue;
+                }
+            };
+
+            let mesh = match meshes.get_mut(mesh_handle.0.clone()) {
+                Some(mesh) => mesh,
+                None => continue,
+            };
+
+            // the points are appended unless some of the drawn ones were edited
+            let push =
+                push.filter(|push| tail.can_append(segment_plot, push.dropped, push.appended));
+
+            let num_quads = match push {
+                Some(push) => {
+                    let removed = tail.drop_front(push.dropped);
+                    let pushed = &segment_plot.data[segment_plot.data.len() - push.appended..];
+                    tail.append(plot, segment_plot, pushed)
+                        .splice_into(mesh, removed)
+                }
+                None => {
+                    let (quads, new_tail) = SegmentTail::build(plot, segment_plot);
+                    *tail = new_tail;
+
+                    let num_quads = quads.len();
+                    *mesh = quads.into_mesh();
+                    num_quads
+                }
+            };
+
+            visibility.is_visible = num_quads > 0;
+        }
+
+        for &k in updates.markers.iter() {
+            let marker_plot = match plot.data.marker_groups.get(k) {
+                Some(marker_plot) if !marker_plot.hidden => marker_plot,
+                _ => continue,
+            };
+
+            let instances = marker_query
+                .iter_mut()
+                .find(|(handle, group, _)| *handle == plot_handle && group.0 == k)
+                .map(|(_, _, instances)| instances);
+
+            match instances {
+                Some(mut instances) => instances.0 = marker_group_instances(plot, marker_plot),
+                None => spawn_marker_group(&mut commands, &mut meshes, plot, plot_handle, k),
+            }
+        }
+    }
+}
@@ -0,0 +1,9 @@
+pub mod picking;
+pub mod point_index;
+pub mod tooltip;
+#[allow(unused_imports)]
+pub use picking::*;
+#[allow(unused_imports)]
+pub use point_index::*;
+#[allow(unused_imports)]
+pub use tooltip::*;
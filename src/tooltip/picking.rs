@@ -0,0 +1,106 @@
+use bevy::prelude::*;
+
+use crate::canvas::*;
+use crate::inputs::*;
+use crate::plot::*;
+
+use super::point_index::*;
+
+/// Sent when a marker or a point of a regular plot is clicked with the left mouse button.
+/// The point is the one closest to the cursor within the `pick_radius` of the [`Plot`].
+#[derive(Debug, Clone)]
+pub struct PlotPointClicked {
+    pub plot: Handle<Plot>,
+
+    /// Either [`DataKind::Marker`] or [`DataKind::Segment`]
+    pub group_kind: DataKind,
+
+    /// Index of the group in `data.marker_groups` or `data.segment_groups`
+    pub group_index: usize,
+
+    /// Index of the point in the `data` field of its group
+    pub point_index: usize,
+
+    /// Coordinates of the point
+    pub value: Vec2,
+}
+
+/// Sent each time the cursor moves over a curve of a regular plot or of a function, or over one
+/// of their points, within the `pick_radius` of the [`Plot`]. No event is sent while a mouse button is pressed.
+#[derive(Debug, Clone)]
+pub struct PlotCurveHovered {
+    pub plot: Handle<Plot>,
+
+    /// Either [`DataKind::Segment`] or [`DataKind::Function`]
+    pub group_kind: DataKind,
+
+    /// Index of the group in `data.segment_groups` or `data.bezier_groups`
+    pub group_index: usize,
+
+    /// Point of the curve closest to the cursor, read on the axes of the group
+    pub position: Vec2,
+}
+
+// Sends the picking events of the data elements of the plot under the cursor
+pub(crate) fn send_picking_events(
+    mut plots: ResMut<Assets<Plot>>,
+    cursor: Res<Cursor>,
+    mouse_button_input: Res<Input<MouseButton>>,
+    canvas_query: Query<(Entity, &Canvas, &GlobalTransform, &Handle<Plot>)>,
+    mut point_clicked_event: EventWriter<PlotPointClicked>,
+    mut curve_hovered_event: EventWriter<PlotCurveHovered>,
+    mut last_cursor_position: Local<Option<Vec2>>,
+) {
+    let clicked = mouse_button_input.just_pressed(MouseButton::Left);
+    let moved = *last_cursor_position != Some(cursor.position);
+    *last_cursor_position = Some(cursor.position);
+
+    let pressed = mouse_button_input.get_pressed().next().is_some();
+    let hovering = moved && !pressed;
+
+    if !clicked && !hovering {
+        return;
+    }
+
+    let hovered = hovered_canvas(
+        canvas_query
+            .iter()
+            .map(|(entity, canvas, transform, _)| (entity, canvas, transform)),
+        cursor.position,
+    );
+
+    let plot_handle = match hovered.and_then(|entity| canvas_query.get(entity).ok()) {
+        Some((_, _, _, plot_handle)) => plot_handle,
+        None => return,
+    };
+
+    let plot = match plots.get_mut(plot_handle) {
+        Some(plot) => plot,
+        None => return,
+    };
+
+    let radius = plot.pick_radius;
+    let element = match plot.nearest_data_element(cursor.position, radius) {
+        Some(element) => element,
+        None => return,
+    };
+
+    if let (true, Some(point_index)) = (clicked, element.point_index) {
+        point_clicked_event.send(PlotPointClicked {
+            plot: plot_handle.clone(),
+            group_kind: element.group_kind,
+            group_index: element.group_index,
+            point_index,
+            value: element.value,
+        });
+    }
+
+    if hovering && element.group_kind != DataKind::Marker {
+        curve_hovered_event.send(PlotCurveHovered {
+            plot: plot_handle.clone(),
+            group_kind: element.group_kind,
+            group_index: element.group_index,
+            position: element.value,
+        });
+    }
+}
@@ -0,0 +1,411 @@
+use bevy::prelude::*;
+use itertools_num::linspace;
+use std::collections::HashMap;
+
+use crate::plot::*;
+
+// Side of the cells of the grid in pixels
+const CELL_SIZE: f32 = 32.0;
+
+/// Kind of group a data element belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataKind {
+    /// One of the `data.marker_groups` of a [`Plot`]
+    Marker,
+
+    /// One of the `data.segment_groups` of a [`Plot`]
+    Segment,
+
+    /// One of the `data.bezier_groups` of a [`Plot`], which has no data points
+    Function,
+}
+
+/// Data point or point on a curve of a [`Plot`], see [`Plot::nearest_data_element`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataElement {
+    /// Kind of the group of the element
+    pub group_kind: DataKind,
+
+    /// Index of the group in `data.marker_groups`, `data.segment_groups` or `data.bezier_groups`
+    pub group_index: usize,
+
+    /// Index of the point in the `data` field of its group, or None if the element lies on a curve
+    /// between two data points or on a function
+    pub point_index: Option<usize>,
+
+    /// Coordinates of the data point, or of the closest point on the curve, read on the axes of the group
+    pub value: Vec2,
+
+    /// Distance between the element and the queried position in pixels
+    pub distance: f32,
+}
+
+/// A displayed data point of a plot
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct IndexedPoint {
+    pub kind: DataKind,
+
+    /// Index of the group in `data.marker_groups` or `data.segment_groups`
+    pub group: usize,
+
+    /// Index of the point in the `data` field of its group
+    pub index: usize,
+
+    /// Position of the point in world coordinates modulo the canvas position
+    pub position: Vec2,
+}
+
+/// A straight piece of a displayed curve, between two data points or two samples of a function
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct IndexedSegment {
+    pub kind: DataKind,
+
+    /// Index of the group in `data.segment_groups` or `data.bezier_groups`
+    pub group: usize,
+
+    /// Ends of the piece in world coordinates modulo the canvas position
+    pub start: Vec2,
+    pub end: Vec2,
+}
+
+/// Uniform grid over the data points and curves of a [`Plot`], for finding the element closest to
+/// the cursor without going through the whole data. Hidden groups and elements outside of the canvas
+/// are left out, and so are functions animated with `show_animation`.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PointIndex {
+    /// Indices in `points` of the points lying in each cell
+    cells: HashMap<(i32, i32), Vec<usize>>,
+
+    points: Vec<IndexedPoint>,
+
+    /// Indices in `segments` of the pieces of curves crossing each cell
+    segment_cells: HashMap<(i32, i32), Vec<usize>>,
+
+    segments: Vec<IndexedSegment>,
+}
+
+impl PointIndex {
+    /// Indexes the points and curves of `plot`. Marker groups come first, so that a marker wins over
+    /// a point of a regular plot at the same position.
+    pub fn new(plot: &Plot) -> Self {
+        let mut index = PointIndex::default();
+
+        for (k, group) in plot.data.marker_groups.iter().enumerate() {
+            if !group.hidden {
+                index.insert_points(plot, DataKind::Marker, k, &group.data, group.secondary);
+            }
+        }
+
+        for (k, group) in plot.data.segment_groups.iter().enumerate() {
+            if group.hidden || group.line_style == LineStyle::None {
+                continue;
+            }
+            index.insert_points(plot, DataKind::Segment, k, &group.data, group.secondary);
+
+            let secondary_map = plot.secondary_map(group.secondary);
+            let curve = group
+                .data
+                .iter()
+                .map(|p| secondary_map.to_primary(*p))
+                .filter(|p| plot.is_displayable(*p))
+                .map(|p| plot.to_local(p))
+                .collect::<Vec<Vec2>>();
+            index.insert_curve(plot, DataKind::Segment, k, &curve);
+        }
+
+        for (k, group) in plot.data.bezier_groups.iter().enumerate() {
+            if group.hidden || group.show_animation || group.line_style == LineStyle::None {
+                continue;
+            }
+            let curve = sample_function(plot, group);
+            index.insert_curve(plot, DataKind::Function, k, &curve);
+        }
+
+        index
+    }
+
+    fn insert_points(
+        &mut self,
+        plot: &Plot,
+        kind: DataKind,
+        group: usize,
+        data: &[Vec2],
+        secondary: BVec2,
+    ) {
+        let bounds_world = plot.compute_bounds_world();
+        let secondary_map = plot.secondary_map(secondary);
+
+        for (k, point) in data.iter().enumerate() {
+            let point = secondary_map.to_primary(*point);
+            if !plot.is_displayable(point) {
+                continue;
+            }
+
+            let position = plot.to_local(point);
+            if position.cmplt(bounds_world.lo).any() || position.cmpgt(bounds_world.up).any() {
+                continue;
+            }
+
+            self.cells
+                .entry(cell(position))
+                .or_default()
+                .push(self.points.len());
+
+            self.points.push(IndexedPoint {
+                kind,
+                group,
+                index: k,
+                position,
+            });
+        }
+    }
+
+    // Adds the pieces of the polyline `curve` to the cells that their bounding box overlaps within the canvas
+    fn insert_curve(&mut self, plot: &Plot, kind: DataKind, group: usize, curve: &[Vec2]) {
+        let bounds_world = plot.compute_bounds_world();
+
+        for w in curve.windows(2) {
+            let (start, end) = (w[0], w[1]);
+            if !start.is_finite() || !end.is_finite() {
+                continue;
+            }
+
+            let lo = start.min(end).max(bounds_world.lo);
+            let up = start.max(end).min(bounds_world.up);
+            if lo.cmpgt(up).any() {
+                continue;
+            }
+
+            let (lo_x, lo_y) = cell(lo);
+            let (up_x, up_y) = cell(up);
+
+            for x in lo_x..=up_x {
+                for y in lo_y..=up_y {
+                    self.segment_cells
+                        .entry((x, y))
+                        .or_default()
+                        .push(self.segments.len());
+                }
+            }
+
+            self.segments.push(IndexedSegment {
+                kind,
+                group,
+                start,
+                end,
+            });
+        }
+    }
+
+    // Ids of the elements in the cells overlapping the square of half side `radius` around `position`
+    fn ids_around<'a>(
+        cells: &'a HashMap<(i32, i32), Vec<usize>>,
+        position: Vec2,
+        radius: f32,
+    ) -> impl Iterator<Item = usize> + 'a {
+        let (lo_x, lo_y) = cell(position - radius);
+        let (up_x, up_y) = cell(position + radius);
+
+        (lo_x..=up_x)
+            .flat_map(move |x| (lo_y..=up_y).map(move |y| (x, y)))
+            .filter_map(move |key| cells.get(&key))
+            .flatten()
+            .copied()
+    }
+
+    /// The point closest to `position` within a distance of `radius` pixels, `position` being in
+    /// world coordinates modulo the canvas position. Among equally close points, the first one
+    /// indexed is returned.
+    pub fn nearest(&self, position: Vec2, radius: f32) -> Option<&IndexedPoint> {
+        if !position.is_finite() || !radius.is_finite() || radius < 0.0 {
+            return None;
+        }
+
+        Self::ids_around(&self.cells, position, radius)
+            .map(|id| (self.points[id].position.distance_squared(position), id))
+            .filter(|(distance, _)| *distance <= radius * radius)
+            .min_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(_, id)| &self.points[id])
+    }
+
+    /// The piece of curve closest to `position` within a distance of `radius` pixels, together with
+    /// the closest point on it
+    pub fn nearest_segment(&self, position: Vec2, radius: f32) -> Option<(&IndexedSegment, Vec2)> {
+        if !position.is_finite() || !radius.is_finite() || radius < 0.0 {
+            return None;
+        }
+
+        Self::ids_around(&self.segment_cells, position, radius)
+            .map(|id| {
+                let segment = &self.segments[id];
+                let closest = closest_on_segment(position, segment.start, segment.end);
+                (closest.distance_squared(position), id, closest)
+            })
+            .filter(|(distance, _, _)| *distance <= radius * radius)
+            .min_by(|a, b| {
+                (a.0, a.1)
+                    .partial_cmp(&(b.0, b.1))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .map(|(_, id, closest)| (&self.segments[id], closest))
+    }
+
+    /// The data point, or else the point on a curve, closest to `position` within `radius` pixels
+    pub fn nearest_element(&self, plot: &Plot, position: Vec2, radius: f32) -> Option<DataElement> {
+        if let Some(point) = self.nearest(position, radius) {
+            if let Some(value) = point_value(plot, point) {
+                return Some(DataElement {
+                    group_kind: point.kind,
+                    group_index: point.group,
+                    point_index: Some(point.index),
+                    value,
+                    distance: point.position.distance(position),
+                });
+            }
+        }
+
+        let (segment, closest) = self.nearest_segment(position, radius)?;
+        let secondary = match segment.kind {
+            DataKind::Segment => plot.data.segment_groups.get(segment.group)?.secondary,
+            DataKind::Function => plot.data.bezier_groups.get(segment.group)?.secondary,
+            DataKind::Marker => return None,
+        };
+
+        let value = plot
+            .secondary_map(secondary)
+            .to_secondary(plot.world_to_plot(closest + plot.canvas_position));
+
+        Some(DataElement {
+            group_kind: segment.kind,
+            group_index: segment.group,
+            point_index: None,
+            value,
+            distance: closest.distance(position),
+        })
+    }
+}
+
+fn cell(position: Vec2) -> (i32, i32) {
+    let cell = (position / CELL_SIZE).floor();
+    (cell.x as i32, cell.y as i32)
+}
+
+fn closest_on_segment(position: Vec2, start: Vec2, end: Vec2) -> Vec2 {
+    let direction = end - start;
+    let length_squared = direction.length_squared();
+    if length_squared == 0.0 {
+        return start;
+    }
+
+    let t = ((position - start).dot(direction) / length_squared).clamp(0.0, 1.0);
+    start + direction * t
+}
+
+// Samples of a function curve in world coordinates modulo the canvas position, taken like in the
+// legend at the time `plot.time`
+fn sample_function(plot: &Plot, curve: &BezierData) -> Vec<Vec2> {
+    let secondary_map = plot.secondary_map(curve.secondary);
+    let num_points = curve.num_points.max(2);
+
+    let points = match &curve.parametric {
+        Some(parametric) => {
+            let (t0, t1) = parametric.t_range;
+            linspace(t0, t1, num_points)
+                .map(|t| secondary_map.to_primary((parametric.function)(t, plot.time)))
+                .collect::<Vec<Vec2>>()
+        }
+        // in polar mode, explicit functions are r = f(θ) with θ going around a full circle
+        None if plot.polar => linspace(
+            0.0,
+            2.0 * std::f32::consts::PI,
+            plot.bezier_num_points.max(2),
+        )
+        .map(|theta| Vec2::new(theta, (curve.function)(theta, plot.time)))
+        .collect::<Vec<Vec2>>(),
+        None => {
+            let bounds = plot.scaled_bounds();
+            linspace(bounds.lo.x, bounds.up.x, num_points)
+                .map(|x| {
+                    let x = secondary_map
+                        .to_secondary(Vec2::new(plot.x_axis_scale.inverse(x), 0.0))
+                        .x;
+                    secondary_map.to_primary(Vec2::new(x, (curve.function)(x, plot.time)))
+                })
+                .collect::<Vec<Vec2>>()
+        }
+    };
+
+    points
+        .into_iter()
+        .filter(|p| plot.is_displayable(*p))
+        .map(|p| plot.to_local(p))
+        .collect()
+}
+
+/// Coordinates of an indexed point, or None if the point is no longer in the data of the plot
+pub(crate) fn point_value(plot: &Plot, point: &IndexedPoint) -> Option<Vec2> {
+    let data = match point.kind {
+        DataKind::Marker => &plot.data.marker_groups.get(point.group)?.data,
+        DataKind::Segment => &plot.data.segment_groups.get(point.group)?.data,
+        DataKind::Function => return None,
+    };
+
+    data.get(point.index).copied()
+}
+
+// The methods of `Plot` that change the bounds or the data drop the index themselves. A
+// `RespawnAllEvent` sent after editing the fields of a plot directly drops it as well, before the
+// tooltips and the picking events read it.
+pub(crate) fn invalidate_point_index(
+    mut plots: ResMut<Assets<Plot>>,
+    mut respawn_all_event: EventReader<RespawnAllEvent>,
+) {
+    for event in respawn_all_event.iter() {
+        if let Some(plot) = plots.get_mut(&event.plot_handle) {
+            plot.point_index = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nearest_element_within_the_pick_radius() {
+        let mut plot = Plot {
+            pick_radius: 10.0,
+            ..Default::default()
+        };
+
+        // a marker on top of the first point of a regular plot, and an isolated marker
+        plot.plotm(vec![Vec2::new(0.5, 0.5)]);
+        plot.plot(vec![Vec2::new(0.5, 0.5), Vec2::new(1.0, 1.0)]);
+        plot.plotm(vec![Vec2::new(0.2, 0.9)]);
+        plot.set_bounds(Vec2::ZERO, Vec2::new(1.2, 1.2));
+
+        let index = PointIndex::new(&plot);
+        let radius = plot.pick_radius;
+        let nearest = |position: Vec2| index.nearest_element(&plot, position, radius);
+
+        // markers win over the points of regular plots
+        let shared = nearest(plot.to_local(Vec2::new(0.5, 0.5))).unwrap();
+        assert_eq!(shared.group_kind, DataKind::Marker);
+        assert_eq!((shared.group_index, shared.point_index), (0, Some(0)));
+
+        let end = nearest(plot.to_local(Vec2::new(1.0, 1.0))).unwrap();
+        assert_eq!(end.group_kind, DataKind::Segment);
+        assert_eq!((end.group_index, end.point_index), (0, Some(1)));
+
+        // between two points, the closest point on the line is picked
+        let middle = nearest(plot.to_local(Vec2::new(0.75, 0.75))).unwrap();
+        assert_eq!((middle.group_kind, middle.point_index), (DataKind::Segment, None));
+
+        let isolated = plot.to_local(Vec2::new(0.2, 0.9));
+        let within = nearest(isolated + Vec2::new(0.9 * radius, 0.0)).unwrap();
+        assert_eq!((within.group_kind, within.group_index), (DataKind::Marker, 1));
+        assert!((within.distance - 0.9 * radius).abs() < 1e-3);
+
+        assert_eq!(nearest(isolated + Vec2::new(1.1 * radius, 0.0)), None);
+    }
+}
@@ -0,0 +1,199 @@
+use bevy::prelude::*;
+
+use crate::canvas::*;
+use crate::inputs::*;
+use crate::markers::*;
+use crate::plot::*;
+use crate::util::*;
+
+use super::point_index::*;
+
+const TOOLTIP_Z_PLANE: f32 = 1.25;
+const HIGHLIGHT_Z_PLANE: f32 = 1.13;
+const TOOLTIP_FONT_SIZE: f32 = 16.0;
+
+/// Text and highlighted marker of the data point under the cursor
+#[derive(Component)]
+pub(crate) struct Tooltip;
+
+// (canvas, kind, group, index) of the data point with a tooltip
+type ShownPoint = (Entity, DataKind, usize, usize);
+
+// Shows a tooltip next to the marker or segment point closest to the cursor, if it lies within
+// `pick_radius` pixels, and highlights the point with a larger marker. Only the canvas under the
+// cursor is searched, using its `PointIndex`, which is rebuilt once the bounds or the data changed.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn update_tooltips(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut plots: ResMut<Assets<Plot>>,
+    maybe_font: Res<TickLabelFont>,
+    cursor: Res<Cursor>,
+    mouse_button_input: Res<Input<MouseButton>>,
+    mut respawn_all_event: EventReader<RespawnAllEvent>,
+    canvas_query: Query<(Entity, &Canvas, &GlobalTransform, &Handle<Plot>)>,
+    tooltip_query: Query<Entity, With<Tooltip>>,
+    mut shown: Local<Option<ShownPoint>>,
+) {
+    // the points have moved or changed, so the tooltip is redrawn even if it shows the same point
+    let mut redraw = respawn_all_event.iter().count() > 0;
+
+    let hovered = hovered_canvas(
+        canvas_query
+            .iter()
+            .map(|(entity, canvas, transform, _)| (entity, canvas, transform)),
+        cursor.position,
+    );
+
+    let mut hit = None;
+
+    // no tooltip while panning or placing the target
+    let pressed = mouse_button_input.get_pressed().next().is_some();
+
+    if let (Some(canvas_entity), false) = (hovered, pressed) {
+        if let Ok((_, _, _, plot_handle)) = canvas_query.get(canvas_entity) {
+            if let Some(plot) = plots.get_mut(plot_handle) {
+                if plot.show_tooltips {
+                    redraw |= plot.update_point_index();
+
+                    let local = cursor.position - plot.canvas_position;
+                    hit = plot
+                        .point_index
+                        .as_ref()
+                        .and_then(|index| index.nearest(local, plot.pick_radius))
+                        // the data may have been edited without respawning the plot
+                        .filter(|point| point_value(plot, point).is_some())
+                        .map(|point| (canvas_entity, *point));
+                }
+            }
+        }
+    }
+
+    let key =
+        hit.map(|(canvas_entity, point)| (canvas_entity, point.kind, point.group, point.index));
+
+    if key == *shown && !redraw {
+        return;
+    }
+    *shown = key;
+
+    for entity in tooltip_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    if let Some((canvas_entity, point)) = hit {
+        if let Ok((_, _, _, plot_handle)) = canvas_query.get(canvas_entity) {
+            if let Some(plot) = plots.get(plot_handle) {
+                spawn_highlight(&mut commands, &mut meshes, plot, &point);
+                spawn_tooltip_text(&mut commands, &maybe_font, plot, canvas_entity, &point);
+            }
+        }
+    }
+}
+
+// A larger marker with a contour on top of the point
+fn spawn_highlight(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    plot: &Plot,
+    point: &IndexedPoint,
+) {
+    let (uniform, scale, color) = match point.kind {
+        DataKind::Marker => {
+            let group = &plot.data.marker_groups[point.group];
+            let color = group.point_colors()[point.index];
+            let mut uniform = MarkerUniform::new(plot, group);
+            uniform.contour = 1.0;
+            (uniform, group.point_size(point.index) * 1.5, color)
+        }
+        DataKind::Segment => {
+            let group = &plot.data.segment_groups[point.group];
+            let marker = MarkerData {
+                color: group.color,
+                marker_point_color: group.color,
+                size: 0.5,
+                draw_contour: true,
+                ..Default::default()
+            };
+            (MarkerUniform::new(plot, &marker), 1.0, group.color)
+        }
+        // functions have no data points
+        DataKind::Function => return,
+    };
+
+    let instance = MarkerInstanceData {
+        position: (plot.canvas_position + point.position).extend(0.0),
+        scale,
+        color: col_to_vec4(color).to_array(),
+    };
+
+    let entity =
+        spawn_marker_instances(commands, meshes, vec![instance], uniform, HIGHLIGHT_Z_PLANE);
+    commands.entity(entity).insert(Tooltip);
+}
+
+// The label of the group, the index of the point and its coordinates, as a child of the canvas
+fn spawn_tooltip_text(
+    commands: &mut Commands,
+    maybe_font: &Res<TickLabelFont>,
+    plot: &Plot,
+    canvas_entity: Entity,
+    point: &IndexedPoint,
+) {
+    let font = match plot.label_font(maybe_font) {
+        Some(font) => font.clone(),
+        None => return,
+    };
+
+    let label = match point.kind {
+        DataKind::Marker => &plot.data.marker_groups[point.group].label,
+        DataKind::Segment => &plot.data.segment_groups[point.group].label,
+        DataKind::Function => &plot.data.bezier_groups[point.group].label,
+    };
+    let value = point_value(plot, point).unwrap_or_default();
+
+    let mut text = match label {
+        Some(label) => format!("{} #{}", label, point.index),
+        None => format!("#{}", point.index),
+    };
+    text.push('\n');
+    text.push_str(&format_coordinates(plot, value));
+
+    // the tooltip is placed above and to the right of the point, unless it would leave the canvas
+    let offset = TOOLTIP_FONT_SIZE * 0.5;
+    let mut position = point.position + Vec2::splat(offset);
+
+    let mut text_alignment = TextAlignment {
+        vertical: VerticalAlign::Bottom,
+        horizontal: HorizontalAlign::Left,
+    };
+
+    let up = plot.canvas_size / 2.0;
+
+    if position.x > up.x - TOOLTIP_FONT_SIZE * 8.0 {
+        text_alignment.horizontal = HorizontalAlign::Right;
+        position.x -= 2.0 * offset;
+    }
+
+    if position.y > up.y - TOOLTIP_FONT_SIZE * 2.5 {
+        text_alignment.vertical = VerticalAlign::Top;
+        position.y -= 2.0 * offset;
+    }
+
+    let text_style = TextStyle {
+        font,
+        font_size: TOOLTIP_FONT_SIZE,
+        color: plot.tooltip_color,
+    };
+
+    let text_entity = commands
+        .spawn_bundle(Text2dBundle {
+            text: Text::with_section(text, text_style, text_alignment),
+            transform: Transform::from_translation(position.extend(TOOLTIP_Z_PLANE)),
+            ..Default::default()
+        })
+        .insert(Tooltip)
+        .id();
+
+    commands.entity(canvas_entity).push_children(&[text_entity]);
+}